@@ -1,5 +1,6 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
@@ -18,7 +19,7 @@ pub struct AbortBucketWormSync {
 
 impl AbortBucketWormSync {
     pub(super) fn new(oss: Oss, worm_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::DELETE);
+        let mut req = OssRequest::new(oss, Method::DELETE, "AbortBucketWorm");
         req.insert_query("wormId", worm_id.into());
         AbortBucketWormSync { req }
     }
@@ -26,10 +27,10 @@ impl AbortBucketWormSync {
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(self) -> Result<(), Error> {
+    pub fn send(self) -> Result<ResponseMeta, Error> {
         let response = self.req.send_to_oss()?;
         match response.status() {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
@@ -1,5 +1,6 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
@@ -18,7 +19,7 @@ pub struct CompleteBucketWormSync {
 
 impl CompleteBucketWormSync {
     pub(super) fn new(oss: Oss, worm_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "CompleteBucketWorm");
         req.insert_query("wormId", worm_id.into());
         req.insert_query("comp", "complete");
         CompleteBucketWormSync { req }
@@ -27,10 +28,10 @@ impl CompleteBucketWormSync {
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(self) -> Result<(), Error> {
+    pub fn send(self) -> Result<ResponseMeta, Error> {
         let response = self.req.send_to_oss()?;
         match response.status() {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
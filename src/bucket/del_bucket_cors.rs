@@ -1,5 +1,6 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error,
     request::{Oss, OssRequest},
 };
@@ -18,7 +19,7 @@ pub struct DelBucketCors {
 
 impl DelBucketCors {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::DELETE);
+        let mut req = OssRequest::new(oss, Method::DELETE, "DeleteBucketCors");
         req.insert_query("cors", "");
         DelBucketCors { req }
     }
@@ -26,11 +27,11 @@ impl DelBucketCors {
     /// Send the request.
     ///
     /// 发送请求。
-    pub async fn send(self) -> Result<(), Error> {
-        let response = self.req.send_to_oss()?.await?;
+    pub async fn send(self) -> Result<ResponseMeta, Error> {
+        let response = self.req.send_to_oss().await?;
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error(response).await),
         }
     }
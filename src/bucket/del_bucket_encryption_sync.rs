@@ -1,5 +1,6 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
@@ -18,7 +19,7 @@ pub struct DelBucketEncryptionSync {
 
 impl DelBucketEncryptionSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::DELETE);
+        let mut req = OssRequest::new(oss, Method::DELETE, "DeleteBucketEncryption");
         req.insert_query("encryption", "");
         DelBucketEncryptionSync { req }
     }
@@ -26,10 +27,10 @@ impl DelBucketEncryptionSync {
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(self) -> Result<(), Error> {
+    pub fn send(self) -> Result<ResponseMeta, Error> {
         let response = self.req.send_to_oss()?;
         match response.status() {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
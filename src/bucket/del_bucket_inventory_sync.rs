@@ -1,5 +1,6 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
@@ -18,7 +19,7 @@ pub struct DelBucketInventorySync {
 
 impl DelBucketInventorySync {
     pub(super) fn new(oss: Oss, inventory_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::DELETE);
+        let mut req = OssRequest::new(oss, Method::DELETE, "DeleteBucketInventory");
         req.insert_query("inventory", "");
         req.insert_query("inventoryId", inventory_id.into());
         DelBucketInventorySync { req }
@@ -27,10 +28,10 @@ impl DelBucketInventorySync {
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(self) -> Result<(), Error> {
+    pub fn send(self) -> Result<ResponseMeta, Error> {
         let response = self.req.send_to_oss()?;
         match response.status() {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
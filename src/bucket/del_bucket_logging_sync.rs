@@ -1,4 +1,5 @@
 use crate::{
+    common::ResponseMeta,
     error::{Error, normal_error_sync},
     request_sync::{Oss, OssRequest},
 };
@@ -12,16 +13,20 @@ pub struct DelBucketLoggingSync {
 }
 impl DelBucketLoggingSync {
     pub(crate) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::DELETE);
+        let mut req = OssRequest::new(oss, Method::DELETE, "DeleteBucketLogging");
         req.insert_query("logging", "");
         DelBucketLoggingSync { req }
     }
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(self) -> Result<(), Error> {
+    pub fn send(self) -> Result<ResponseMeta, Error> {
         let response = self.req.send_to_oss()?;
         let status = response.status();
-        if status.is_success() { Ok(()) } else { Err(normal_error_sync(response)) }
+        if status.is_success() {
+            Ok(ResponseMeta::from_headers(response.headers()))
+        } else {
+            Err(normal_error_sync(response))
+        }
     }
 }
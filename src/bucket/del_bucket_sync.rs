@@ -1,5 +1,6 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
@@ -21,19 +22,19 @@ pub struct DelBucketSync {
 }
 impl DelBucketSync {
     pub(super) fn new(oss: Oss) -> Self {
-        DelBucketSync { req: OssRequest::new(oss, Method::DELETE) }
+        DelBucketSync { req: OssRequest::new(oss, Method::DELETE, "DeleteBucket") }
     }
 
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(self) -> Result<(), Error> {
+    pub fn send(self) -> Result<ResponseMeta, Error> {
         // Build the HTTP request
         let response = self.req.send_to_oss()?;
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
@@ -1,13 +1,10 @@
 use crate::{
     Error,
+    common::{ResponseMeta, md5_base64},
     error::normal_error,
     request::{Oss, OssRequest},
 };
-use base64::{Engine, engine::general_purpose};
-use bytes::Bytes;
 use http::Method;
-use http_body_util::Full;
-use md5::{Digest, Md5};
 use std::collections::HashSet;
 
 /// Delete multiple objects.
@@ -27,7 +24,7 @@ pub struct DelObjects {
 }
 impl DelObjects {
     pub(super) fn new(oss: Oss, files: Vec<impl Into<String>>) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "DeleteMultipleObjects");
         req.insert_query("delete", "");
         let len = files.len();
         if len == 0 {
@@ -58,7 +55,7 @@ impl DelObjects {
     /// Send the request.
     ///
     /// 发送请求。
-    pub async fn send(mut self) -> Result<(), Error> {
+    pub async fn send(mut self) -> Result<ResponseMeta, Error> {
         // Generate body
         let body = format!(
             "<?xml version=\"1.0\" encoding=\"UTF-8\"?><Delete><Quiet>true</Quiet>{}</Delete>",
@@ -67,21 +64,18 @@ impl DelObjects {
         // Calculate body length
         let body_len = body.len();
         // Calculate body MD5
-        let mut hasher = Md5::new();
-        hasher.update(&body);
-        let result = hasher.finalize();
-        let body_md5 = general_purpose::STANDARD.encode(&result);
+        let body_md5 = md5_base64(body.as_bytes());
         // Insert body content
-        self.req.set_body(Full::new(Bytes::from(body)));
+        self.req.set_body(body);
         // Insert header content
         self.req.insert_header("Content-Length", body_len.to_string());
         self.req.insert_header("Content-MD5", body_md5);
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error(response).await),
         }
     }
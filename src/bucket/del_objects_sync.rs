@@ -1,11 +1,10 @@
 use crate::{
     Error,
+    common::{ResponseMeta, md5_base64},
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
-use base64::{Engine, engine::general_purpose};
 use http::Method;
-use md5::{Digest, Md5};
 use std::collections::HashSet;
 
 /// Delete multiple objects.
@@ -25,7 +24,7 @@ pub struct DelObjectsSync {
 }
 impl DelObjectsSync {
     pub(super) fn new(oss: Oss, files: Vec<impl Into<String>>) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "DeleteMultipleObjects");
         req.insert_query("delete", "");
         let len = files.len();
         if len == 0 {
@@ -56,7 +55,7 @@ impl DelObjectsSync {
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(mut self) -> Result<(), Error> {
+    pub fn send(mut self) -> Result<ResponseMeta, Error> {
         // Generate body
         let body = format!(
             "<?xml version=\"1.0\" encoding=\"UTF-8\"?><Delete><Quiet>true</Quiet>{}</Delete>",
@@ -65,10 +64,7 @@ impl DelObjectsSync {
         // Calculate body length
         let body_len = body.len();
         // Calculate body MD5
-        let mut hasher = Md5::new();
-        hasher.update(&body);
-        let result = hasher.finalize();
-        let body_md5 = general_purpose::STANDARD.encode(&result);
+        let body_md5 = md5_base64(body.as_bytes());
         // Insert body content
         self.req.set_body(body.into_bytes());
         // Insert header content
@@ -79,7 +75,7 @@ impl DelObjectsSync {
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
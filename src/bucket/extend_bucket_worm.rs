@@ -1,11 +1,10 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error,
     request::{Oss, OssRequest},
 };
-use bytes::Bytes;
 use http::Method;
-use http_body_util::Full;
 
 /// Extend the retention period of an existing WORM configuration.
 ///
@@ -21,7 +20,7 @@ pub struct ExtendBucketWorm {
 
 impl ExtendBucketWorm {
     pub(super) fn new(oss: Oss, worm_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "ExtendBucketWorm");
         req.insert_query("wormId", worm_id.into());
         req.insert_query("comp", "extend");
         ExtendBucketWorm { req, retention_days: None }
@@ -38,16 +37,16 @@ impl ExtendBucketWorm {
     /// Send the request.
     ///
     /// 发送请求。
-    pub async fn send(mut self) -> Result<(), Error> {
+    pub async fn send(mut self) -> Result<ResponseMeta, Error> {
         let days = self.retention_days.ok_or(Error::MissingRequestBody)?;
         let body = format!(
             "<ExtendWormConfiguration><RetentionPeriodInDays>{}</RetentionPeriodInDays></ExtendWormConfiguration>",
             days
         );
-        self.req.set_body(Full::new(Bytes::from(body)));
-        let response = self.req.send_to_oss()?.await?;
+        self.req.set_body(body);
+        let response = self.req.send_to_oss().await?;
         match response.status() {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error(response).await),
         }
     }
@@ -1,5 +1,6 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
@@ -19,7 +20,7 @@ pub struct ExtendBucketWormSync {
 
 impl ExtendBucketWormSync {
     pub(super) fn new(oss: Oss, worm_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "ExtendBucketWorm");
         req.insert_query("wormId", worm_id.into());
         req.insert_query("comp", "extend");
         ExtendBucketWormSync { req, retention_days: None }
@@ -36,7 +37,7 @@ impl ExtendBucketWormSync {
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(mut self) -> Result<(), Error> {
+    pub fn send(mut self) -> Result<ResponseMeta, Error> {
         let days = self.retention_days.ok_or(Error::MissingRequestBody)?;
         let body = format!(
             "<ExtendWormConfiguration><RetentionPeriodInDays>{}</RetentionPeriodInDays></ExtendWormConfiguration>",
@@ -45,7 +46,7 @@ impl ExtendBucketWormSync {
         self.req.set_body(body.into_bytes());
         let response = self.req.send_to_oss()?;
         match response.status() {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
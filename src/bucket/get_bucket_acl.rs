@@ -33,7 +33,7 @@ pub struct GetBucketAcl {
 }
 impl GetBucketAcl {
     pub(crate) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketAcl");
         req.insert_query("acl", "");
         GetBucketAcl { req }
     }
@@ -41,7 +41,7 @@ impl GetBucketAcl {
     ///
     /// 发送请求并返回 Bucket ACL 信息。
     pub async fn send(self) -> Result<BucketAcl, Error> {
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
@@ -33,7 +33,7 @@ pub struct GetBucketAclSync {
 }
 impl GetBucketAclSync {
     pub(crate) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketAcl");
         req.insert_query("acl", "");
         GetBucketAclSync { req }
     }
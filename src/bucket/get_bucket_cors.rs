@@ -21,7 +21,7 @@ pub struct GetBucketCors {
 
 impl GetBucketCors {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketCors");
         req.insert_query("cors", "");
         GetBucketCors { req }
     }
@@ -30,7 +30,7 @@ impl GetBucketCors {
     ///
     /// 发送请求并返回 CORS 规则。
     pub async fn send(self) -> Result<Vec<CorsRule>, Error> {
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
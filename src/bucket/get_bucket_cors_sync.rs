@@ -30,7 +30,7 @@ pub struct GetBucketCorsSync {
 
 impl GetBucketCorsSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketCors");
         req.insert_query("cors", "");
         GetBucketCorsSync { req }
     }
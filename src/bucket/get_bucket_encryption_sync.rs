@@ -21,7 +21,7 @@ pub struct GetBucketEncryptionSync {
 
 impl GetBucketEncryptionSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketEncryption");
         req.insert_query("encryption", "");
         GetBucketEncryptionSync { req }
     }
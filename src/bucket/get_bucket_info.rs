@@ -139,7 +139,7 @@ pub struct GetBucketInfo {
 }
 impl GetBucketInfo {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketInfo");
         req.insert_query("bucketInfo", "");
         GetBucketInfo { req }
     }
@@ -148,7 +148,7 @@ impl GetBucketInfo {
     /// 发送请求并返回 Bucket 信息。
     pub async fn send(self) -> Result<BucketInfo, Error> {
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
@@ -139,7 +139,7 @@ pub struct GetBucketInfoSync {
 }
 impl GetBucketInfoSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketInfo");
         req.insert_query("bucketInfo", "");
         GetBucketInfoSync { req }
     }
@@ -19,7 +19,7 @@ pub struct GetBucketInventory {
 
 impl GetBucketInventory {
     pub(super) fn new(oss: Oss, inventory_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketInventory");
         req.insert_query("inventory", "");
         req.insert_query("inventoryId", inventory_id.into());
         GetBucketInventory { req }
@@ -29,7 +29,7 @@ impl GetBucketInventory {
     ///
     /// 发送请求并返回清单 XML。
     pub async fn send(self) -> Result<String, Error> {
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         match response.status() {
             code if code.is_success() => {
                 let bytes = body_to_bytes(response.into_body()).await?;
@@ -19,7 +19,7 @@ pub struct GetBucketInventorySync {
 
 impl GetBucketInventorySync {
     pub(super) fn new(oss: Oss, inventory_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketInventory");
         req.insert_query("inventory", "");
         req.insert_query("inventoryId", inventory_id.into());
         GetBucketInventorySync { req }
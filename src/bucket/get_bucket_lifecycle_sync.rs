@@ -19,7 +19,7 @@ pub struct GetBucketLifecycleSync {
 
 impl GetBucketLifecycleSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketLifecycle");
         req.insert_query("lifecycle", "");
         GetBucketLifecycleSync { req }
     }
@@ -10,7 +10,7 @@ use serde_derive::Deserialize;
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 struct LocationConstraint {
-    #[serde(rename = "$value")]
+    #[serde(rename = "#text")]
     pub location: String,
 }
 
@@ -26,7 +26,7 @@ pub struct GetBucketLocation {
 }
 impl GetBucketLocation {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketLocation");
         req.insert_query("location", "");
         GetBucketLocation { req }
     }
@@ -34,7 +34,7 @@ impl GetBucketLocation {
     ///
     /// 发送请求并返回地域标识。
     pub async fn send(self) -> Result<String, Error> {
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
@@ -10,7 +10,8 @@ use std::io::Read;
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 struct LocationConstraint {
-    location_constraint: String,
+    #[serde(rename = "#text")]
+    location: String,
 }
 
 /// Retrieve bucket location (sync).
@@ -21,7 +22,7 @@ pub struct GetBucketLocationSync {
 }
 impl GetBucketLocationSync {
     pub(crate) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketLocation");
         req.insert_query("location", "");
         GetBucketLocationSync { req }
     }
@@ -37,7 +38,7 @@ impl GetBucketLocationSync {
             reader.read_to_end(&mut buf)?;
             let result: LocationConstraint =
                 serde_xml_rs::from_reader(&*buf).map_err(|_| Error::OssInvalidResponse(Some(Bytes::from(buf))))?;
-            Ok(result.location_constraint)
+            Ok(result.location)
         } else {
             Err(normal_error_sync(response))
         }
@@ -42,7 +42,7 @@ pub struct GetBucketLogging {
 }
 impl GetBucketLogging {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketLogging");
         req.insert_query("logging", "");
         GetBucketLogging { req }
     }
@@ -50,7 +50,7 @@ impl GetBucketLogging {
     ///
     /// 发送请求并返回日志配置（如有）。
     pub async fn send(self) -> Result<Option<LoggingEnabled>, Error> {
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
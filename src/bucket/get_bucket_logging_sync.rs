@@ -38,7 +38,7 @@ pub struct GetBucketLoggingSync {
 }
 impl GetBucketLoggingSync {
     pub(crate) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketLogging");
         req.insert_query("logging", "");
         GetBucketLoggingSync { req }
     }
@@ -19,7 +19,7 @@ pub struct GetBucketPolicy {
 
 impl GetBucketPolicy {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketPolicy");
         req.insert_query("policy", "");
         GetBucketPolicy { req }
     }
@@ -28,7 +28,7 @@ impl GetBucketPolicy {
     ///
     /// 发送请求并返回策略 JSON。
     pub async fn send(self) -> Result<String, Error> {
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         match response.status() {
             code if code.is_success() => {
                 let bytes = body_to_bytes(response.into_body()).await?;
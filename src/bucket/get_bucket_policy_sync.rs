@@ -19,7 +19,7 @@ pub struct GetBucketPolicySync {
 
 impl GetBucketPolicySync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketPolicy");
         req.insert_query("policy", "");
         GetBucketPolicySync { req }
     }
@@ -21,7 +21,7 @@ pub struct GetBucketRefererSync {
 
 impl GetBucketRefererSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketReferer");
         req.insert_query("referer", "");
         GetBucketRefererSync { req }
     }
@@ -21,7 +21,7 @@ pub struct GetBucketRequestPayment {
 
 impl GetBucketRequestPayment {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketRequestPayment");
         req.insert_query("requestPayment", "");
         GetBucketRequestPayment { req }
     }
@@ -30,7 +30,7 @@ impl GetBucketRequestPayment {
     ///
     /// 发送请求并返回解析后的配置。
     pub async fn send(self) -> Result<RequestPaymentConfiguration, Error> {
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         match response.status() {
             code if code.is_success() => {
                 let bytes = body_to_bytes(response.into_body()).await?;
@@ -21,7 +21,7 @@ pub struct GetBucketRequestPaymentSync {
 
 impl GetBucketRequestPaymentSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketRequestPayment");
         req.insert_query("requestPayment", "");
         GetBucketRequestPaymentSync { req }
     }
@@ -120,7 +120,7 @@ pub struct GetBucketStat {
 }
 impl GetBucketStat {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketStat");
         req.insert_query("stat", "");
         GetBucketStat { req }
     }
@@ -130,7 +130,7 @@ impl GetBucketStat {
     /// 发送请求并返回统计信息。
     pub async fn send(self) -> Result<BucketStat, Error> {
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
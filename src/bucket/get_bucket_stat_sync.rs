@@ -120,7 +120,7 @@ pub struct GetBucketStatSync {
 }
 impl GetBucketStatSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketStat");
         req.insert_query("stat", "");
         GetBucketStatSync { req }
     }
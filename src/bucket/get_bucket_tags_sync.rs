@@ -21,7 +21,7 @@ pub struct GetBucketTagsSync {
 
 impl GetBucketTagsSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketTags");
         req.insert_query("tagging", "");
         GetBucketTagsSync { req }
     }
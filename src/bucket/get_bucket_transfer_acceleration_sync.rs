@@ -21,7 +21,7 @@ pub struct GetBucketTransferAccelerationSync {
 
 impl GetBucketTransferAccelerationSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketTransferAcceleration");
         req.insert_query("transferAcceleration", "");
         GetBucketTransferAccelerationSync { req }
     }
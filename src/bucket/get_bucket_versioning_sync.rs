@@ -21,7 +21,7 @@ pub struct GetBucketVersioningSync {
 
 impl GetBucketVersioningSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketVersioning");
         req.insert_query("versioning", "");
         GetBucketVersioningSync { req }
     }
@@ -21,7 +21,7 @@ pub struct GetBucketWebsite {
 
 impl GetBucketWebsite {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketWebsite");
         req.insert_query("website", "");
         GetBucketWebsite { req }
     }
@@ -30,7 +30,7 @@ impl GetBucketWebsite {
     ///
     /// 发送请求并返回解析后的配置。
     pub async fn send(self) -> Result<WebsiteConfiguration, Error> {
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         match response.status() {
             code if code.is_success() => {
                 let bytes = body_to_bytes(response.into_body()).await?;
@@ -21,7 +21,7 @@ pub struct GetBucketWebsiteSync {
 
 impl GetBucketWebsiteSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketWebsite");
         req.insert_query("website", "");
         GetBucketWebsiteSync { req }
     }
@@ -21,7 +21,7 @@ pub struct GetBucketWorm {
 
 impl GetBucketWorm {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketWorm");
         req.insert_query("worm", "");
         GetBucketWorm { req }
     }
@@ -30,7 +30,7 @@ impl GetBucketWorm {
     ///
     /// 发送请求并返回解析后的配置。
     pub async fn send(self) -> Result<BucketWormConfiguration, Error> {
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         match response.status() {
             code if code.is_success() => {
                 let bytes = body_to_bytes(response.into_body()).await?;
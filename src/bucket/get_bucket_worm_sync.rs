@@ -21,7 +21,7 @@ pub struct GetBucketWormSync {
 
 impl GetBucketWormSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetBucketWorm");
         req.insert_query("worm", "");
         GetBucketWormSync { req }
     }
@@ -1,11 +1,10 @@
 use crate::{
     Error,
+    common::{InitiateBucketWormOutput, ResponseMeta},
     error::normal_error,
     request::{Oss, OssRequest},
 };
-use bytes::Bytes;
 use http::Method;
-use http_body_util::Full;
 
 /// Start a WORM retention configuration for the bucket.
 ///
@@ -25,7 +24,7 @@ pub struct InitiateBucketWorm {
 
 impl InitiateBucketWorm {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "InitiateBucketWorm");
         req.insert_query("worm", "");
         req.insert_query("comp", "initiate");
         InitiateBucketWorm { req, retention_days: None }
@@ -42,14 +41,14 @@ impl InitiateBucketWorm {
     /// Send the request and return the WORM ID.
     ///
     /// 发送请求并返回 WORM ID。
-    pub async fn send(mut self) -> Result<String, Error> {
+    pub async fn send(mut self) -> Result<InitiateBucketWormOutput, Error> {
         let days = self.retention_days.ok_or(Error::MissingRequestBody)?;
         let body = format!(
             "<InitiateWormConfiguration><RetentionPeriodInDays>{}</RetentionPeriodInDays></InitiateWormConfiguration>",
             days
         );
-        self.req.set_body(Full::new(Bytes::from(body)));
-        let response = self.req.send_to_oss()?.await?;
+        self.req.set_body(body);
+        let response = self.req.send_to_oss().await?;
         match response.status() {
             code if code.is_success() => {
                 let worm_id = response
@@ -57,7 +56,10 @@ impl InitiateBucketWorm {
                     .get("x-oss-worm-id")
                     .and_then(|value| value.to_str().ok())
                     .ok_or(Error::OssInvalidResponse(None))?;
-                Ok(worm_id.to_string())
+                Ok(InitiateBucketWormOutput {
+                    worm_id: worm_id.to_string(),
+                    meta: ResponseMeta::from_headers(response.headers()),
+                })
             }
             _ => Err(normal_error(response).await),
         }
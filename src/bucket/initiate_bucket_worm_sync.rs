@@ -1,5 +1,6 @@
 use crate::{
     Error,
+    common::{InitiateBucketWormOutput, ResponseMeta},
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
@@ -23,7 +24,7 @@ pub struct InitiateBucketWormSync {
 
 impl InitiateBucketWormSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "InitiateBucketWorm");
         req.insert_query("worm", "");
         req.insert_query("comp", "initiate");
         InitiateBucketWormSync { req, retention_days: None }
@@ -40,7 +41,7 @@ impl InitiateBucketWormSync {
     /// Send the request and return the WORM ID.
     ///
     /// 发送请求并返回 WORM ID。
-    pub fn send(mut self) -> Result<String, Error> {
+    pub fn send(mut self) -> Result<InitiateBucketWormOutput, Error> {
         let days = self.retention_days.ok_or(Error::MissingRequestBody)?;
         let body = format!(
             "<InitiateWormConfiguration><RetentionPeriodInDays>{}</RetentionPeriodInDays></InitiateWormConfiguration>",
@@ -55,7 +56,10 @@ impl InitiateBucketWormSync {
                     .get("x-oss-worm-id")
                     .and_then(|value| value.to_str().ok())
                     .ok_or(Error::OssInvalidResponse(None))?;
-                Ok(worm_id.to_string())
+                Ok(InitiateBucketWormOutput {
+                    worm_id: worm_id.to_string(),
+                    meta: ResponseMeta::from_headers(response.headers()),
+                })
             }
             _ => Err(normal_error_sync(response)),
         }
@@ -19,7 +19,7 @@ pub struct ListBucketInventory {
 
 impl ListBucketInventory {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "ListBucketInventory");
         req.insert_query("inventory", "");
         req.insert_query("comp", "list");
         ListBucketInventory { req }
@@ -29,7 +29,7 @@ impl ListBucketInventory {
     ///
     /// 发送请求并返回 XML 响应。
     pub async fn send(self) -> Result<String, Error> {
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         match response.status() {
             code if code.is_success() => {
                 let bytes = body_to_bytes(response.into_body()).await?;
@@ -19,7 +19,7 @@ pub struct ListBucketInventorySync {
 
 impl ListBucketInventorySync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "ListBucketInventory");
         req.insert_query("inventory", "");
         req.insert_query("comp", "list");
         ListBucketInventorySync { req }
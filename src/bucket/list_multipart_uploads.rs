@@ -5,7 +5,6 @@ use crate::{
 };
 use http::Method;
 use serde_derive::Deserialize;
-use std::cmp;
 
 // Returned content
 #[derive(Debug, Deserialize)]
@@ -85,7 +84,7 @@ pub struct ListUploads {
 
 impl ListUploads {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "ListMultipartUploads");
         req.insert_query("uploads", "");
         ListUploads { req }
     }
@@ -129,7 +128,7 @@ impl ListUploads {
     ///
     /// 默认 1000，合法范围 1-1000。
     pub fn set_max_uploads(mut self, max_keys: u32) -> Self {
-        let max_keys = cmp::min(1000, cmp::max(1, max_keys));
+        let max_keys = max_keys.clamp(1, 1000);
         self.req.insert_query("max-uploads", max_keys.to_string());
         self
     }
@@ -138,7 +137,7 @@ impl ListUploads {
     /// 发送请求并返回结果。
     pub async fn send(self) -> Result<ListMultipartUploadsResult, Error> {
         // Upload file
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
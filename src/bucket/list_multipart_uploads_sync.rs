@@ -5,7 +5,6 @@ use crate::{
 };
 use http::Method;
 use serde_derive::Deserialize;
-use std::cmp;
 
 // Returned content
 #[derive(Debug, Deserialize)]
@@ -85,7 +84,7 @@ pub struct ListUploadsSync {
 
 impl ListUploadsSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "ListMultipartUploads");
         req.insert_query("uploads", "");
         ListUploadsSync { req }
     }
@@ -129,7 +128,7 @@ impl ListUploadsSync {
     ///
     /// 默认 1000，合法范围 1-1000。
     pub fn set_max_uploads(mut self, max_keys: u32) -> Self {
-        let max_keys = cmp::min(1000, cmp::max(1, max_keys));
+        let max_keys = max_keys.clamp(1, 1000);
         self.req.insert_query("max-uploads", max_keys.to_string());
         self
     }
@@ -7,7 +7,6 @@ use crate::{
 };
 use http::Method;
 use serde_derive::Deserialize;
-use std::cmp;
 
 // Returned content
 #[derive(Debug, Deserialize)]
@@ -97,7 +96,7 @@ pub struct ListObjects {
 
 impl ListObjects {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "ListObjectsV2");
         req.insert_query("list-type", "2");
         req.insert_query("max-keys", "1000");
         ListObjects { req }
@@ -154,7 +153,7 @@ impl ListObjects {
     ///
     /// 默认 1000，合法范围 1-1000。
     pub fn set_max_keys(mut self, max_keys: u32) -> Self {
-        let max_keys = cmp::min(1000, cmp::max(1, max_keys));
+        let max_keys = max_keys.clamp(1, 1000);
         self.req.insert_query("max-keys", max_keys.to_string());
         self
     }
@@ -170,7 +169,7 @@ impl ListObjects {
     /// 发送请求并返回结果。
     pub async fn send(self) -> Result<ObjectsList, Error> {
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
@@ -181,7 +180,7 @@ impl ListObjects {
                     .map_err(|_| Error::OssInvalidResponse(Some(response_bytes)))?;
                 Ok(object_list)
             }
-            _ => return Err(normal_error(response).await),
+            _ => Err(normal_error(response).await),
         }
     }
 }
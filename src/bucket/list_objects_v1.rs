@@ -7,7 +7,6 @@ use crate::{
 };
 use http::Method;
 use serde_derive::Deserialize;
-use std::cmp;
 
 use super::list_objects::CommonPrefixes;
 
@@ -59,7 +58,7 @@ pub struct ObjectInfo {
 
 impl ListObjectsV1 {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "ListObjects");
         req.insert_query("max-keys", "1000");
         ListObjectsV1 { req }
     }
@@ -92,7 +91,7 @@ impl ListObjectsV1 {
     ///
     /// 设置返回条目上限。
     pub fn set_max_keys(mut self, max_keys: u32) -> Self {
-        let max_keys = cmp::min(1000, cmp::max(1, max_keys));
+        let max_keys = max_keys.clamp(1, 1000);
         self.req.insert_query("max-keys", max_keys.to_string());
         self
     }
@@ -101,7 +100,7 @@ impl ListObjectsV1 {
     ///
     /// 发送请求。
     pub async fn send(self) -> Result<ListObjectsV1Result, Error> {
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         match response.status() {
             code if code.is_success() => {
                 let response_bytes =
@@ -7,7 +7,6 @@ use crate::{
 };
 use http::Method;
 use serde_derive::Deserialize;
-use std::cmp;
 
 use super::list_objects_sync::CommonPrefixes;
 
@@ -59,7 +58,7 @@ pub struct ObjectInfo {
 
 impl ListObjectsV1Sync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "ListObjects");
         req.insert_query("max-keys", "1000");
         ListObjectsV1Sync { req }
     }
@@ -92,7 +91,7 @@ impl ListObjectsV1Sync {
     ///
     /// 设置返回条目上限。
     pub fn set_max_keys(mut self, max_keys: u32) -> Self {
-        let max_keys = cmp::min(1000, cmp::max(1, max_keys));
+        let max_keys = max_keys.clamp(1, 1000);
         self.req.insert_query("max-keys", max_keys.to_string());
         self
     }
@@ -4,6 +4,8 @@
 
 #[doc(hidden)]
 pub use self::oss_bucket::OssBucket;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+pub use self::post_policy::{PostPolicy, PostPolicyForm};
 #[cfg(feature = "_async-base")]
 pub use self::{
     abort_bucket_worm::AbortBucketWorm,
@@ -185,6 +187,8 @@ mod list_objects;
 #[cfg(feature = "_async-base")]
 mod list_objects_v1;
 mod oss_bucket;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+mod post_policy;
 #[cfg(feature = "_async-base")]
 mod put_bucket;
 #[cfg(feature = "_async-base")]
@@ -534,23 +538,17 @@ pub struct VersioningConfiguration {
 }
 
 #[cfg(any(feature = "_async-base", feature = "_sync-base"))]
-#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[derive(Debug, Clone, Default, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "PascalCase")]
 /// Versioning status.
 ///
 /// 版本控制状态。
 pub enum VersioningStatus {
     Enabled,
+    #[default]
     Suspended,
 }
 
-#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
-impl Default for VersioningStatus {
-    fn default() -> Self {
-        VersioningStatus::Suspended
-    }
-}
-
 #[cfg(any(feature = "_async-base", feature = "_sync-base"))]
 #[derive(Debug, Default, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -576,23 +574,17 @@ pub struct RequestPaymentConfiguration {
 }
 
 #[cfg(any(feature = "_async-base", feature = "_sync-base"))]
-#[derive(Debug, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
+#[derive(Debug, Clone, Default, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "PascalCase")]
 /// Request payer.
 ///
 /// 请求付费方。
 pub enum RequestPayer {
     Requester,
+    #[default]
     BucketOwner,
 }
 
-#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
-impl Default for RequestPayer {
-    fn default() -> Self {
-        RequestPayer::BucketOwner
-    }
-}
-
 #[cfg(any(feature = "_async-base", feature = "_sync-base"))]
 #[derive(Debug, Default, Clone, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "PascalCase")]
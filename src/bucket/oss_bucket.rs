@@ -1,3 +1,5 @@
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use super::PostPolicy;
 #[cfg(feature = "_async-base")]
 use super::{
     AbortBucketWorm, CompleteBucketWorm, DelBucket, DelBucketCors, DelBucketEncryption, DelBucketInventory,
@@ -26,7 +28,19 @@ use super::{
 };
 #[cfg(any(feature = "_async-base", feature = "_sync-base"))]
 use crate::OssObject;
+#[cfg(feature = "_async-base")]
+use crate::client::RawRequest;
+#[cfg(feature = "_sync-base")]
+use crate::client::RawRequestSync;
+#[cfg(feature = "_async-base")]
+use crate::object::PresignUrl;
+#[cfg(feature = "_sync-base")]
+use crate::object::PresignUrlSync;
 use crate::oss::Oss;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use http::Method;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use time::OffsetDateTime;
 
 /// Bucket handle that exposes bucket-level APIs (lifecycle, ACL, CORS, logging, etc.).
 ///
@@ -62,6 +76,25 @@ impl OssBucket {
         self.oss.set_https(enable_https);
         self
     }
+    /// Follow the bucket to its own region: when OSS rejects a request because it was sent to or
+    /// signed for another region, look the region up with GetBucketLocation, rebind endpoint and
+    /// signing region, and re-send. The region is remembered per bucket by every handle of the
+    /// client, so later rejections, such as a genuine `AccessDenied`, are returned without another
+    /// lookup. Requests through a custom domain are left untouched.
+    ///
+    /// An endpoint set explicitly on the client is kept: only the signing region is rebound, which
+    /// suits gateways that route by bucket but not a public endpoint of another region.
+    ///
+    /// 自动跟随 Bucket 所在地域：当 OSS 因请求发往或签名于其他地域而拒绝时，通过 GetBucketLocation
+    /// 查询地域，重新绑定 Endpoint 与签名地域后重发。地域按 Bucket 记录，由客户端的所有句柄共享，
+    /// 因此之后的拒绝（例如真正的 `AccessDenied`）会直接返回，不再重复查询。使用自定义域名的请求不受影响。
+    ///
+    /// 客户端上显式设置的 Endpoint 保持不变，仅重新绑定签名地域；这适用于按 Bucket 路由的网关，
+    /// 但不适用于其他地域的公网 Endpoint。
+    pub fn with_region_discovery(mut self, enable: bool) -> Self {
+        self.oss.region_discovery = enable;
+        self
+    }
     /// Create an object handle under this bucket.
     ///
     /// 在当前 Bucket 下创建对象句柄。
@@ -69,6 +102,43 @@ impl OssBucket {
     pub fn object(&self, object: impl Into<String>) -> OssObject {
         OssObject::new(self.oss.clone(), object)
     }
+    /// Pre-sign a bucket-level request with any method, e.g. `GET ?uploads` or `PUT ?acl`; add the
+    /// sub-resource with `insert_query`.
+    ///
+    /// 为任意方法的 Bucket 级请求生成预签名 URL，例如 `GET ?uploads` 或 `PUT ?acl`；通过 `insert_query`
+    /// 添加子资源。
+    #[cfg(feature = "_async-base")]
+    pub fn presign(&self, method: Method) -> PresignUrl {
+        PresignUrl::new(self.oss.clone(), method)
+    }
+    /// Pre-sign a bucket-level request with any method (sync).
+    ///
+    /// 为任意方法的 Bucket 级请求生成预签名 URL（同步）。
+    #[cfg(feature = "_sync-base")]
+    pub fn presign_sync(&self, method: Method) -> PresignUrlSync {
+        PresignUrlSync::new(self.oss.clone(), method)
+    }
+    /// Build a signed policy for browser uploads through an HTML form POST, valid until `expiration`.
+    ///
+    /// 生成用于浏览器端 HTML 表单 POST 直传的签名 Policy，在 `expiration` 之前有效。
+    #[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+    pub fn post_policy(&self, expiration: OffsetDateTime) -> PostPolicy {
+        PostPolicy::new(self.oss.clone(), expiration)
+    }
+    /// Build a signed request to an OSS API not wrapped by this crate, addressed to this bucket.
+    ///
+    /// 构建发送至任意 OSS API 的签名请求（目标为当前 Bucket），用于本库尚未封装的接口。
+    #[cfg(feature = "_async-base")]
+    pub fn raw_request(&self, method: Method) -> RawRequest {
+        RawRequest::new(self.oss.clone(), method)
+    }
+    /// Build a signed request to an OSS API not wrapped by this crate, addressed to this bucket (sync).
+    ///
+    /// 构建发送至任意 OSS API 的签名请求（目标为当前 Bucket），用于本库尚未封装的接口（同步）。
+    #[cfg(feature = "_sync-base")]
+    pub fn raw_request_sync(&self, method: Method) -> RawRequestSync {
+        RawRequestSync::new(self.oss.clone(), method)
+    }
     /// Create the bucket.
     ///
     /// 创建 Bucket。
@@ -427,6 +497,7 @@ impl OssBucket {
 }
 
 #[cfg(test)]
+#[allow(clippy::items_after_test_module)]
 mod tests {
     use super::*;
 
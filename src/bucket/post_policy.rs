@@ -0,0 +1,191 @@
+use crate::signer::{self, ALGORITHM};
+use crate::{Error, common::signature_v4, oss::Oss};
+use base64::{Engine, engine::general_purpose};
+use serde_json::{Value, json};
+use time::{OffsetDateTime, UtcOffset, format_description};
+
+/// Policy for browser uploads through an HTML form POST (PostObject), signed with Signature V4.
+///
+/// Every exact-match field set here is added both to the policy conditions and to the returned form
+/// fields, so the page only has to add `key` (unless fixed with `set_key`) and `file`.
+///
+/// See the [Alibaba Cloud documentation](https://help.aliyun.com/zh/oss/developer-reference/signature-version-4-recommend)
+/// for details.
+///
+/// 通过 HTML 表单 POST（PostObject）在浏览器端直传时使用的 Policy，采用 V4 签名。
+///
+/// 此处设置的精确匹配字段会同时加入 Policy 条件与返回的表单字段，页面只需补充 `key`
+/// （除非已通过 `set_key` 固定）与 `file`。
+///
+/// 详情见 [阿里云文档](https://help.aliyun.com/zh/oss/developer-reference/signature-version-4-recommend)。
+pub struct PostPolicy {
+    oss: Oss,
+    expiration: OffsetDateTime,
+    conditions: Vec<Value>,
+    fields: Vec<(String, String)>,
+}
+
+/// Form target and fields produced by [`PostPolicy::build`].
+///
+/// 由 [`PostPolicy::build`] 生成的表单地址与字段。
+#[derive(Debug, Clone)]
+pub struct PostPolicyForm {
+    /// URL the form is posted to.
+    ///
+    /// 表单提交地址。
+    pub url: String,
+    /// Form fields, in the order they should precede the `file` field.
+    ///
+    /// 表单字段，应按此顺序放在 `file` 字段之前。
+    pub fields: Vec<(String, String)>,
+}
+
+impl PostPolicy {
+    pub(super) fn new(oss: Oss, expiration: OffsetDateTime) -> Self {
+        PostPolicy { oss, expiration, conditions: Vec::new(), fields: Vec::new() }
+    }
+    /// Limit the size of the uploaded file, in bytes.
+    ///
+    /// 限制上传文件的大小（字节）。
+    pub fn set_content_length_range(mut self, min: u64, max: u64) -> Self {
+        self.conditions.push(json!(["content-length-range", min, max]));
+        self
+    }
+    /// Fix the object key.
+    ///
+    /// 固定对象键。
+    pub fn set_key(self, key: impl Into<String>) -> Self {
+        self.set_field("key", key)
+    }
+    /// Require the object key to start with `prefix`.
+    ///
+    /// 要求对象键以 `prefix` 开头。
+    pub fn set_key_starts_with(self, prefix: impl Into<String>) -> Self {
+        self.set_starts_with("key", prefix)
+    }
+    /// Require form field `field` (for example `Content-Type`) to start with `prefix`.
+    ///
+    /// 要求表单字段 `field`（例如 `Content-Type`）以 `prefix` 开头。
+    pub fn set_starts_with(mut self, field: impl Into<String>, prefix: impl Into<String>) -> Self {
+        self.conditions.push(json!(["starts-with", format!("${}", field.into()), prefix.into()]));
+        self
+    }
+    /// Require form field `field` to equal `value`, and add it to the form.
+    ///
+    /// Use it for fields such as `Content-Type`, `Cache-Control`, `x-oss-object-acl` or `x-oss-meta-*`.
+    ///
+    /// 要求表单字段 `field` 等于 `value`，并将其加入表单。
+    ///
+    /// 适用于 `Content-Type`、`Cache-Control`、`x-oss-object-acl` 或 `x-oss-meta-*` 等字段。
+    pub fn set_field(mut self, field: impl Into<String>, value: impl Into<String>) -> Self {
+        let (field, value) = (field.into(), value.into());
+        self.conditions.push(json!(["eq", format!("${}", field), value]));
+        self.fields.push((field, value));
+        self
+    }
+    /// HTTP status returned after a successful upload (200, 201 or 204; OSS defaults to 204).
+    ///
+    /// 上传成功后返回的 HTTP 状态码（200、201 或 204；OSS 默认为 204）。
+    pub fn set_success_action_status(self, status: u16) -> Self {
+        self.set_field("success_action_status", status.to_string())
+    }
+    /// Upload callback, given as the callback JSON (`callbackUrl`, `callbackBody`, ...); it is
+    /// Base64-encoded into the `callback` field.
+    ///
+    /// 上传回调，传入回调 JSON（`callbackUrl`、`callbackBody` 等），会以 Base64 编码写入 `callback` 字段。
+    pub fn set_callback(self, callback: impl AsRef<[u8]>) -> Self {
+        self.set_field("callback", general_purpose::STANDARD.encode(callback))
+    }
+    /// Sign the policy and return the form URL and fields.
+    ///
+    /// With a credentials provider configured this may block while expired credentials are refreshed.
+    ///
+    /// 签名 Policy 并返回表单地址与字段。
+    ///
+    /// 配置了凭证提供者时，刷新过期凭证可能会阻塞当前线程。
+    pub fn build(self) -> Result<PostPolicyForm, Error> {
+        let credentials = self.oss.credentials()?;
+        let now = self.oss.clock.now();
+        let date = signer::format_date(now);
+        let date_short = signer::format_date_short(now);
+        let region = self.oss.region.as_ref();
+        let credential = signer::credential(&credentials, &date_short, region);
+        // expiry is measured on the local clock, like pre-signed URLs
+        let expiration = self
+            .expiration
+            .to_offset(UtcOffset::UTC)
+            .format(
+                &format_description::parse("[year]-[month]-[day]T[hour]:[minute]:[second].000Z").expect("valid format"),
+            )
+            .expect("formatting");
+
+        let mut signed_fields = vec![
+            ("x-oss-signature-version".to_string(), ALGORITHM.to_string()),
+            ("x-oss-credential".to_string(), credential),
+            ("x-oss-date".to_string(), date),
+        ];
+        if let Some(token) = credentials.security_token() {
+            signed_fields.push(("x-oss-security-token".to_string(), token.to_string()));
+        }
+        let mut conditions = Vec::with_capacity(self.conditions.len() + signed_fields.len() + 1);
+        if let Some(bucket) = self.oss.bucket.as_deref() {
+            conditions.push(json!({ "bucket": bucket }));
+        }
+        conditions.extend(signed_fields.iter().map(|(field, value)| json!({ field.as_str(): value })));
+        conditions.extend(self.conditions);
+        let policy = json!({ "expiration": expiration, "conditions": conditions });
+        let policy = general_purpose::STANDARD.encode(policy.to_string());
+        let signature = signature_v4(credentials.access_key_secret(), &date_short, region, &policy);
+
+        let mut fields = self.fields;
+        fields.push(("policy".to_string(), policy));
+        fields.extend(signed_fields);
+        fields.push(("x-oss-signature".to_string(), signature));
+        Ok(PostPolicyForm { url: self.oss.base_url(), fields })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Duration;
+
+    #[test]
+    fn test_post_policy_form_fields() {
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_bucket("bucket");
+        oss.set_security_token("token");
+        let expiration = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap() + Duration::hours(1);
+        let form = PostPolicy::new(oss, expiration)
+            .set_content_length_range(1, 1024)
+            .set_key_starts_with("user/")
+            .set_field("Content-Type", "image/png")
+            .set_success_action_status(201)
+            .set_callback(r#"{"callbackUrl":"https://example.com/cb"}"#)
+            .build()
+            .unwrap();
+        assert_eq!(form.url, "https://bucket.oss-cn-hangzhou.aliyuncs.com/");
+        let field = |name: &str| form.fields.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str());
+        assert_eq!(field("Content-Type"), Some("image/png"));
+        assert_eq!(field("success_action_status"), Some("201"));
+        assert_eq!(field("x-oss-security-token"), Some("token"));
+        assert_eq!(field("x-oss-signature-version"), Some("OSS4-HMAC-SHA256"));
+        assert!(field("x-oss-credential").unwrap().starts_with("id/"));
+
+        let policy = general_purpose::STANDARD.decode(field("policy").unwrap()).unwrap();
+        let policy: Value = serde_json::from_slice(&policy).unwrap();
+        assert_eq!(policy["expiration"], "2023-11-14T23:13:20.000Z");
+        let conditions = policy["conditions"].as_array().unwrap();
+        assert!(conditions.contains(&json!({ "bucket": "bucket" })));
+        assert!(conditions.contains(&json!({ "x-oss-security-token": "token" })));
+        assert!(conditions.contains(&json!(["content-length-range", 1, 1024])));
+        assert!(conditions.contains(&json!(["starts-with", "$key", "user/"])));
+        assert!(conditions.contains(&json!(["eq", "$success_action_status", "201"])));
+
+        let date_short = &field("x-oss-date").unwrap()[..8];
+        assert_eq!(
+            field("x-oss-signature").unwrap(),
+            signature_v4("secret", date_short, "cn-hangzhou", field("policy").unwrap())
+        );
+    }
+}
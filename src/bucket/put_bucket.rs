@@ -1,12 +1,10 @@
 use crate::{
     Error,
-    common::{Acl, DataRedundancyType, StorageClass},
+    common::{Acl, DataRedundancyType, ResponseMeta, StorageClass},
     error::normal_error,
     request::{Oss, OssRequest},
 };
-use bytes::Bytes;
 use http::Method;
-use http_body_util::Full;
 
 /// Create a bucket with PutBucket.
 ///
@@ -26,7 +24,18 @@ pub struct PutBucket {
 }
 impl PutBucket {
     pub(super) fn new(oss: Oss) -> Self {
-        PutBucket { req: OssRequest::new(oss, Method::PUT), storage_class: None, data_redundancy_type: None }
+        PutBucket {
+            req: OssRequest::new(oss, Method::PUT, "PutBucket"),
+            storage_class: None,
+            data_redundancy_type: None,
+        }
+    }
+    /// Sign the SHA-256 of the request body instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// 对请求体的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    pub fn set_signed_payload(mut self, enable: bool) -> Self {
+        self.req.set_signed_payload(enable);
+        self
     }
     /// Set bucket ACL.
     ///
@@ -61,7 +70,7 @@ impl PutBucket {
         self.storage_class = Some(storage_class);
         self.data_redundancy_type = normalize_redundancy(self.storage_class, self.data_redundancy_type);
         let body_str = build_create_bucket_body(self.storage_class, self.data_redundancy_type);
-        self.req.set_body(Full::new(Bytes::from(body_str)));
+        self.req.set_body(body_str);
         self
     }
     /// Set bucket data redundancy type.
@@ -75,19 +84,19 @@ impl PutBucket {
         self.data_redundancy_type = Some(redundancy_type);
         self.data_redundancy_type = normalize_redundancy(self.storage_class, self.data_redundancy_type);
         let body_str = build_create_bucket_body(self.storage_class, self.data_redundancy_type);
-        self.req.set_body(Full::new(Bytes::from(body_str)));
+        self.req.set_body(body_str);
         self
     }
     /// Send the request.
     ///
     /// 发送请求。
-    pub async fn send(self) -> Result<(), Error> {
+    pub async fn send(self) -> Result<ResponseMeta, Error> {
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error(response).await),
         }
     }
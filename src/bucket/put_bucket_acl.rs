@@ -1,6 +1,6 @@
 use crate::{
     Error,
-    common::Acl,
+    common::{Acl, ResponseMeta},
     error::normal_error,
     request::{Oss, OssRequest},
 };
@@ -18,7 +18,7 @@ pub struct PutBucketAcl {
 }
 impl PutBucketAcl {
     pub(crate) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "PutBucketAcl");
         req.insert_query("acl", "");
         PutBucketAcl { req }
     }
@@ -32,11 +32,11 @@ impl PutBucketAcl {
     /// Send the request.
     ///
     /// 发送请求。
-    pub async fn send(self) -> Result<(), Error> {
-        let response = self.req.send_to_oss()?.await?;
+    pub async fn send(self) -> Result<ResponseMeta, Error> {
+        let response = self.req.send_to_oss().await?;
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error(response).await),
         }
     }
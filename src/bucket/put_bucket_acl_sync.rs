@@ -1,5 +1,5 @@
 use crate::{
-    common::Acl,
+    common::{Acl, ResponseMeta},
     error::{Error, normal_error_sync},
     request_sync::{Oss, OssRequest},
 };
@@ -13,7 +13,7 @@ pub struct PutBucketAclSync {
 }
 impl PutBucketAclSync {
     pub(crate) fn new(oss: Oss) -> Self {
-        let req = OssRequest::new(oss, Method::PUT);
+        let req = OssRequest::new(oss, Method::PUT, "PutBucketAcl");
         PutBucketAclSync { req }
     }
     /// Set the access control.
@@ -27,9 +27,13 @@ impl PutBucketAclSync {
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(self) -> Result<(), Error> {
+    pub fn send(self) -> Result<ResponseMeta, Error> {
         let response = self.req.send_to_oss()?;
         let status = response.status();
-        if status.is_success() { Ok(()) } else { Err(normal_error_sync(response)) }
+        if status.is_success() {
+            Ok(ResponseMeta::from_headers(response.headers()))
+        } else {
+            Err(normal_error_sync(response))
+        }
     }
 }
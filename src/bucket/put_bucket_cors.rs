@@ -1,11 +1,10 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error,
     request::{Oss, OssRequest},
 };
-use bytes::Bytes;
 use http::Method;
-use http_body_util::Full;
 use serde_derive::{Deserialize, Serialize};
 
 use super::CorsConfiguration;
@@ -24,11 +23,19 @@ pub struct PutBucketCors {
 
 impl PutBucketCors {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "PutBucketCors");
         req.insert_query("cors", "");
         PutBucketCors { req, cors: CorsConfiguration::default() }
     }
 
+    /// Sign the SHA-256 of the request body instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// 对请求体的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    pub fn set_signed_payload(mut self, enable: bool) -> Self {
+        self.req.set_signed_payload(enable);
+        self
+    }
+
     /// Replace the complete set of CORS rules.
     ///
     /// 替换全部 CORS 规则。
@@ -48,13 +55,13 @@ impl PutBucketCors {
     /// Send the request.
     ///
     /// 发送请求。
-    pub async fn send(mut self) -> Result<(), Error> {
+    pub async fn send(mut self) -> Result<ResponseMeta, Error> {
         let body = serde_xml_rs::to_string(&self.cors).map_err(|_| Error::InvalidCharacter)?;
-        self.req.set_body(Full::new(Bytes::from(body)));
-        let response = self.req.send_to_oss()?.await?;
+        self.req.set_body(body);
+        let response = self.req.send_to_oss().await?;
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error(response).await),
         }
     }
@@ -1,5 +1,6 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
@@ -21,11 +22,19 @@ pub struct PutBucketEncryptionSync {
 
 impl PutBucketEncryptionSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "PutBucketEncryption");
         req.insert_query("encryption", "");
         PutBucketEncryptionSync { req, encryption: BucketEncryption::default() }
     }
 
+    /// Sign the SHA-256 of the request body instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// 对请求体的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    pub fn set_signed_payload(mut self, enable: bool) -> Self {
+        self.req.set_signed_payload(enable);
+        self
+    }
+
     /// Set the encryption algorithm (e.g., `AES256`, `KMS`).
     ///
     /// 设置加密算法（如 `AES256`、`KMS`）。
@@ -53,12 +62,12 @@ impl PutBucketEncryptionSync {
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(mut self) -> Result<(), Error> {
+    pub fn send(mut self) -> Result<ResponseMeta, Error> {
         let body = serde_xml_rs::to_string(&self.encryption).map_err(|_| Error::InvalidCharacter)?;
         self.req.set_body(body.into_bytes());
         let response = self.req.send_to_oss()?;
         match response.status() {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
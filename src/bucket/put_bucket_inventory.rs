@@ -1,11 +1,10 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error,
     request::{Oss, OssRequest},
 };
-use bytes::Bytes;
 use http::Method;
-use http_body_util::Full;
 
 /// Configure a bucket inventory task.
 ///
@@ -21,12 +20,20 @@ pub struct PutBucketInventory {
 
 impl PutBucketInventory {
     pub(super) fn new(oss: Oss, inventory_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "PutBucketInventory");
         req.insert_query("inventory", "");
         req.insert_query("inventoryId", inventory_id.into());
         PutBucketInventory { req, body: None }
     }
 
+    /// Sign the SHA-256 of the request body instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// 对请求体的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    pub fn set_signed_payload(mut self, enable: bool) -> Self {
+        self.req.set_signed_payload(enable);
+        self
+    }
+
     /// Provide the inventory configuration XML.
     ///
     /// 提供清单配置 XML。
@@ -38,12 +45,12 @@ impl PutBucketInventory {
     /// Send the request.
     ///
     /// 发送请求。
-    pub async fn send(mut self) -> Result<(), Error> {
+    pub async fn send(mut self) -> Result<ResponseMeta, Error> {
         let body = self.body.ok_or(Error::MissingRequestBody)?;
-        self.req.set_body(Full::new(Bytes::from(body)));
-        let response = self.req.send_to_oss()?.await?;
+        self.req.set_body(body);
+        let response = self.req.send_to_oss().await?;
         match response.status() {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error(response).await),
         }
     }
@@ -1,5 +1,6 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
@@ -19,12 +20,20 @@ pub struct PutBucketInventorySync {
 
 impl PutBucketInventorySync {
     pub(super) fn new(oss: Oss, inventory_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "PutBucketInventory");
         req.insert_query("inventory", "");
         req.insert_query("inventoryId", inventory_id.into());
         PutBucketInventorySync { req, body: None }
     }
 
+    /// Sign the SHA-256 of the request body instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// 对请求体的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    pub fn set_signed_payload(mut self, enable: bool) -> Self {
+        self.req.set_signed_payload(enable);
+        self
+    }
+
     /// Provide the inventory configuration XML.
     ///
     /// 提供清单配置 XML。
@@ -36,12 +45,12 @@ impl PutBucketInventorySync {
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(mut self) -> Result<(), Error> {
+    pub fn send(mut self) -> Result<ResponseMeta, Error> {
         let body = self.body.ok_or(Error::MissingRequestBody)?;
         self.req.set_body(body.into_bytes());
         let response = self.req.send_to_oss()?;
         match response.status() {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
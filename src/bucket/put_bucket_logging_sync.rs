@@ -1,4 +1,5 @@
 use crate::{
+    common::ResponseMeta,
     error::{Error, normal_error_sync},
     request_sync::{Oss, OssRequest},
 };
@@ -12,7 +13,7 @@ pub struct PutBucketLoggingSync {
 }
 impl PutBucketLoggingSync {
     pub(crate) fn new(oss: Oss, target_bucket: impl Into<String>, target_prefix: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "PutBucketLogging");
         req.insert_query("logging", "");
         let body = format!(
             "<?xml version=\"1.0\" encoding=\"UTF-8\"?><BucketLoggingStatus><LoggingEnabled><TargetBucket>{}</TargetBucket><TargetPrefix>{}</TargetPrefix></LoggingEnabled></BucketLoggingStatus>",
@@ -22,12 +23,23 @@ impl PutBucketLoggingSync {
         req.set_body(body.into_bytes());
         PutBucketLoggingSync { req }
     }
+    /// Sign the SHA-256 of the request body instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// 对请求体的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    pub fn set_signed_payload(mut self, enable: bool) -> Self {
+        self.req.set_signed_payload(enable);
+        self
+    }
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(self) -> Result<(), Error> {
+    pub fn send(self) -> Result<ResponseMeta, Error> {
         let response = self.req.send_to_oss()?;
         let status = response.status();
-        if status.is_success() { Ok(()) } else { Err(normal_error_sync(response)) }
+        if status.is_success() {
+            Ok(ResponseMeta::from_headers(response.headers()))
+        } else {
+            Err(normal_error_sync(response))
+        }
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
     Error,
-    common::{Acl, DataRedundancyType, StorageClass},
+    common::{Acl, DataRedundancyType, ResponseMeta, StorageClass},
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
@@ -24,7 +24,18 @@ pub struct PutBucketSync {
 }
 impl PutBucketSync {
     pub(super) fn new(oss: Oss) -> Self {
-        PutBucketSync { req: OssRequest::new(oss, Method::PUT), storage_class: None, data_redundancy_type: None }
+        PutBucketSync {
+            req: OssRequest::new(oss, Method::PUT, "PutBucket"),
+            storage_class: None,
+            data_redundancy_type: None,
+        }
+    }
+    /// Sign the SHA-256 of the request body instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// 对请求体的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    pub fn set_signed_payload(mut self, enable: bool) -> Self {
+        self.req.set_signed_payload(enable);
+        self
     }
     /// Set bucket ACL.
     ///
@@ -79,13 +90,13 @@ impl PutBucketSync {
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(self) -> Result<(), Error> {
+    pub fn send(self) -> Result<ResponseMeta, Error> {
         // Build the HTTP request
         let response = self.req.send_to_oss()?;
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
@@ -1,11 +1,10 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error,
     request::{Oss, OssRequest},
 };
-use bytes::Bytes;
 use http::Method;
-use http_body_util::Full;
 
 use super::{BucketTagging, Tag};
 
@@ -23,7 +22,7 @@ pub struct PutBucketTags {
 
 impl PutBucketTags {
     pub(super) fn new(oss: Oss, tags: Vec<(impl Into<String>, impl Into<String>)>) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "PutBucketTags");
         req.insert_query("tagging", "");
         let mut tagging = BucketTagging::default();
         tagging.tag_set.tags =
@@ -31,6 +30,14 @@ impl PutBucketTags {
         PutBucketTags { req, tagging }
     }
 
+    /// Sign the SHA-256 of the request body instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// 对请求体的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    pub fn set_signed_payload(mut self, enable: bool) -> Self {
+        self.req.set_signed_payload(enable);
+        self
+    }
+
     /// Add more tags.
     ///
     /// 追加标签。
@@ -53,12 +60,12 @@ impl PutBucketTags {
     /// Send the request.
     ///
     /// 发送请求。
-    pub async fn send(mut self) -> Result<(), Error> {
+    pub async fn send(mut self) -> Result<ResponseMeta, Error> {
         let body = serde_xml_rs::to_string(&self.tagging).map_err(|_| Error::InvalidCharacter)?;
-        self.req.set_body(Full::new(Bytes::from(body)));
-        let response = self.req.send_to_oss()?.await?;
+        self.req.set_body(body);
+        let response = self.req.send_to_oss().await?;
         match response.status() {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error(response).await),
         }
     }
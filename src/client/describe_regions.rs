@@ -72,7 +72,7 @@ pub struct DescribeRegions {
 
 impl DescribeRegions {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "DescribeRegions");
         req.insert_query("regions", "");
         DescribeRegions { req }
     }
@@ -102,7 +102,7 @@ impl DescribeRegions {
     /// 发送请求。
     pub async fn send(self) -> Result<Vec<RegionInfo>, Error> {
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
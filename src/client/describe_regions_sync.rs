@@ -72,7 +72,7 @@ pub struct DescribeRegionsSync {
 
 impl DescribeRegionsSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "DescribeRegions");
         req.insert_query("regions", "");
         DescribeRegionsSync { req }
     }
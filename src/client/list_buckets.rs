@@ -104,7 +104,7 @@ pub struct ListBuckets {
 
 impl ListBuckets {
     pub(super) fn new(oss: Oss) -> Self {
-        ListBuckets { req: OssRequest::new(oss, Method::GET) }
+        ListBuckets { req: OssRequest::new(oss, Method::GET, "ListBuckets") }
     }
 
     /// Limit bucket names to those starting with the given prefix.
@@ -159,7 +159,7 @@ impl ListBuckets {
     /// 发送请求。
     pub async fn send(self) -> Result<ListAllMyBuckets, Error> {
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
@@ -104,7 +104,7 @@ pub struct ListBucketsSync {
 
 impl ListBucketsSync {
     pub(super) fn new(oss: Oss) -> Self {
-        ListBucketsSync { req: OssRequest::new(oss, Method::GET) }
+        ListBucketsSync { req: OssRequest::new(oss, Method::GET, "ListBuckets") }
     }
 
     /// Limit bucket names to those starting with the given prefix.
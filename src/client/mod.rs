@@ -11,6 +11,10 @@ pub use self::list_buckets::ListBuckets;
 #[cfg(feature = "_sync-base")]
 pub use self::list_buckets_sync::ListBucketsSync;
 pub use self::oss_client::OssClient;
+#[cfg(feature = "_async-base")]
+pub use self::raw_request::RawRequest;
+#[cfg(feature = "_sync-base")]
+pub use self::raw_request_sync::RawRequestSync;
 
 #[cfg(feature = "_async-base")]
 mod describe_regions;
@@ -21,3 +25,7 @@ mod list_buckets;
 #[cfg(feature = "_sync-base")]
 mod list_buckets_sync;
 mod oss_client;
+#[cfg(feature = "_async-base")]
+mod raw_request;
+#[cfg(feature = "_sync-base")]
+mod raw_request_sync;
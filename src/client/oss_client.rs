@@ -1,8 +1,15 @@
 #[cfg(feature = "_async-base")]
-use super::{DescribeRegions, ListBuckets};
+use super::{DescribeRegions, ListBuckets, RawRequest};
 #[cfg(feature = "_sync-base")]
-use super::{DescribeRegionsSync, ListBucketsSync};
-use crate::{OssBucket, oss::Oss};
+use super::{DescribeRegionsSync, ListBucketsSync, RawRequestSync};
+use crate::common::{AddressingStyle, EndpointKind, SignatureVersion};
+use crate::{CredentialsProvider, Interceptor, OssBucket, Proxy, RetryPolicy, oss::Oss};
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use crate::{Error, TlsConfig};
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use http::Method;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Entry point for OSS, providing service-level APIs such as listing buckets and regions.
 ///
@@ -27,6 +34,24 @@ impl OssClient {
     pub fn new(ak_id: impl Into<String>, ak_secret: impl Into<String>, region: impl Into<String>) -> Self {
         OssClient { oss: Oss::new(ak_id, ak_secret, region) }
     }
+    /// Create a new client whose credentials come from a provider, resolved each time a request is signed.
+    ///
+    /// 创建使用凭证提供者的客户端，每次签名请求时解析凭证。
+    pub fn from_credentials_provider(provider: impl CredentialsProvider + 'static, region: impl Into<String>) -> Self {
+        OssClient::new("", "", region).with_credentials_provider(provider)
+    }
+    /// Resolve credentials from a provider instead of the static AccessKey pair.
+    ///
+    /// Credentials are cached and fetched again shortly before they expire. The provider replaces
+    /// any AccessKey or security token set on this client.
+    ///
+    /// 从凭证提供者获取凭证，替代静态 AccessKey。
+    ///
+    /// 凭证会被缓存，并在即将过期前重新获取。提供者会取代该客户端上设置的 AccessKey 与安全令牌。
+    pub fn with_credentials_provider(mut self, provider: impl CredentialsProvider + 'static) -> Self {
+        self.set_credentials_provider(provider);
+        self
+    }
     /// Disable HTTPS and use HTTP for all requests.
     ///
     /// 禁用 HTTPS，所有请求改为使用 HTTP。
@@ -34,6 +59,43 @@ impl OssClient {
         self.oss.set_https(false);
         self
     }
+    /// Use an endpoint of the given kind for the client's region instead of the public one, for
+    /// example `oss-cn-hangzhou-internal.aliyuncs.com` for [`EndpointKind::Internal`].
+    ///
+    /// 为客户端所在地域使用指定类型的 Endpoint 而非公网 Endpoint，例如 [`EndpointKind::Internal`]
+    /// 对应 `oss-cn-hangzhou-internal.aliyuncs.com`。
+    pub fn with_endpoint_kind(mut self, kind: EndpointKind) -> Self {
+        self.oss.set_endpoint_kind(kind);
+        self
+    }
+    /// Choose between virtual-hosted (`bucket.endpoint/key`) and path-style (`endpoint/bucket/key`)
+    /// URLs for API calls and pre-signed URLs.
+    ///
+    /// Path style suits local emulators and OSS-compatible gateways such as `127.0.0.1:9000`, where
+    /// per-bucket host names do not resolve. Custom domains are unaffected.
+    ///
+    /// 选择 API 调用与预签名 URL 使用虚拟主机风格（`bucket.endpoint/key`）还是路径风格
+    /// （`endpoint/bucket/key`）。
+    ///
+    /// 路径风格适用于本地模拟器及兼容 OSS 的网关（如 `127.0.0.1:9000`），这类地址无法解析按 Bucket
+    /// 区分的域名。不影响自定义域名。
+    pub fn with_addressing_style(mut self, style: AddressingStyle) -> Self {
+        self.oss.set_addressing_style(style);
+        self
+    }
+    /// Sign requests and pre-signed URLs with the given signature version; V4 is the default.
+    ///
+    /// V1 (HMAC-SHA1) is meant for private-cloud deployments and gateways that do not accept V4.
+    /// It ignores the region and does not sign the payload hash, so `with_signed_payload` has no effect.
+    ///
+    /// 使用指定的签名版本对请求与预签名 URL 签名，默认为 V4。
+    ///
+    /// V1（HMAC-SHA1）用于不支持 V4 的专有云部署与网关。V1 不使用地域，也不对请求体哈希签名，
+    /// 因此 `with_signed_payload` 不生效。
+    pub fn with_signature_version(mut self, version: SignatureVersion) -> Self {
+        self.oss.set_signature_version(version);
+        self
+    }
     /// Attach a temporary security token for STS authentication.
     ///
     /// 设置临时安全令牌用于 STS 鉴权。
@@ -41,24 +103,217 @@ impl OssClient {
         self.oss.set_security_token(token);
         self
     }
+    /// Sign the SHA-256 of request bodies instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// In-memory bodies are hashed directly and uploaded files are hashed in a streaming pre-pass.
+    /// Upload and bucket configuration builders can also enable it per request with `set_signed_payload`.
+    ///
+    /// 对请求体的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    ///
+    /// 内存请求体直接计算哈希，上传文件则以流式方式预先计算。上传与 Bucket 配置类构建器也可通过
+    /// `set_signed_payload` 按请求启用。
+    pub fn with_signed_payload(mut self, enable: bool) -> Self {
+        self.oss.set_signed_payload(enable);
+        self
+    }
+    /// Verify uploads and full-object downloads against the CRC-64 OSS reports, failing with
+    /// `Error::CrcMismatch` on a difference. Enabled by default; upload and download builders can
+    /// override it per request with `set_crc_check`.
+    ///
+    /// 使用 OSS 返回的 CRC-64 校验上传与完整对象下载，不一致时返回 `Error::CrcMismatch`。默认开启；
+    /// 上传与下载构建器可通过 `set_crc_check` 按请求覆盖。
+    pub fn with_crc_check(mut self, enable: bool) -> Self {
+        self.oss.set_crc_check(enable);
+        self
+    }
+    /// Set how long an idle pooled connection is kept before it is closed.
+    ///
+    /// All bucket and object handles created from this client share one connection pool.
+    ///
+    /// 设置连接池中空闲连接的保留时长。
+    ///
+    /// 由该客户端创建的所有 Bucket 与对象句柄共享同一个连接池。
+    pub fn with_pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.oss.set_pool_idle_timeout(timeout);
+        self
+    }
+    /// Set the maximum number of idle pooled connections kept per host.
+    ///
+    /// 设置每个主机在连接池中保留的最大空闲连接数。
+    pub fn with_pool_max_idle_per_host(mut self, max_idle: usize) -> Self {
+        self.oss.set_pool_max_idle_per_host(max_idle);
+        self
+    }
+    /// Set the maximum time allowed to establish a TCP connection.
+    ///
+    /// 设置建立 TCP 连接的最长时间。
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.oss.set_connect_timeout(timeout);
+        self
+    }
+    /// Set the maximum idle time while waiting for response data.
+    ///
+    /// Async requests apply it between response body chunks, and to the wait for response headers
+    /// when the request body is held in memory. Sync requests apply it to every read from the connection,
+    /// response headers and body alike.
+    ///
+    /// 设置等待响应数据时的最长空闲时间。
+    ///
+    /// 异步请求在响应体数据块之间生效，请求体在内存中时也作用于等待响应头；
+    /// 同步请求作用于每次从连接读取数据，包括响应头与响应体。
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.oss.set_read_timeout(timeout);
+        self
+    }
+    /// Set the overall deadline for each operation, including retries and reading the response body.
+    ///
+    /// Operations such as `GetObject` and `PutObject` can override it with `set_timeout`.
+    ///
+    /// 设置每个操作的整体截止时间，包括重试与读取响应体。
+    ///
+    /// `GetObject`、`PutObject` 等操作可通过 `set_timeout` 覆盖。
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.oss.set_timeout(timeout);
+        self
+    }
+    /// Route connections through an HTTP `CONNECT` or SOCKS5 proxy.
+    ///
+    /// Without this setting the proxy is taken from `HTTPS_PROXY`, `HTTP_PROXY`, `ALL_PROXY` and
+    /// `NO_PROXY`; pass [`Proxy::disabled()`] to always connect directly.
+    ///
+    /// 通过 HTTP `CONNECT` 或 SOCKS5 代理建立连接。
+    ///
+    /// 未设置时从 `HTTPS_PROXY`、`HTTP_PROXY`、`ALL_PROXY` 与 `NO_PROXY` 读取代理；传入
+    /// [`Proxy::disabled()`] 则始终直接连接。
+    pub fn with_proxy(mut self, proxy: Proxy) -> Self {
+        self.oss.set_proxy(proxy);
+        self
+    }
+    /// Apply custom TLS settings: trusted roots, a client certificate for mutual TLS and a minimum
+    /// protocol version.
+    ///
+    /// Fails if the certificates or key are invalid, or if the enabled TLS backend cannot honour
+    /// the settings.
+    ///
+    /// 应用自定义 TLS 配置：受信任的根证书、用于双向 TLS 的客户端证书以及最低协议版本。
+    ///
+    /// 证书或私钥无效，或所启用的 TLS 后端无法满足该配置时返回错误。
+    #[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+    pub fn with_tls_config(mut self, tls: TlsConfig) -> Result<Self, Error> {
+        self.oss.set_tls_config(&tls)?;
+        Ok(self)
+    }
+    /// Set the retry policy used by every request sent from this client and its handles.
+    ///
+    /// Pass [`RetryPolicy::none()`] to disable retries.
+    ///
+    /// 设置该客户端及其句柄发送请求时使用的重试策略。
+    ///
+    /// 传入 [`RetryPolicy::none()`] 可禁用重试。
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.oss.set_retry_policy(policy);
+        self
+    }
+    /// Register an interceptor that sees every request after signing and every response before
+    /// error decoding, for all handles created from this client afterwards.
+    ///
+    /// Interceptors run in registration order.
+    ///
+    /// 注册拦截器：此后由该客户端创建的所有句柄发出的请求（签名后）与收到的响应（解析错误前）都会经过它。
+    ///
+    /// 拦截器按注册顺序执行。
+    pub fn with_interceptor(mut self, interceptor: impl Interceptor + 'static) -> Self {
+        self.add_interceptor(interceptor);
+        self
+    }
+    /// Register an interceptor in place.
+    ///
+    /// 就地注册拦截器。
+    pub fn add_interceptor(&mut self, interceptor: impl Interceptor + 'static) {
+        self.oss.add_interceptor(Arc::new(interceptor));
+    }
     /// Override the endpoint used for subsequent requests.
     ///
     /// 覆盖后续请求使用的 Endpoint。
     pub fn set_endpoint(&mut self, endpoint: impl Into<String>) {
         self.oss.set_endpoint(endpoint);
     }
+    /// Switch to an endpoint of the given kind, taking it from `DescribeRegions` when OSS reports one.
+    ///
+    /// The region is looked up once; the result is cached and shared by clones of this client.
+    /// Kinds that `DescribeRegions` does not report, such as dual-stack, are derived from the region.
+    ///
+    /// 切换为指定类型的 Endpoint，OSS 的 `DescribeRegions` 返回了该类型时优先使用其结果。
+    ///
+    /// 每个地域只查询一次，结果会被缓存并由该客户端的克隆共享。`DescribeRegions` 不返回的类型
+    /// （如双栈）根据地域推导。
+    #[cfg(feature = "_async-base")]
+    pub async fn resolve_endpoint(&mut self, kind: EndpointKind) -> Result<(), Error> {
+        if self.oss.needs_region_lookup(kind) {
+            let regions = self.describe_regions().set_regions(format!("oss-{}", self.oss.region)).send().await?;
+            for info in regions {
+                self.oss.cache_region_endpoints(
+                    &info.region,
+                    &info.internet_endpoint,
+                    &info.internal_endpoint,
+                    &info.accelerate_endpoint,
+                );
+            }
+        }
+        self.oss.set_endpoint_kind(kind);
+        Ok(())
+    }
+    /// Switch to an endpoint of the given kind, taking it from `DescribeRegions` when OSS reports one (sync).
+    ///
+    /// 切换为指定类型的 Endpoint，OSS 的 `DescribeRegions` 返回了该类型时优先使用其结果（同步）。
+    #[cfg(feature = "_sync-base")]
+    pub fn resolve_endpoint_sync(&mut self, kind: EndpointKind) -> Result<(), Error> {
+        if self.oss.needs_region_lookup(kind) {
+            let regions = self.describe_regions_sync().set_regions(format!("oss-{}", self.oss.region)).send()?;
+            for info in regions {
+                self.oss.cache_region_endpoints(
+                    &info.region,
+                    &info.internet_endpoint,
+                    &info.internal_endpoint,
+                    &info.accelerate_endpoint,
+                );
+            }
+        }
+        self.oss.set_endpoint_kind(kind);
+        Ok(())
+    }
     /// Update the security token in place for reuse.
     ///
     /// 就地更新安全令牌，便于复用。
     pub fn set_security_token(&mut self, token: impl Into<String>) {
         self.oss.set_security_token(token);
     }
+    /// Replace the credentials provider in place.
+    ///
+    /// 就地替换凭证提供者。
+    pub fn set_credentials_provider(&mut self, provider: impl CredentialsProvider + 'static) {
+        self.oss.set_credentials_provider(Arc::new(provider));
+    }
     /// Bind a bucket name and create a bucket handle.
     ///
     /// 绑定 Bucket 名称并创建桶句柄。
     pub fn bucket(&self, bucket: impl Into<String>) -> OssBucket {
         OssBucket::new(self.oss.clone(), bucket)
     }
+    /// Build a signed request to an OSS API not wrapped by this crate, addressed to the service endpoint.
+    ///
+    /// 构建发送至任意 OSS API 的签名请求（目标为服务 Endpoint），用于本库尚未封装的接口。
+    #[cfg(feature = "_async-base")]
+    pub fn raw_request(&self, method: Method) -> RawRequest {
+        RawRequest::new(self.oss.clone(), method)
+    }
+    /// Build a signed request to an OSS API not wrapped by this crate, addressed to the service endpoint (sync).
+    ///
+    /// 构建发送至任意 OSS API 的签名请求（目标为服务 Endpoint），用于本库尚未封装的接口（同步）。
+    #[cfg(feature = "_sync-base")]
+    pub fn raw_request_sync(&self, method: Method) -> RawRequestSync {
+        RawRequestSync::new(self.oss.clone(), method)
+    }
     /// List OSS regions and their endpoints.
     ///
     /// 列举 OSS 支持的地域与对应的 Endpoint。
@@ -110,4 +365,88 @@ mod tests {
         assert_eq!(bucket.oss.endpoint.as_ref(), "endpoint");
         assert_eq!(bucket.oss.security_token.as_deref(), Some("token2"));
     }
+
+    #[test]
+    fn test_client_addressing_style() {
+        let client = OssClient::new("id", "secret", "cn-hangzhou");
+        assert_eq!(client.oss.addressing_style, AddressingStyle::VirtualHosted);
+        let client = client.with_addressing_style(AddressingStyle::Path);
+        assert_eq!(client.bucket("bucket").oss.addressing_style, AddressingStyle::Path);
+    }
+
+    #[test]
+    fn test_client_signature_version() {
+        let client = OssClient::new("id", "secret", "cn-hangzhou");
+        assert_eq!(client.oss.signature_version, SignatureVersion::V4);
+        let client = client.with_signature_version(SignatureVersion::V1);
+        assert_eq!(client.bucket("bucket").oss.signature_version, SignatureVersion::V1);
+    }
+
+    #[cfg(feature = "_async-base")]
+    #[test]
+    fn test_client_resolve_endpoint() {
+        use crate::test_util::{MockResponse, MockServer};
+        let server = MockServer::start(vec![MockResponse::new(
+            200,
+            "<RegionInfoList><RegionInfo><Region>oss-cn-hangzhou</Region>\
+             <InternetEndpoint>oss-cn-hangzhou.aliyuncs.com</InternetEndpoint>\
+             <InternalEndpoint>oss-cn-hangzhou-internal.aliyuncs.com</InternalEndpoint>\
+             <AccelerateEndpoint>oss-accelerate.aliyuncs.com</AccelerateEndpoint></RegionInfo></RegionInfoList>",
+        )]);
+        let mut client = OssClient::new("id", "secret", "cn-hangzhou").disable_https();
+        client.set_endpoint(server.host());
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(client.resolve_endpoint(EndpointKind::Internal)).unwrap();
+        assert_eq!(client.oss.endpoint.as_ref(), "oss-cn-hangzhou-internal.aliyuncs.com");
+        assert_eq!(server.requests()[0].target, "/?regions=oss-cn-hangzhou");
+
+        // answered from the cache, then derived without a lookup
+        runtime.block_on(client.clone().resolve_endpoint(EndpointKind::Accelerate)).unwrap();
+        runtime.block_on(client.resolve_endpoint(EndpointKind::Dualstack)).unwrap();
+        assert_eq!(client.oss.endpoint.as_ref(), "cn-hangzhou.oss.aliyuncs.com");
+        assert_eq!(server.requests().len(), 1);
+        let client = client.with_endpoint_kind(EndpointKind::Accelerate);
+        assert_eq!(client.bucket("bucket").oss.endpoint.as_ref(), "oss-accelerate.aliyuncs.com");
+    }
+
+    #[test]
+    fn test_client_pool_shared_with_handles() {
+        let client = OssClient::new("id", "secret", "cn-hangzhou")
+            .with_pool_idle_timeout(Duration::from_secs(30))
+            .with_pool_max_idle_per_host(16);
+        let bucket = client.bucket("bucket");
+        assert!(std::sync::Arc::ptr_eq(&client.oss.transport, &bucket.oss.transport));
+        assert_eq!(bucket.oss.transport.config().pool_max_idle_per_host, Some(16));
+    }
+
+    #[test]
+    fn test_client_credentials_provider() {
+        let provider = crate::credentials::StaticCredentialsProvider::new(
+            crate::Credentials::new("provided", "secret").with_security_token("token"),
+        );
+        let client = OssClient::from_credentials_provider(provider, "cn-hangzhou");
+        let credentials = client.bucket("bucket").oss.credentials().unwrap();
+        assert_eq!(credentials.access_key_id(), "provided");
+        assert_eq!(credentials.security_token(), Some("token"));
+    }
+
+    #[test]
+    fn test_client_interceptors_propagate() {
+        struct Noop;
+        impl Interceptor for Noop {}
+        let client = OssClient::new("id", "secret", "cn-hangzhou").with_interceptor(Noop);
+        assert_eq!(client.bucket("bucket").oss.interceptors.len(), 1);
+    }
+
+    #[test]
+    fn test_client_timeouts() {
+        let client = OssClient::new("id", "secret", "cn-hangzhou")
+            .with_connect_timeout(Duration::from_secs(3))
+            .with_read_timeout(Duration::from_secs(10))
+            .with_timeout(Duration::from_secs(60));
+        let config = client.bucket("bucket").oss.transport.config().clone();
+        assert_eq!(config.connect_timeout, Some(Duration::from_secs(3)));
+        assert_eq!(config.read_timeout, Some(Duration::from_secs(10)));
+        assert_eq!(config.timeout, Some(Duration::from_secs(60)));
+    }
 }
@@ -0,0 +1,109 @@
+use crate::{
+    Error, ResponseBody,
+    error::normal_error,
+    request::{Oss, OssRequest},
+};
+use bytes::Bytes;
+use http::{Method, Response};
+use std::time::Duration;
+
+/// Signed request to any OSS API, for endpoints this crate does not wrap yet.
+///
+/// The request is addressed to the client, bucket or object it was created from, signed and sent
+/// through the shared transport with the client's retry policy, timeouts and interceptors.
+///
+/// 发送至任意 OSS API 的签名请求，用于本库尚未封装的接口。
+///
+/// 请求的目标为创建它的客户端、Bucket 或对象，签名后通过共享的传输层发送，并沿用客户端的重试策略、
+/// 超时设置与拦截器。
+pub struct RawRequest {
+    req: OssRequest,
+}
+impl RawRequest {
+    pub(crate) fn new(oss: Oss, method: Method) -> Self {
+        RawRequest { req: OssRequest::new(oss, method, "RawRequest") }
+    }
+    /// Add a query parameter; use an empty value for sub-resources such as `acl` or `uploads`.
+    ///
+    /// 添加查询参数；`acl`、`uploads` 等子资源使用空值。
+    pub fn insert_query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.req.insert_query(key, value);
+        self
+    }
+    /// Add a request header.
+    ///
+    /// 添加请求头。
+    pub fn insert_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.req.insert_header(key, value);
+        self
+    }
+    /// Set the request body; in-memory bodies can be replayed when the request is retried.
+    ///
+    /// 设置请求体；内存请求体可在重试时重放。
+    pub fn set_body(mut self, body: impl Into<Bytes>) -> Self {
+        self.req.set_body(body);
+        self
+    }
+    /// Override the client's overall timeout for this request.
+    ///
+    /// 为本次请求覆盖客户端的整体超时时间。
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.req.set_timeout(timeout);
+        self
+    }
+    /// Send the request and return the successful response with its body still streaming.
+    ///
+    /// Non-success responses are decoded into `Error::OssError` (or `Error::OssInvalidError`).
+    ///
+    /// 发送请求并返回成功的响应，响应体以流式读取。
+    ///
+    /// 非成功响应会解析为 `Error::OssError`（或 `Error::OssInvalidError`）。
+    pub async fn send(self) -> Result<Response<ResponseBody>, Error> {
+        let response = self.req.send_to_oss().await?;
+        match response.status() {
+            code if code.is_success() => Ok(response),
+            _ => Err(normal_error(response).await),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{MockResponse, MockServer, oss_error_body};
+    use http_body_util::BodyExt;
+
+    #[test]
+    fn test_raw_request_round_trip() {
+        let server = MockServer::start(vec![
+            MockResponse::new(200, "<Result/>").header("x-oss-request-id", "req-1"),
+            MockResponse::new(404, oss_error_body("NoSuchKey")),
+        ]);
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_custom_domain(server.host());
+        oss.set_https(false);
+        oss.set_object("key");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let response = runtime
+            .block_on(
+                RawRequest::new(oss.clone(), Method::PUT)
+                    .insert_query("newApi", "")
+                    .insert_header("x-oss-new-header", "v")
+                    .set_body("payload")
+                    .send(),
+            )
+            .unwrap();
+        assert_eq!(response.headers()["x-oss-request-id"], "req-1");
+        let body = runtime.block_on(response.into_body().collect()).unwrap().to_bytes();
+        assert_eq!(body.as_ref(), b"<Result/>");
+        let request = &server.requests()[0];
+        assert_eq!(request.method, "PUT");
+        assert_eq!(request.target, "/key?newApi");
+        assert_eq!(request.header("x-oss-new-header"), Some("v"));
+        assert_eq!(request.body, b"payload");
+        assert!(request.header("authorization").is_some());
+
+        let error = runtime.block_on(RawRequest::new(oss, Method::GET).send()).unwrap_err();
+        assert!(matches!(error, Error::OssError(status, _) if status == 404));
+    }
+}
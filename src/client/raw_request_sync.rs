@@ -0,0 +1,68 @@
+use crate::{
+    Error,
+    error::normal_error_sync,
+    request_sync::{Oss, OssRequest},
+};
+use http::{Method, Response};
+use std::time::Duration;
+use ureq::Body;
+
+/// Signed request to any OSS API, for endpoints this crate does not wrap yet (sync).
+///
+/// The request is addressed to the client, bucket or object it was created from, signed and sent
+/// through the shared transport with the client's retry policy, timeouts and interceptors.
+///
+/// 发送至任意 OSS API 的签名请求，用于本库尚未封装的接口（同步）。
+///
+/// 请求的目标为创建它的客户端、Bucket 或对象，签名后通过共享的传输层发送，并沿用客户端的重试策略、
+/// 超时设置与拦截器。
+pub struct RawRequestSync {
+    req: OssRequest,
+}
+impl RawRequestSync {
+    pub(crate) fn new(oss: Oss, method: Method) -> Self {
+        RawRequestSync { req: OssRequest::new(oss, method, "RawRequest") }
+    }
+    /// Add a query parameter; use an empty value for sub-resources such as `acl` or `uploads`.
+    ///
+    /// 添加查询参数；`acl`、`uploads` 等子资源使用空值。
+    pub fn insert_query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.req.insert_query(key, value);
+        self
+    }
+    /// Add a request header.
+    ///
+    /// 添加请求头。
+    pub fn insert_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.req.insert_header(key, value);
+        self
+    }
+    /// Set the request body.
+    ///
+    /// 设置请求体。
+    pub fn set_body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.req.set_body(body.into());
+        self
+    }
+    /// Override the client's overall timeout for this request.
+    ///
+    /// 为本次请求覆盖客户端的整体超时时间。
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.req.set_timeout(timeout);
+        self
+    }
+    /// Send the request and return the successful response with its body still streaming.
+    ///
+    /// Non-success responses are decoded into `Error::OssError` (or `Error::OssInvalidError`).
+    ///
+    /// 发送请求并返回成功的响应，响应体以流式读取。
+    ///
+    /// 非成功响应会解析为 `Error::OssError`（或 `Error::OssInvalidError`）。
+    pub fn send(self) -> Result<Response<Body>, Error> {
+        let response = self.req.send_to_oss()?;
+        match response.status() {
+            code if code.is_success() => Ok(response),
+            _ => Err(normal_error_sync(response)),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Clock-skew tracking used to correct signing timestamps.
+//!
+//! 用于校正签名时间的时钟偏差跟踪。
+use crate::{Error, OssErrorCode, common::parse_gmt};
+use http::HeaderMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use time::{Duration, OffsetDateTime};
+
+/// Differences smaller than this between the server and the corrected local clock are ignored,
+/// which absorbs network latency and the one-second resolution of the `Date` header.
+const SKEW_THRESHOLD: Duration = Duration::seconds(30);
+
+/// Offset between the OSS server clock and the local clock, shared by every handle of a client.
+///
+/// OSS 服务器时钟与本地时钟之间的偏差，由同一客户端的所有句柄共享。
+#[derive(Debug, Default)]
+pub(crate) struct ClockSkew {
+    offset_millis: AtomicI64,
+}
+
+impl ClockSkew {
+    /// Current time corrected by the recorded offset.
+    ///
+    /// 按记录的偏差校正后的当前时间。
+    pub fn now(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc() + self.offset()
+    }
+
+    pub fn offset(&self) -> Duration {
+        Duration::milliseconds(self.offset_millis.load(Ordering::Relaxed))
+    }
+
+    /// Compare the response `Date` header with the corrected clock and record a new offset when
+    /// they are too far apart. Returns whether the offset changed.
+    ///
+    /// 将响应的 `Date` 头与校正后的时钟比较，差距过大时记录新的偏差。返回偏差是否发生变化。
+    pub fn observe(&self, headers: &HeaderMap) -> bool {
+        let Some(server) = headers.get(http::header::DATE).and_then(|v| v.to_str().ok()).and_then(parse_gmt) else {
+            return false;
+        };
+        self.observe_server_time(server, OffsetDateTime::now_utc())
+    }
+
+    fn observe_server_time(&self, server: OffsetDateTime, local: OffsetDateTime) -> bool {
+        if (server - (local + self.offset())).abs() <= SKEW_THRESHOLD {
+            return false;
+        }
+        let offset = server - local;
+        self.offset_millis.store(offset.whole_milliseconds() as i64, Ordering::Relaxed);
+        true
+    }
+}
+
+/// Whether OSS rejected the request because its signing time was too far off.
+///
+/// OSS 是否因签名时间偏差过大而拒绝了请求。
+pub(crate) fn is_time_skewed(error: &Error) -> bool {
+    matches!(error, Error::OssError(_, oss_error) if oss_error.error_code() == OssErrorCode::RequestTimeTooSkewed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::format_gmt;
+
+    #[test]
+    fn test_observe_records_large_offsets_only() {
+        let clock = ClockSkew::default();
+        let local = OffsetDateTime::now_utc();
+        assert!(!clock.observe_server_time(local + Duration::seconds(5), local));
+        assert_eq!(clock.offset(), Duration::ZERO);
+        assert!(clock.observe_server_time(local + Duration::minutes(20), local));
+        assert_eq!(clock.offset(), Duration::minutes(20));
+        assert!(!clock.observe_server_time(local + Duration::minutes(20) + Duration::seconds(2), local));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(http::header::DATE, format_gmt(OffsetDateTime::now_utc()).parse().unwrap());
+        assert!(clock.observe(&headers));
+        assert!(clock.offset().abs() < Duration::seconds(2));
+    }
+}
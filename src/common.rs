@@ -1,19 +1,19 @@
 //! Common data definitions.
 //!
 //! 通用数据定义。
+#[cfg(feature = "_async-base")]
+use crate::{Error, transport::ResponseBody};
 #[cfg(any(feature = "_async-base", feature = "_sync-base"))]
 use bytes::Bytes;
 #[cfg(feature = "_async-base")]
 use http_body_util::BodyExt;
-#[cfg(feature = "_async-base")]
-use hyper::body::Incoming;
 use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
 use serde_derive::{Deserialize, Serialize};
 use std::fmt;
 #[cfg(feature = "_sync-base")]
 use std::io::Read;
 use std::sync::OnceLock;
-use time::{OffsetDateTime, UtcOffset, format_description};
+use time::{OffsetDateTime, PrimitiveDateTime, UtcOffset, format_description};
 #[cfg(feature = "_sync-base")]
 use ureq::Body;
 
@@ -40,7 +40,7 @@ pub(crate) fn invalid_metadata_key(input: &str) -> bool {
 
 #[cfg(feature = "_async-base")]
 #[inline]
-pub(crate) async fn body_to_bytes(body: Incoming) -> Result<Bytes, hyper::Error> {
+pub(crate) async fn body_to_bytes(body: ResponseBody) -> Result<Bytes, Error> {
     Ok(body.collect().await?.to_bytes())
 }
 
@@ -53,15 +53,171 @@ pub(crate) fn body_to_bytes_sync(body: Body) -> Result<Bytes, std::io::Error> {
     Ok(Bytes::from(buf))
 }
 
+// Hex-encoded SHA-256, used for signed payloads
+#[inline]
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
+    hex(aws_lc_rs::digest::digest(&aws_lc_rs::digest::SHA256, data).as_ref())
+}
+
+// Hex-encoded V4 signature: HMAC-SHA256 of the string to sign with the key derived from the
+// secret, date and region
+pub(crate) fn signature_v4(access_key_secret: &str, date_short: &str, region: &str, string_to_sign: &str) -> String {
+    use aws_lc_rs::hmac::{HMAC_SHA256, Key, sign};
+    let key = format!("aliyun_v4{}", access_key_secret);
+    let k_date = sign(&Key::new(HMAC_SHA256, key.as_bytes()), date_short.as_bytes());
+    let k_region = sign(&Key::new(HMAC_SHA256, k_date.as_ref()), region.as_bytes());
+    let k_service = sign(&Key::new(HMAC_SHA256, k_region.as_ref()), b"oss");
+    let k_sign = sign(&Key::new(HMAC_SHA256, k_service.as_ref()), b"aliyun_v4_request");
+    hex(sign(&Key::new(HMAC_SHA256, k_sign.as_ref()), string_to_sign.as_bytes()).as_ref())
+}
+
+/// Base64 MD5 of the body, as sent in `Content-MD5`.
+///
+/// 请求体的 Base64 编码 MD5，即 `Content-MD5` 的值。
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+pub(crate) fn md5_base64(data: &[u8]) -> String {
+    use base64::{Engine, engine::general_purpose};
+    use md5::{Digest, Md5};
+    general_purpose::STANDARD.encode(Md5::digest(data))
+}
+
+/// Digests of a file body, computed only when requested.
+///
+/// 文件请求体的摘要，仅在需要时计算。
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+#[derive(Debug, Default)]
+pub(crate) struct FileDigests {
+    /// Hex SHA-256 for a signed payload.
+    ///
+    /// 用于签名请求体的十六进制 SHA-256。
+    pub sha256: Option<String>,
+    /// Base64 MD5 for `Content-MD5`.
+    ///
+    /// 用于 `Content-MD5` 的 Base64 MD5。
+    pub md5: Option<String>,
+}
+
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+struct Digester {
+    sha256: Option<aws_lc_rs::digest::Context>,
+    md5: Option<md5::Md5>,
+}
+
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+impl Digester {
+    fn new(sha256: bool, md5: bool) -> Self {
+        use md5::Digest;
+        Digester {
+            sha256: sha256.then(|| aws_lc_rs::digest::Context::new(&aws_lc_rs::digest::SHA256)),
+            md5: md5.then(md5::Md5::new),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.sha256.is_none() && self.md5.is_none()
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        use md5::Digest;
+        if let Some(context) = &mut self.sha256 {
+            context.update(data);
+        }
+        if let Some(hasher) = &mut self.md5 {
+            hasher.update(data);
+        }
+    }
+
+    fn finish(self) -> FileDigests {
+        use base64::{Engine, engine::general_purpose};
+        use md5::Digest;
+        FileDigests {
+            sha256: self.sha256.map(|context| hex(context.finish().as_ref())),
+            md5: self.md5.map(|hasher| general_purpose::STANDARD.encode(hasher.finalize())),
+        }
+    }
+}
+
+// Hash the next `len` bytes of a file in one streaming pre-pass feeding every requested digest, then seek back so
+// the same bytes can be uploaded
+#[cfg(feature = "_async-base")]
+pub(crate) async fn digest_file(
+    file: &mut tokio::fs::File,
+    len: u64,
+    sha256: bool,
+    md5: bool,
+) -> Result<FileDigests, std::io::Error> {
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+    let mut digester = Digester::new(sha256, md5);
+    if digester.is_empty() {
+        return Ok(FileDigests::default());
+    }
+    let start = file.stream_position().await?;
+    let mut buf = vec![0; 131072];
+    let mut remaining = len;
+    while remaining > 0 {
+        let limit = buf.len().min(remaining as usize);
+        let read = file.read(&mut buf[..limit]).await?;
+        if read == 0 {
+            break;
+        }
+        digester.update(&buf[..read]);
+        remaining -= read as u64;
+    }
+    file.seek(std::io::SeekFrom::Start(start)).await?;
+    Ok(digester.finish())
+}
+
+// Blocking version of `digest_file`
+#[cfg(feature = "_sync-base")]
+pub(crate) fn digest_file_sync(
+    file: &mut std::fs::File,
+    len: u64,
+    sha256: bool,
+    md5: bool,
+) -> Result<FileDigests, std::io::Error> {
+    use std::io::{Seek, SeekFrom};
+    let mut digester = Digester::new(sha256, md5);
+    if digester.is_empty() {
+        return Ok(FileDigests::default());
+    }
+    let start = file.stream_position()?;
+    let mut buf = vec![0; 131072];
+    let mut remaining = len;
+    while remaining > 0 {
+        let limit = buf.len().min(remaining as usize);
+        let read = file.read(&mut buf[..limit])?;
+        if read == 0 {
+            break;
+        }
+        digester.update(&buf[..read]);
+        remaining -= read as u64;
+    }
+    file.seek(SeekFrom::Start(start))?;
+    Ok(digester.finish())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
 static GMT_FORMAT: OnceLock<Vec<format_description::FormatItem<'static>>> = OnceLock::new();
 
-#[inline]
-pub(crate) fn format_gmt(datetime: OffsetDateTime) -> String {
-    let format = GMT_FORMAT.get_or_init(|| {
+fn gmt_format() -> &'static [format_description::FormatItem<'static>] {
+    GMT_FORMAT.get_or_init(|| {
         format_description::parse("[weekday repr:short], [day] [month repr:short] [year] [hour]:[minute]:[second] GMT")
             .expect("valid format")
-    });
-    datetime.to_offset(UtcOffset::UTC).format(format).expect("formatting")
+    })
+}
+
+#[inline]
+pub(crate) fn format_gmt(datetime: OffsetDateTime) -> String {
+    datetime.to_offset(UtcOffset::UTC).format(gmt_format()).expect("formatting")
+}
+
+// Parse an HTTP date such as the `Date` response header
+#[inline]
+pub(crate) fn parse_gmt(input: &str) -> Option<OffsetDateTime> {
+    PrimitiveDateTime::parse(input.trim(), gmt_format()).ok().map(PrimitiveDateTime::assume_utc)
 }
 
 // -------------------------- Common data --------------------------
@@ -70,26 +226,23 @@ pub(crate) fn format_gmt(datetime: OffsetDateTime) -> String {
 ///
 /// 访问权限（ACL）。
 #[derive(Debug, Deserialize, Clone)]
+#[serde(try_from = "String")]
 pub enum Acl {
     /// Only for object ACL; the object inherits the bucket ACL.
     ///
     /// 仅用于对象 ACL；表示对象继承 Bucket ACL。
-    #[serde(rename = "default")]
     Default,
     /// Private; all requests require authorization.
     ///
     /// 私有；所有请求需要授权。
-    #[serde(rename = "private")]
     Private,
     /// Public read; anonymous reads are allowed.
     ///
     /// 公共读；允许匿名读取。
-    #[serde(rename = "public-read")]
     PublicRead,
     /// Public read/write; anonymous reads and writes are allowed.
     ///
     /// 公共读写；允许匿名读写。
-    #[serde(rename = "public-read-write")]
     PublicReadWrite,
 }
 impl fmt::Display for Acl {
@@ -103,11 +256,106 @@ impl fmt::Display for Acl {
         write!(f, "{}", value)
     }
 }
+// serde-xml-rs reads enums as child elements, so text values are parsed from a string
+impl TryFrom<String> for Acl {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "default" => Ok(Acl::Default),
+            "private" => Ok(Acl::Private),
+            "public-read" => Ok(Acl::PublicRead),
+            "public-read-write" => Ok(Acl::PublicReadWrite),
+            _ => Err(format!("unknown ACL: {}", value)),
+        }
+    }
+}
+
+/// How the bucket name is placed in request URLs.
+///
+/// 请求 URL 中 Bucket 名称的放置方式。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AddressingStyle {
+    /// `https://bucket.endpoint/key`, the OSS default.
+    ///
+    /// `https://bucket.endpoint/key`，OSS 的默认方式。
+    #[default]
+    VirtualHosted,
+    /// `https://endpoint/bucket/key`, for emulators and OSS-compatible gateways that cannot resolve
+    /// per-bucket host names. Custom domains are unaffected.
+    ///
+    /// `https://endpoint/bucket/key`，适用于无法解析按 Bucket 区分的域名的模拟器与兼容 OSS 的网关。
+    /// 不影响自定义域名。
+    Path,
+}
+
+/// Kind of OSS endpoint derived from the region.
+///
+/// 根据地域推导的 OSS Endpoint 类型。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EndpointKind {
+    /// `oss-<region>.aliyuncs.com`, the public endpoint.
+    ///
+    /// `oss-<region>.aliyuncs.com`，公网 Endpoint。
+    #[default]
+    Public,
+    /// `oss-<region>-internal.aliyuncs.com`, reachable from ECS instances and VPCs in the same region
+    /// without internet traffic charges.
+    ///
+    /// `oss-<region>-internal.aliyuncs.com`，同地域的 ECS 实例与 VPC 可访问，不产生外网流量费用。
+    Internal,
+    /// `oss-accelerate.aliyuncs.com`, transfer acceleration; the bucket must have it enabled.
+    ///
+    /// `oss-accelerate.aliyuncs.com`，传输加速，Bucket 需已开启传输加速。
+    Accelerate,
+    /// `oss-accelerate-overseas.aliyuncs.com`, transfer acceleration for regions outside the Chinese
+    /// mainland.
+    ///
+    /// `oss-accelerate-overseas.aliyuncs.com`，中国内地以外地域的传输加速。
+    AccelerateOverseas,
+    /// `<region>.oss.aliyuncs.com`, reachable over both IPv4 and IPv6.
+    ///
+    /// `<region>.oss.aliyuncs.com`，同时支持 IPv4 与 IPv6 访问。
+    Dualstack,
+}
+
+impl EndpointKind {
+    /// Host name of this kind of endpoint for a region such as `cn-hangzhou`.
+    ///
+    /// 该类型 Endpoint 在指定地域（如 `cn-hangzhou`）的主机名。
+    pub fn endpoint(self, region: &str) -> String {
+        match self {
+            EndpointKind::Public => format!("oss-{}.aliyuncs.com", region),
+            EndpointKind::Internal => format!("oss-{}-internal.aliyuncs.com", region),
+            EndpointKind::Accelerate => "oss-accelerate.aliyuncs.com".to_string(),
+            EndpointKind::AccelerateOverseas => "oss-accelerate-overseas.aliyuncs.com".to_string(),
+            EndpointKind::Dualstack => format!("{}.oss.aliyuncs.com", region),
+        }
+    }
+}
+
+/// Signature algorithm used to authenticate requests and pre-signed URLs.
+///
+/// 用于请求与预签名 URL 鉴权的签名算法。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SignatureVersion {
+    /// Legacy HMAC-SHA1 signature (`Authorization: OSS <id>:<signature>`, `Expires`/`Signature`
+    /// URL parameters), for private-cloud deployments and gateways without V4 support.
+    ///
+    /// 旧版 HMAC-SHA1 签名（`Authorization: OSS <id>:<signature>`、`Expires`/`Signature` URL
+    /// 参数），适用于不支持 V4 的专有云部署与网关。
+    V1,
+    /// Signature V4 (`OSS4-HMAC-SHA256`).
+    ///
+    /// V4 签名（`OSS4-HMAC-SHA256`）。
+    #[default]
+    V4,
+}
 
 /// Storage class.
 ///
 /// 存储类型。
 #[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq)]
+#[serde(try_from = "String")]
 pub enum StorageClass {
     /// Standard storage.
     ///
@@ -141,11 +389,25 @@ impl fmt::Display for StorageClass {
         }
     }
 }
+impl TryFrom<String> for StorageClass {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "Standard" => Ok(StorageClass::Standard),
+            "IA" => Ok(StorageClass::IA),
+            "Archive" => Ok(StorageClass::Archive),
+            "ColdArchive" => Ok(StorageClass::ColdArchive),
+            "DeepColdArchive" => Ok(StorageClass::DeepColdArchive),
+            _ => Err(format!("unknown storage class: {}", value)),
+        }
+    }
+}
 
 /// Data redundancy type.
 ///
 /// 数据冗余类型。
 #[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq)]
+#[serde(try_from = "String")]
 pub enum DataRedundancyType {
     /// Local redundancy (LRS) stores data on multiple devices within one zone.
     ///
@@ -164,6 +426,16 @@ impl fmt::Display for DataRedundancyType {
         }
     }
 }
+impl TryFrom<String> for DataRedundancyType {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "LRS" => Ok(DataRedundancyType::LRS),
+            "ZRS" => Ok(DataRedundancyType::ZRS),
+            _ => Err(format!("unknown data redundancy type: {}", value)),
+        }
+    }
+}
 
 /// Restore priority.
 ///
@@ -287,10 +559,205 @@ pub struct BucketAcl {
     pub acl: Acl,
 }
 
+/// Metadata OSS returns with a response, kept for auditing and follow-up requests.
+///
+/// OSS 随响应返回的元数据，可用于审计与后续请求。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseMeta {
+    /// Request ID (`x-oss-request-id`).
+    ///
+    /// 请求 ID（`x-oss-request-id`）。
+    pub request_id: Option<String>,
+    /// ETag as returned by OSS, quotes included.
+    ///
+    /// OSS 返回的 ETag，包含引号。
+    pub etag: Option<String>,
+    /// Version ID (`x-oss-version-id`) on versioned buckets.
+    ///
+    /// 开启版本控制的 Bucket 返回的版本 ID（`x-oss-version-id`）。
+    pub version_id: Option<String>,
+    /// CRC-64/ECMA of the object (`x-oss-hash-crc64ecma`).
+    ///
+    /// 对象的 CRC-64/ECMA 校验值（`x-oss-hash-crc64ecma`）。
+    pub crc64: Option<u64>,
+    /// Whether a delete marker was created or removed (`x-oss-delete-marker`).
+    ///
+    /// 是否创建或删除了删除标记（`x-oss-delete-marker`）。
+    pub delete_marker: bool,
+}
+
+impl ResponseMeta {
+    pub(crate) fn from_headers(headers: &http::HeaderMap) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).map(str::to_owned);
+        ResponseMeta {
+            request_id: header("x-oss-request-id"),
+            etag: header("etag"),
+            version_id: header("x-oss-version-id"),
+            crc64: header("x-oss-hash-crc64ecma").and_then(|v| v.parse().ok()),
+            delete_marker: header("x-oss-delete-marker").is_some_and(|v| v == "true"),
+        }
+    }
+}
+
+/// Result of `AppendObject`.
+///
+/// `AppendObject` 的返回结果。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AppendObjectOutput {
+    /// Position to pass to the next append (`x-oss-next-append-position`).
+    ///
+    /// 下一次追加应使用的位置（`x-oss-next-append-position`）。
+    pub next_position: Option<u64>,
+    /// Metadata; `crc64` covers the whole object after this append.
+    ///
+    /// 响应元数据；`crc64` 为本次追加后整个对象的校验值。
+    pub meta: ResponseMeta,
+}
+
+/// Result of `CopyObject`.
+///
+/// `CopyObject` 的返回结果。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CopyObjectOutput {
+    /// Last modification time of the new object, as reported by OSS.
+    ///
+    /// OSS 返回的新对象最后修改时间。
+    pub last_modified: Option<String>,
+    /// Metadata; `etag` is taken from the response body when the header is absent.
+    ///
+    /// 响应元数据；响应头缺少 ETag 时，`etag` 取自响应体。
+    pub meta: ResponseMeta,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase", default)]
+struct ResultBody {
+    e_tag: Option<String>,
+    last_modified: Option<String>,
+    location: Option<String>,
+    bucket: Option<String>,
+    key: Option<String>,
+}
+
+impl ResultBody {
+    // Bodies that are not the documented XML (for example a callback reply) leave every field empty
+    fn parse(meta: &mut ResponseMeta, body: &[u8]) -> Self {
+        let result: ResultBody = serde_xml_rs::from_reader(body).unwrap_or_default();
+        if meta.etag.is_none() {
+            meta.etag = result.e_tag.clone();
+        }
+        result
+    }
+}
+
+impl CopyObjectOutput {
+    pub(crate) fn new(mut meta: ResponseMeta, body: &[u8]) -> Self {
+        let result = ResultBody::parse(&mut meta, body);
+        CopyObjectOutput { last_modified: result.last_modified, meta }
+    }
+}
+
+/// Result of `CompleteUpload`.
+///
+/// `CompleteUpload` 的返回结果。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompleteUploadOutput {
+    /// URL of the assembled object.
+    ///
+    /// 合并后对象的 URL。
+    pub location: Option<String>,
+    /// Bucket name.
+    ///
+    /// Bucket 名称。
+    pub bucket: Option<String>,
+    /// Object key.
+    ///
+    /// 对象 Key。
+    pub key: Option<String>,
+    /// Metadata; `etag` is taken from the response body when the header is absent.
+    ///
+    /// 响应元数据；响应头缺少 ETag 时，`etag` 取自响应体。
+    pub meta: ResponseMeta,
+}
+
+impl CompleteUploadOutput {
+    pub(crate) fn new(mut meta: ResponseMeta, body: &[u8]) -> Self {
+        let result = ResultBody::parse(&mut meta, body);
+        CompleteUploadOutput { location: result.location, bucket: result.bucket, key: result.key, meta }
+    }
+}
+
+/// Result of `InitUpload`.
+///
+/// `InitUpload` 的返回结果。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InitUploadOutput {
+    /// Upload ID identifying the multipart upload.
+    ///
+    /// 标识本次分片上传的上传 ID。
+    pub upload_id: String,
+    /// Metadata of the initiate request.
+    ///
+    /// 初始化请求的响应元数据。
+    pub meta: ResponseMeta,
+}
+
+/// Result of `UploadPart` and `CopyToPart`.
+///
+/// `UploadPart` 与 `CopyToPart` 的返回结果。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UploadPartOutput {
+    /// Part ETag, quotes included, as `CompleteUpload` expects it.
+    ///
+    /// 分片 ETag，包含引号，可直接用于 `CompleteUpload`。
+    pub etag: String,
+    /// Metadata; `crc64` covers this part only.
+    ///
+    /// 响应元数据；`crc64` 仅为本分片的校验值。
+    pub meta: ResponseMeta,
+}
+
+/// Result of `InitiateBucketWorm`.
+///
+/// `InitiateBucketWorm` 的返回结果。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InitiateBucketWormOutput {
+    /// ID of the new WORM configuration.
+    ///
+    /// 新建 WORM 配置的 ID。
+    pub worm_id: String,
+    /// Metadata of the initiate request.
+    ///
+    /// 初始化请求的响应元数据。
+    pub meta: ResponseMeta,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_response_meta_and_outputs() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert("x-oss-request-id", "rid".parse().unwrap());
+        headers.insert("x-oss-version-id", "v1".parse().unwrap());
+        headers.insert("x-oss-hash-crc64ecma", "5981764153023615706".parse().unwrap());
+        headers.insert("x-oss-delete-marker", "true".parse().unwrap());
+        let meta = ResponseMeta::from_headers(&headers);
+        assert_eq!(meta.request_id.as_deref(), Some("rid"));
+        assert_eq!(meta.version_id.as_deref(), Some("v1"));
+        assert_eq!(meta.crc64, Some(5981764153023615706));
+        assert!(meta.delete_marker && meta.etag.is_none());
+
+        let body = b"<CompleteMultipartUploadResult><Location>http://b.oss/k</Location><Bucket>b</Bucket>\
+                     <Key>k</Key><ETag>\"E-2\"</ETag></CompleteMultipartUploadResult>";
+        let output = CompleteUploadOutput::new(meta.clone(), body);
+        assert_eq!((output.bucket.as_deref(), output.key.as_deref()), (Some("b"), Some("k")));
+        assert_eq!(output.meta.etag.as_deref(), Some("\"E-2\""));
+        let output = CopyObjectOutput::new(meta, b"{\"callback\":\"ok\"}");
+        assert!(output.last_modified.is_none() && output.meta.etag.is_none());
+    }
+
     #[test]
     fn test_url_encode_and_invalid_metadata_key() {
         assert_eq!(url_encode("a b"), "a%20b");
@@ -306,4 +773,81 @@ mod tests {
         let expected = format!("attachment;filename=\"{0}\";filename*=UTF-8''{0}", url_encode(name));
         assert_eq!(ContentDisposition::AttachmentWithNewName(name.into()).to_string(), expected);
     }
+
+    #[test]
+    fn test_sha256_hex_and_gmt_round_trip() {
+        assert_eq!(sha256_hex(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        let date = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        assert_eq!(parse_gmt(&format_gmt(date)), Some(date));
+    }
+
+    #[test]
+    fn test_endpoint_kind_hosts() {
+        assert_eq!(EndpointKind::Public.endpoint("cn-hangzhou"), "oss-cn-hangzhou.aliyuncs.com");
+        assert_eq!(EndpointKind::Internal.endpoint("cn-hangzhou"), "oss-cn-hangzhou-internal.aliyuncs.com");
+        assert_eq!(EndpointKind::Accelerate.endpoint("cn-hangzhou"), "oss-accelerate.aliyuncs.com");
+        assert_eq!(EndpointKind::AccelerateOverseas.endpoint("us-west-1"), "oss-accelerate-overseas.aliyuncs.com");
+        assert_eq!(EndpointKind::Dualstack.endpoint("cn-hangzhou"), "cn-hangzhou.oss.aliyuncs.com");
+    }
+
+    #[test]
+    fn test_enums_deserialize_from_xml_text() {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct Item {
+            storage_class: StorageClass,
+            data_redundancy_type: DataRedundancyType,
+            grant: Acl,
+        }
+        let xml = "<Item><StorageClass>IA</StorageClass><DataRedundancyType>ZRS</DataRedundancyType>\
+                   <Grant>public-read</Grant></Item>";
+        let item: Item = serde_xml_rs::from_reader(xml.as_bytes()).unwrap();
+        assert_eq!(item.storage_class, StorageClass::IA);
+        assert_eq!(item.data_redundancy_type, DataRedundancyType::ZRS);
+        assert!(matches!(item.grant, Acl::PublicRead));
+        assert!(serde_xml_rs::from_reader::<Item, _>(xml.replace("IA", "Hot").as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_enums_parse_their_display_values() {
+        for acl in [Acl::Default, Acl::Private, Acl::PublicRead, Acl::PublicReadWrite] {
+            assert_eq!(Acl::try_from(acl.to_string()).unwrap().to_string(), acl.to_string());
+        }
+        for class in [
+            StorageClass::Standard,
+            StorageClass::IA,
+            StorageClass::Archive,
+            StorageClass::ColdArchive,
+            StorageClass::DeepColdArchive,
+        ] {
+            assert_eq!(StorageClass::try_from(class.to_string()), Ok(class));
+        }
+        for redundancy in [DataRedundancyType::LRS, DataRedundancyType::ZRS] {
+            assert_eq!(DataRedundancyType::try_from(redundancy.to_string()), Ok(redundancy));
+        }
+        assert!(Acl::try_from("Private".to_string()).is_err());
+    }
+
+    #[cfg(feature = "_sync-base")]
+    #[test]
+    fn test_digest_file_sync_hashes_range_in_one_pass() {
+        use std::io::{Seek, SeekFrom};
+        let path = std::env::temp_dir().join(format!("oss-digest-{}", std::process::id()));
+        std::fs::write(&path, b"xx0123456789yy").unwrap();
+        let mut file = std::fs::File::open(&path).unwrap();
+        file.seek(SeekFrom::Start(2)).unwrap();
+        let digests = digest_file_sync(&mut file, 10, true, true).unwrap();
+        // the file is back at the start of the range
+        let mut content = Vec::new();
+        file.read_to_end(&mut content).unwrap();
+        assert_eq!(content, b"0123456789yy");
+        assert_eq!(digests.sha256, Some(sha256_hex(b"0123456789")));
+        assert_eq!(digests.md5.as_deref(), Some("eB5eJF1ptWaXm4bijSPyxw=="));
+
+        let digests = digest_file_sync(&mut file, 10, false, true).unwrap();
+        assert!(digests.sha256.is_none() && digests.md5.is_some());
+        let digests = digest_file_sync(&mut file, 10, false, false).unwrap();
+        assert!(digests.sha256.is_none() && digests.md5.is_none());
+        std::fs::remove_file(&path).ok();
+    }
 }
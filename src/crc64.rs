@@ -0,0 +1,276 @@
+//! CRC-64/ECMA-182 as reported by OSS in `x-oss-hash-crc64ecma`, used to verify transfers.
+//!
+//! OSS 在 `x-oss-hash-crc64ecma` 中返回的 CRC-64/ECMA-182 校验值，用于校验传输内容。
+use crate::Error;
+use http::HeaderMap;
+#[cfg(feature = "_sync-base")]
+use std::io::Read;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// Reflected ECMA-182 polynomial, with the all-ones initial value and final XOR (CRC-64/XZ)
+const POLY: u64 = 0xC96C_5795_D787_0F42;
+
+const TABLE: [u64; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ POLY } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Extend `crc`, the checksum of the data so far (0 for none), with `data`.
+///
+/// 以 `data` 扩展已有数据的校验值 `crc`（无数据时为 0）。
+pub(crate) fn update(crc: u64, data: &[u8]) -> u64 {
+    let mut crc = !crc;
+    for &byte in data {
+        crc = TABLE[((crc ^ byte as u64) & 0xff) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+/// Running checksum shared with a body stream that is consumed elsewhere.
+///
+/// 与在别处消费的请求体流共享的累计校验值。
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SharedCrc(Arc<AtomicU64>);
+
+impl SharedCrc {
+    /// Start from the checksum of data that precedes the stream, e.g. an appendable object.
+    ///
+    /// 以流之前数据的校验值为起点，例如追加对象已有的内容。
+    pub fn new(initial: u64) -> Self {
+        SharedCrc(Arc::new(AtomicU64::new(initial)))
+    }
+
+    pub fn update(&self, data: &[u8]) {
+        // chunks arrive in order from a single stream, so a plain load/store is enough
+        self.0.store(update(self.value(), data), Ordering::Relaxed);
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Reader feeding everything read through it into a [`SharedCrc`].
+///
+/// 将读取的所有数据计入 [`SharedCrc`] 的读取器。
+#[cfg(feature = "_sync-base")]
+pub(crate) struct CrcReader<R> {
+    inner: R,
+    crc: SharedCrc,
+}
+
+#[cfg(feature = "_sync-base")]
+impl<R: Read> CrcReader<R> {
+    pub fn new(inner: R, crc: SharedCrc) -> Self {
+        CrcReader { inner, crc }
+    }
+}
+
+#[cfg(feature = "_sync-base")]
+impl<R: Read> Read for CrcReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.crc.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+/// Checksum reported by OSS in the response headers.
+///
+/// OSS 在响应头中返回的校验值。
+pub(crate) fn from_headers(headers: &HeaderMap) -> Option<u64> {
+    headers.get("x-oss-hash-crc64ecma")?.to_str().ok()?.parse().ok()
+}
+
+/// Compare the local checksum with the one OSS reported; responses without one pass.
+///
+/// 比较本地校验值与 OSS 返回的校验值；未返回校验值的响应视为通过。
+pub(crate) fn verify(headers: &HeaderMap, actual: u64) -> Result<(), Error> {
+    check(from_headers(headers), actual)
+}
+
+/// Compare against a checksum taken from the headers earlier, for bodies consumed after the response.
+///
+/// 与先前从响应头取得的校验值比较，用于在响应之后才消费的响应体。
+pub(crate) fn check(expected: Option<u64>, actual: u64) -> Result<(), Error> {
+    match expected {
+        Some(expected) if expected != actual => Err(Error::CrcMismatch { expected, actual }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "_async-base")]
+    use futures_util::StreamExt;
+
+    #[test]
+    fn test_crc64_vectors_and_chaining() {
+        assert_eq!(update(0, b""), 0);
+        assert_eq!(update(0, b"123456789"), 0x995D_C9BB_DF19_39FA);
+        assert_eq!(update(update(0, b"12345"), b"6789"), update(0, b"123456789"));
+        let shared = SharedCrc::new(update(0, b"1234"));
+        shared.clone().update(b"56789");
+        assert_eq!(shared.value(), 0x995D_C9BB_DF19_39FA);
+
+        let mut headers = HeaderMap::new();
+        assert!(verify(&headers, 1).is_ok());
+        headers.insert("x-oss-hash-crc64ecma", update(0, b"abc").to_string().parse().unwrap());
+        assert!(verify(&headers, update(0, b"abc")).is_ok());
+        assert!(matches!(verify(&headers, 7), Err(Error::CrcMismatch { actual: 7, .. })));
+    }
+
+    #[cfg(feature = "_async-base")]
+    #[test]
+    fn test_crc_mismatch_detected() {
+        use crate::OssClient;
+        use crate::common::AddressingStyle;
+        use crate::test_util::{MockResponse, MockServer};
+
+        let wrong = update(0, b"other").to_string();
+        let server = MockServer::start(vec![
+            MockResponse::new(200, "").header("x-oss-hash-crc64ecma", wrong.clone()),
+            MockResponse::new(200, "").header("x-oss-hash-crc64ecma", wrong.clone()),
+            MockResponse::new(200, "hello").header("x-oss-hash-crc64ecma", wrong.clone()),
+            MockResponse::new(206, "ell").header("x-oss-hash-crc64ecma", wrong.clone()),
+            MockResponse::new(200, "hello").header("x-oss-hash-crc64ecma", wrong),
+        ]);
+        let mut client = OssClient::new("id", "secret", "cn-hangzhou")
+            .disable_https()
+            .with_addressing_style(AddressingStyle::Path)
+            .with_retry_policy(crate::RetryPolicy::none());
+        client.set_endpoint(server.host());
+        let object = client.bucket("bucket").object("a.txt");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let expected = update(0, b"hello");
+            match object.put_object().send_content(b"hello".to_vec()).await {
+                Err(Error::CrcMismatch { actual, .. }) => assert_eq!(actual, expected),
+                other => panic!("expected a CRC mismatch, got {:?}", other.map(|_| ())),
+            }
+            object.put_object().set_crc_check(false).send_content(b"hello".to_vec()).await.unwrap();
+            assert!(matches!(object.get_object().download().await, Err(Error::CrcMismatch { .. })));
+            // partial content carries the whole object's checksum and is not verified
+            assert_eq!(object.get_object().set_range(1, Some(3)).download().await.unwrap().as_ref(), b"ell");
+            let items: Vec<_> = object.get_object().download_to_stream().await.unwrap().collect().await;
+            assert!(matches!(items.last(), Some(Err(Error::CrcMismatch { .. }))));
+        });
+    }
+
+    #[cfg(feature = "_async-base")]
+    #[test]
+    fn test_failed_stream_skips_crc_check() {
+        use crate::OssClient;
+        use crate::common::AddressingStyle;
+        use crate::test_util::{MockResponse, MockServer};
+
+        let server = MockServer::start(vec![
+            MockResponse::new(200, "hello world")
+                .header("x-oss-hash-crc64ecma", update(0, b"hello world").to_string())
+                .stall(std::time::Duration::from_millis(500)),
+        ]);
+        let mut client = OssClient::new("id", "secret", "cn-hangzhou")
+            .disable_https()
+            .with_addressing_style(AddressingStyle::Path)
+            .with_read_timeout(std::time::Duration::from_millis(100))
+            .with_retry_policy(crate::RetryPolicy::none());
+        client.set_endpoint(server.host());
+        let object = client.bucket("bucket").object("a.txt");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let items: Vec<_> =
+            runtime.block_on(async { object.get_object().download_to_stream().await.unwrap().collect().await });
+        // the read error ends the stream without a checksum verdict on the partial body
+        assert!(matches!(items.last(), Some(Err(Error::Timeout))));
+        assert!(!items.iter().any(|item| matches!(item, Err(Error::CrcMismatch { .. }))));
+    }
+
+    #[cfg(feature = "_async-base")]
+    #[test]
+    fn test_failed_download_removes_file() {
+        use crate::OssClient;
+        use crate::common::AddressingStyle;
+        use crate::test_util::{MockResponse, MockServer};
+
+        let checksum = update(0, b"hello world").to_string();
+        let server = MockServer::start(vec![
+            MockResponse::new(200, "hello world").header("x-oss-hash-crc64ecma", update(0, b"other").to_string()),
+            MockResponse::new(200, "hello world")
+                .header("x-oss-hash-crc64ecma", checksum.clone())
+                .stall(std::time::Duration::from_millis(500)),
+            MockResponse::new(200, "hello world").header("x-oss-hash-crc64ecma", checksum),
+        ]);
+        let mut client = OssClient::new("id", "secret", "cn-hangzhou")
+            .disable_https()
+            .with_addressing_style(AddressingStyle::Path)
+            .with_read_timeout(std::time::Duration::from_millis(100))
+            .with_retry_policy(crate::RetryPolicy::none());
+        client.set_endpoint(server.host());
+        let object = client.bucket("bucket").object("a.txt");
+        let path = std::env::temp_dir().join(format!("oss-download-{}", std::process::id()));
+        let save_path = path.to_str().unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let result = object.get_object().download_to_file(save_path).await;
+            assert!(matches!(result, Err(Error::CrcMismatch { .. })));
+            assert!(!path.exists());
+            let result = object.get_object().download_to_file(save_path).await;
+            assert!(matches!(result, Err(Error::Timeout)));
+            assert!(!path.exists());
+            // the server answers the retry once the stalled response is finished
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            object.get_object().download_to_file(save_path).await.unwrap();
+        });
+        assert_eq!(std::fs::read(&path).unwrap(), b"hello world");
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[cfg(feature = "_sync-base")]
+    #[test]
+    fn test_failed_download_removes_file_sync() {
+        use crate::OssClient;
+        use crate::common::AddressingStyle;
+        use crate::test_util::{MockResponse, MockServer};
+
+        let checksum = update(0, b"hello world").to_string();
+        let server = MockServer::start(vec![
+            MockResponse::new(200, "hello world").header("x-oss-hash-crc64ecma", update(0, b"other").to_string()),
+            MockResponse::new(200, "hello world")
+                .header("x-oss-hash-crc64ecma", checksum.clone())
+                .stall(std::time::Duration::from_millis(500)),
+            MockResponse::new(200, "hello world").header("x-oss-hash-crc64ecma", checksum),
+        ]);
+        let mut client = OssClient::new("id", "secret", "cn-hangzhou")
+            .disable_https()
+            .with_addressing_style(AddressingStyle::Path)
+            .with_read_timeout(std::time::Duration::from_millis(100))
+            .with_retry_policy(crate::RetryPolicy::none());
+        client.set_endpoint(server.host());
+        let object = client.bucket("bucket").object("a.txt");
+        let path = std::env::temp_dir().join(format!("oss-download-sync-{}", std::process::id()));
+        let save_path = path.to_str().unwrap();
+        let result = object.get_object_sync().download_to_file(save_path);
+        assert!(matches!(result, Err(Error::CrcMismatch { .. })));
+        assert!(!path.exists());
+        let result = object.get_object_sync().download_to_file(save_path);
+        assert!(matches!(result, Err(Error::Timeout)));
+        assert!(!path.exists());
+        // the server answers the retry once the stalled response is finished
+        std::thread::sleep(std::time::Duration::from_millis(500));
+        object.get_object_sync().download_to_file(save_path).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"hello world");
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,797 @@
+//! Credentials and the providers that resolve them at signing time.
+//!
+//! 访问凭证，以及在签名时解析凭证的提供者。
+use crate::Error;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use crate::common::url_encode;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use crate::{
+    Proxy,
+    transport::{Transport, TransportConfig},
+};
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use bytes::Bytes;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use http::{Method, StatusCode};
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use serde_derive::Deserialize;
+use std::fmt;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use std::sync::OnceLock;
+use std::sync::{Arc, Mutex, TryLockError};
+use std::time::Duration;
+use time::OffsetDateTime;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use time::format_description::well_known::Rfc3339;
+
+/// Cached credentials are refreshed once they are this close to expiring.
+const REFRESH_MARGIN: Duration = Duration::from_secs(5 * 60);
+
+/// Upper bound for a single call to a metadata or STS endpoint.
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// AccessKey pair, optional STS security token and expiration time.
+///
+/// AccessKey 对、可选的 STS 安全令牌及过期时间。
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    access_key_id: String,
+    access_key_secret: String,
+    security_token: Option<String>,
+    expiration: Option<OffsetDateTime>,
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("access_key_secret", &"***")
+            .field("security_token", &self.security_token.as_ref().map(|_| "***"))
+            .field("expiration", &self.expiration)
+            .finish()
+    }
+}
+
+impl Credentials {
+    /// Create long-lived credentials from an AccessKey pair.
+    ///
+    /// 使用 AccessKey 对创建长期凭证。
+    pub fn new(access_key_id: impl Into<String>, access_key_secret: impl Into<String>) -> Self {
+        Credentials {
+            access_key_id: access_key_id.into(),
+            access_key_secret: access_key_secret.into(),
+            security_token: None,
+            expiration: None,
+        }
+    }
+    /// Attach an STS security token.
+    ///
+    /// 附加 STS 安全令牌。
+    pub fn with_security_token(mut self, security_token: impl Into<String>) -> Self {
+        self.security_token = Some(security_token.into());
+        self
+    }
+    /// Set the time after which the credentials are no longer valid.
+    ///
+    /// 设置凭证失效的时间。
+    pub fn with_expiration(mut self, expiration: OffsetDateTime) -> Self {
+        self.expiration = Some(expiration);
+        self
+    }
+    /// AccessKey ID.
+    ///
+    /// AccessKey ID。
+    pub fn access_key_id(&self) -> &str {
+        &self.access_key_id
+    }
+    /// AccessKey Secret.
+    ///
+    /// AccessKey Secret。
+    pub fn access_key_secret(&self) -> &str {
+        &self.access_key_secret
+    }
+    /// STS security token, if any.
+    ///
+    /// STS 安全令牌（如有）。
+    pub fn security_token(&self) -> Option<&str> {
+        self.security_token.as_deref()
+    }
+    /// Expiration time, if the credentials are temporary.
+    ///
+    /// 过期时间（仅临时凭证）。
+    pub fn expiration(&self) -> Option<OffsetDateTime> {
+        self.expiration
+    }
+
+    fn expires_within(&self, margin: Duration) -> bool {
+        self.expiration.is_some_and(|expiration| expiration - margin <= OffsetDateTime::now_utc())
+    }
+}
+
+/// Source of credentials, consulted whenever a request is signed.
+///
+/// `fetch` may block (for example on an HTTP call); results are cached by the client and fetched
+/// again shortly before `Credentials::expiration`. Async requests call it on Tokio's blocking pool.
+///
+/// 凭证来源，每次签名请求时都会查询。
+///
+/// `fetch` 可以阻塞（例如发起 HTTP 调用）；客户端会缓存结果，并在 `Credentials::expiration`
+/// 临近时重新获取。异步请求会在 Tokio 的阻塞线程池中调用它。
+pub trait CredentialsProvider: Send + Sync {
+    /// Fetch fresh credentials.
+    ///
+    /// 获取新的凭证。
+    fn fetch(&self) -> Result<Credentials, Error>;
+}
+
+/// Provider returning fixed credentials.
+///
+/// 返回固定凭证的提供者。
+#[derive(Debug, Clone)]
+pub struct StaticCredentialsProvider {
+    credentials: Credentials,
+}
+
+impl StaticCredentialsProvider {
+    /// Always return `credentials`.
+    ///
+    /// 始终返回 `credentials`。
+    pub fn new(credentials: Credentials) -> Self {
+        StaticCredentialsProvider { credentials }
+    }
+}
+
+impl CredentialsProvider for StaticCredentialsProvider {
+    fn fetch(&self) -> Result<Credentials, Error> {
+        Ok(self.credentials.clone())
+    }
+}
+
+/// Provider reading `ALIBABA_CLOUD_ACCESS_KEY_ID`, `ALIBABA_CLOUD_ACCESS_KEY_SECRET` and the optional
+/// `ALIBABA_CLOUD_SECURITY_TOKEN` environment variables on every fetch.
+///
+/// 每次获取时读取环境变量 `ALIBABA_CLOUD_ACCESS_KEY_ID`、`ALIBABA_CLOUD_ACCESS_KEY_SECRET`
+/// 以及可选的 `ALIBABA_CLOUD_SECURITY_TOKEN` 的提供者。
+#[derive(Debug, Clone, Default)]
+pub struct EnvironmentCredentialsProvider;
+
+impl EnvironmentCredentialsProvider {
+    /// Read the credentials from the environment on every fetch.
+    ///
+    /// 每次获取时从环境变量读取凭证。
+    pub fn new() -> Self {
+        EnvironmentCredentialsProvider
+    }
+
+    fn fetch_with(lookup: impl Fn(&str) -> Option<String>) -> Result<Credentials, Error> {
+        let read = |key: &str| lookup(key).filter(|value| !value.trim().is_empty());
+        let (Some(id), Some(secret)) = (read("ALIBABA_CLOUD_ACCESS_KEY_ID"), read("ALIBABA_CLOUD_ACCESS_KEY_SECRET"))
+        else {
+            return Err(Error::CredentialsError(
+                "ALIBABA_CLOUD_ACCESS_KEY_ID or ALIBABA_CLOUD_ACCESS_KEY_SECRET is not set".to_string(),
+            ));
+        };
+        let credentials = Credentials::new(id, secret);
+        Ok(match read("ALIBABA_CLOUD_SECURITY_TOKEN") {
+            Some(token) => credentials.with_security_token(token),
+            None => credentials,
+        })
+    }
+}
+
+impl CredentialsProvider for EnvironmentCredentialsProvider {
+    fn fetch(&self) -> Result<Credentials, Error> {
+        EnvironmentCredentialsProvider::fetch_with(|key| std::env::var(key).ok())
+    }
+}
+
+/// Provider for the RAM role attached to an ECS instance, read from the instance metadata service.
+///
+/// When no role name is set, `ALIBABA_CLOUD_ECS_METADATA` is used, and otherwise the role is
+/// discovered from the metadata service.
+///
+/// ECS 实例所绑定 RAM 角色的凭证提供者，从实例元数据服务读取。
+///
+/// 未设置角色名时使用 `ALIBABA_CLOUD_ECS_METADATA`，否则从元数据服务自动发现。
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+#[derive(Debug, Clone)]
+pub struct EcsRamRoleCredentialsProvider {
+    role_name: Option<String>,
+    endpoint: String,
+    imds_v2: bool,
+}
+
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+impl Default for EcsRamRoleCredentialsProvider {
+    fn default() -> Self {
+        EcsRamRoleCredentialsProvider::new()
+    }
+}
+
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+impl EcsRamRoleCredentialsProvider {
+    /// Use the role named by `ALIBABA_CLOUD_ECS_METADATA`, or discover it, from `http://100.100.100.200`.
+    ///
+    /// The metadata service is always reached directly, bypassing any proxy.
+    ///
+    /// 从 `http://100.100.100.200` 读取 `ALIBABA_CLOUD_ECS_METADATA` 指定的角色，未指定时自动发现。
+    ///
+    /// 始终直接访问元数据服务，不经过任何代理。
+    pub fn new() -> Self {
+        EcsRamRoleCredentialsProvider {
+            role_name: std::env::var("ALIBABA_CLOUD_ECS_METADATA").ok().filter(|v| !v.trim().is_empty()),
+            endpoint: "http://100.100.100.200".to_string(),
+            imds_v2: false,
+        }
+    }
+    /// Set the RAM role name instead of discovering it.
+    ///
+    /// 指定 RAM 角色名，不再自动发现。
+    pub fn set_role_name(mut self, role_name: impl Into<String>) -> Self {
+        self.role_name = Some(role_name.into());
+        self
+    }
+    /// Override the metadata service base URL, `http://100.100.100.200` by default.
+    ///
+    /// 覆盖元数据服务地址，默认为 `http://100.100.100.200`。
+    pub fn set_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = endpoint.into().trim_end_matches('/').to_string();
+        self
+    }
+    /// Use the hardened mode (IMDSv2), which requests a session token before reading metadata.
+    ///
+    /// 使用加固模式（IMDSv2），读取元数据前先获取会话令牌。
+    pub fn set_imds_v2(mut self, enable: bool) -> Self {
+        self.imds_v2 = enable;
+        self
+    }
+
+    fn get(&self, path: &str, token: Option<&str>) -> Result<String, Error> {
+        let url = format!("{}/latest/{}", self.endpoint, path);
+        let headers: Vec<(&str, &str)> =
+            token.map(|token| vec![("X-aliyun-ecs-metadata-token", token)]).unwrap_or_default();
+        let (status, body) = http_call(Method::GET, &url, &headers, None, Route::Direct)?;
+        let body = String::from_utf8_lossy(&body).trim().to_string();
+        if !status.is_success() {
+            return Err(Error::CredentialsError(format!("ECS metadata returned {}: {}", status, body)));
+        }
+        Ok(body)
+    }
+}
+
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+impl CredentialsProvider for EcsRamRoleCredentialsProvider {
+    fn fetch(&self) -> Result<Credentials, Error> {
+        let token = if self.imds_v2 {
+            let url = format!("{}/latest/api/token", self.endpoint);
+            let headers = [("X-aliyun-ecs-metadata-token-ttl-seconds", "21600")];
+            let (status, body) = http_call(Method::PUT, &url, &headers, None, Route::Direct)?;
+            if !status.is_success() {
+                return Err(Error::CredentialsError(format!("ECS metadata token request returned {}", status)));
+            }
+            Some(String::from_utf8_lossy(&body).trim().to_string())
+        } else {
+            None
+        };
+        let role_name = match &self.role_name {
+            Some(role_name) => role_name.clone(),
+            None => self.get("meta-data/ram/security-credentials/", token.as_deref())?,
+        };
+        let body = self.get(&format!("meta-data/ram/security-credentials/{}", role_name), token.as_deref())?;
+        let response: EcsCredentialsResponse = serde_json::from_str(&body)
+            .map_err(|e| Error::CredentialsError(format!("invalid ECS metadata response: {}", e)))?;
+        if response.code != "Success" {
+            return Err(Error::CredentialsError(format!("ECS metadata returned code {}", response.code)));
+        }
+        response.credentials.into_credentials()
+    }
+}
+
+/// Provider exchanging an OIDC token for STS credentials with `AssumeRoleWithOIDC`, as used by RRSA
+/// on Container Service for Kubernetes.
+///
+/// The token file is read again on every fetch, so rotated service account tokens are picked up.
+///
+/// 通过 `AssumeRoleWithOIDC` 使用 OIDC 令牌换取 STS 凭证的提供者，用于容器服务 Kubernetes 版的 RRSA。
+///
+/// 每次获取都会重新读取令牌文件，以便使用轮换后的服务账号令牌。
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+#[derive(Debug, Clone)]
+pub struct OidcRoleCredentialsProvider {
+    role_arn: String,
+    oidc_provider_arn: String,
+    oidc_token_file: String,
+    role_session_name: String,
+    duration: Duration,
+    policy: Option<String>,
+    sts_endpoint: String,
+}
+
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+impl OidcRoleCredentialsProvider {
+    /// Assume `role_arn` with the token in `oidc_token_file`, issued by the OIDC provider `oidc_provider_arn`.
+    ///
+    /// Defaults to a one-hour session named after the current time and the public STS endpoint.
+    ///
+    /// 使用 OIDC 提供者 `oidc_provider_arn` 签发、保存在 `oidc_token_file` 中的令牌扮演 `role_arn`。
+    ///
+    /// 默认会话有效期为一小时，会话名称取自当前时间，并使用公网 STS Endpoint。
+    pub fn new(
+        role_arn: impl Into<String>,
+        oidc_provider_arn: impl Into<String>,
+        oidc_token_file: impl Into<String>,
+    ) -> Self {
+        OidcRoleCredentialsProvider {
+            role_arn: role_arn.into(),
+            oidc_provider_arn: oidc_provider_arn.into(),
+            oidc_token_file: oidc_token_file.into(),
+            role_session_name: format!("aliyun-oss-rs-{}", OffsetDateTime::now_utc().unix_timestamp()),
+            duration: Duration::from_secs(3600),
+            policy: None,
+            sts_endpoint: "https://sts.aliyuncs.com".to_string(),
+        }
+    }
+    /// Build the provider from the variables injected by RRSA: `ALIBABA_CLOUD_ROLE_ARN`,
+    /// `ALIBABA_CLOUD_OIDC_PROVIDER_ARN`, `ALIBABA_CLOUD_OIDC_TOKEN_FILE`, and optionally
+    /// `ALIBABA_CLOUD_ROLE_SESSION_NAME` and `ALIBABA_CLOUD_STS_ENDPOINT`.
+    ///
+    /// 使用 RRSA 注入的环境变量构建提供者：`ALIBABA_CLOUD_ROLE_ARN`、`ALIBABA_CLOUD_OIDC_PROVIDER_ARN`、
+    /// `ALIBABA_CLOUD_OIDC_TOKEN_FILE`，以及可选的 `ALIBABA_CLOUD_ROLE_SESSION_NAME` 与 `ALIBABA_CLOUD_STS_ENDPOINT`。
+    pub fn from_env() -> Result<Self, Error> {
+        let read = |key: &str| std::env::var(key).ok().filter(|value| !value.trim().is_empty());
+        let required = |key: &str| read(key).ok_or_else(|| Error::CredentialsError(format!("{} is not set", key)));
+        let mut provider = OidcRoleCredentialsProvider::new(
+            required("ALIBABA_CLOUD_ROLE_ARN")?,
+            required("ALIBABA_CLOUD_OIDC_PROVIDER_ARN")?,
+            required("ALIBABA_CLOUD_OIDC_TOKEN_FILE")?,
+        );
+        if let Some(name) = read("ALIBABA_CLOUD_ROLE_SESSION_NAME") {
+            provider = provider.set_role_session_name(name);
+        }
+        if let Some(endpoint) = read("ALIBABA_CLOUD_STS_ENDPOINT") {
+            provider = provider.set_sts_endpoint(endpoint);
+        }
+        Ok(provider)
+    }
+    /// Set the role session name.
+    ///
+    /// 设置角色会话名称。
+    pub fn set_role_session_name(mut self, role_session_name: impl Into<String>) -> Self {
+        self.role_session_name = role_session_name.into();
+        self
+    }
+    /// Set how long the issued credentials stay valid, one hour by default.
+    ///
+    /// 设置签发凭证的有效期，默认一小时。
+    pub fn set_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+    /// Restrict the issued credentials with an inline policy (JSON).
+    ///
+    /// 使用内联策略（JSON）限制签发凭证的权限。
+    pub fn set_policy(mut self, policy: impl Into<String>) -> Self {
+        self.policy = Some(policy.into());
+        self
+    }
+    /// Override the STS endpoint, `https://sts.aliyuncs.com` by default.
+    ///
+    /// A bare host name is treated as HTTPS.
+    ///
+    /// 覆盖 STS Endpoint，默认为 `https://sts.aliyuncs.com`。
+    ///
+    /// 仅提供主机名时使用 HTTPS。
+    pub fn set_sts_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        let endpoint = endpoint.into();
+        let endpoint = if endpoint.contains("://") { endpoint } else { format!("https://{}", endpoint) };
+        self.sts_endpoint = endpoint.trim_end_matches('/').to_string();
+        self
+    }
+}
+
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+impl CredentialsProvider for OidcRoleCredentialsProvider {
+    fn fetch(&self) -> Result<Credentials, Error> {
+        let token = std::fs::read_to_string(&self.oidc_token_file)?;
+        let timestamp = OffsetDateTime::now_utc()
+            .format(
+                &time::format_description::parse("[year]-[month]-[day]T[hour]:[minute]:[second]Z")
+                    .expect("valid format"),
+            )
+            .expect("formatting");
+        let duration = self.duration.as_secs().to_string();
+        let mut params = vec![
+            ("Action", "AssumeRoleWithOIDC"),
+            ("Version", "2015-04-01"),
+            ("Format", "JSON"),
+            ("Timestamp", timestamp.as_str()),
+            ("RoleArn", self.role_arn.as_str()),
+            ("OIDCProviderArn", self.oidc_provider_arn.as_str()),
+            ("OIDCToken", token.trim()),
+            ("RoleSessionName", self.role_session_name.as_str()),
+            ("DurationSeconds", duration.as_str()),
+        ];
+        if let Some(policy) = &self.policy {
+            params.push(("Policy", policy));
+        }
+        let form = params
+            .iter()
+            .map(|(key, value)| format!("{}={}", url_encode(key), url_encode(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+        let url = format!("{}/", self.sts_endpoint);
+        let headers = [("Content-Type", "application/x-www-form-urlencoded")];
+        let (status, body) = http_call(Method::POST, &url, &headers, Some(form), Route::Proxied)?;
+        if !status.is_success() {
+            let detail = serde_json::from_slice::<StsErrorResponse>(&body)
+                .map(|e| format!("{}: {}", e.code, e.message))
+                .unwrap_or_else(|_| String::from_utf8_lossy(&body).to_string());
+            return Err(Error::CredentialsError(format!("AssumeRoleWithOIDC returned {}: {}", status, detail)));
+        }
+        let response: StsResponse = serde_json::from_slice(&body)
+            .map_err(|e| Error::CredentialsError(format!("invalid AssumeRoleWithOIDC response: {}", e)))?;
+        response.credentials.into_credentials()
+    }
+}
+
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TemporaryCredentials {
+    access_key_id: String,
+    access_key_secret: String,
+    security_token: String,
+    expiration: String,
+}
+
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+impl TemporaryCredentials {
+    fn into_credentials(self) -> Result<Credentials, Error> {
+        let expiration = OffsetDateTime::parse(&self.expiration, &Rfc3339)
+            .map_err(|e| Error::CredentialsError(format!("invalid expiration {}: {}", self.expiration, e)))?;
+        Ok(Credentials::new(self.access_key_id, self.access_key_secret)
+            .with_security_token(self.security_token)
+            .with_expiration(expiration))
+    }
+}
+
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+#[derive(Deserialize)]
+struct EcsCredentialsResponse {
+    #[serde(rename = "Code")]
+    code: String,
+    #[serde(flatten)]
+    credentials: TemporaryCredentials,
+}
+
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+#[derive(Deserialize)]
+struct StsResponse {
+    #[serde(rename = "Credentials")]
+    credentials: TemporaryCredentials,
+}
+
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct StsErrorResponse {
+    code: String,
+    message: String,
+}
+
+/// How a provider request reaches its endpoint.
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Route {
+    /// Ignore proxies, for the link-local ECS metadata service that a proxy cannot reach.
+    Direct,
+    /// Use the proxy from the environment, like an `OssClient` without an explicit proxy.
+    Proxied,
+}
+
+/// Transport shared by every provider request on the same route, so connections are pooled across fetches.
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+fn provider_transport(route: Route) -> &'static Transport {
+    static DIRECT: OnceLock<Transport> = OnceLock::new();
+    static PROXIED: OnceLock<Transport> = OnceLock::new();
+    match route {
+        Route::Direct => DIRECT
+            .get_or_init(|| Transport::new(TransportConfig { proxy: Some(Proxy::disabled()), ..Default::default() })),
+        Route::Proxied => PROXIED.get_or_init(|| Transport::new(TransportConfig::default())),
+    }
+}
+
+/// Plain HTTP call used by the built-in providers; returns the status and the full body.
+#[cfg(feature = "_sync-base")]
+fn http_call(
+    method: Method,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<String>,
+    route: Route,
+) -> Result<(StatusCode, Bytes), Error> {
+    let mut builder = http::Request::builder().method(method).uri(url);
+    for (key, value) in headers {
+        builder = builder.header(*key, *value);
+    }
+    let request = builder.body(body.unwrap_or_default())?;
+    let agent = provider_transport(route).sync_agent();
+    let request = agent.configure_request(request).timeout_global(Some(FETCH_TIMEOUT)).build();
+    let response = agent.run(request).map_err(|e| match e {
+        ureq::Error::Timeout(_) => Error::Timeout,
+        e => Error::RequestError(e),
+    })?;
+    let status = response.status();
+    let body = response.into_body().read_to_vec()?;
+    Ok((status, Bytes::from(body)))
+}
+
+/// Plain HTTP call used by the built-in providers; returns the status and the full body.
+///
+/// Runs on a dedicated runtime shared by all fetches and blocks until it finishes, so it can be called
+/// from any context, including from inside an async task.
+#[cfg(all(feature = "_async-base", not(feature = "_sync-base")))]
+fn http_call(
+    method: Method,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<String>,
+    route: Route,
+) -> Result<(StatusCode, Bytes), Error> {
+    use http_body_util::{BodyExt, Full};
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+
+    let mut builder = http::Request::builder().method(method).uri(url);
+    for (key, value) in headers {
+        builder = builder.header(*key, *value);
+    }
+    let request =
+        builder.body(Full::new(Bytes::from(body.unwrap_or_default())).map_err(|never| match never {}).boxed())?;
+    let runtime = match RUNTIME.get() {
+        Some(runtime) => runtime,
+        None => {
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(1)
+                .thread_name("oss-credentials")
+                .enable_all()
+                .build()?;
+            RUNTIME.get_or_init(|| runtime)
+        }
+    };
+    let (sender, receiver) = std::sync::mpsc::channel();
+    runtime.spawn(async move {
+        let call = async {
+            let response = provider_transport(route).async_client().request(request).await?;
+            let status = response.status();
+            let body = response.into_body().collect().await?.to_bytes();
+            Ok((status, body))
+        };
+        let _ = sender.send(tokio::time::timeout(FETCH_TIMEOUT, call).await.unwrap_or(Err(Error::Timeout)));
+    });
+    receiver.recv().unwrap_or_else(|_| Err(Error::CredentialsError("credentials request was cancelled".to_string())))
+}
+
+/// Provider wrapper caching credentials until they are about to expire.
+///
+/// 缓存凭证直至其即将过期的提供者包装。
+pub(crate) struct CredentialsCache {
+    provider: Arc<dyn CredentialsProvider>,
+    cached: Mutex<Option<Credentials>>,
+    // Held while fetching, so concurrent callers share one refresh instead of each calling the provider
+    refresh: Mutex<()>,
+}
+
+impl fmt::Debug for CredentialsCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CredentialsCache").field("cached", &self.cached).finish_non_exhaustive()
+    }
+}
+
+impl CredentialsCache {
+    pub fn new(provider: Arc<dyn CredentialsProvider>) -> Self {
+        CredentialsCache { provider, cached: Mutex::new(None), refresh: Mutex::new(()) }
+    }
+
+    /// Cached credentials that are not due for a refresh.
+    ///
+    /// 尚未到刷新时间的缓存凭证。
+    pub fn fresh(&self) -> Option<Credentials> {
+        self.cached_for(REFRESH_MARGIN)
+    }
+
+    // Cached credentials that stay valid for at least `margin`
+    fn cached_for(&self, margin: Duration) -> Option<Credentials> {
+        let cached = self.cached.lock().unwrap_or_else(|e| e.into_inner());
+        cached.as_ref().filter(|credentials| !credentials.expires_within(margin)).cloned()
+    }
+
+    /// Return cached credentials, fetching new ones when they are missing or about to expire.
+    ///
+    /// Only one caller fetches at a time. While it does, others keep using cached credentials that have not
+    /// expired yet, and if the refresh fails those keep being used too.
+    ///
+    /// 返回缓存凭证；缺失或即将过期时重新获取。
+    ///
+    /// 同一时间只有一个调用方获取凭证；获取期间其他调用方继续使用尚未过期的缓存凭证，刷新失败时也继续使用。
+    pub fn get(&self) -> Result<Credentials, Error> {
+        if let Some(credentials) = self.fresh() {
+            return Ok(credentials);
+        }
+        let _refresh = match self.refresh.try_lock() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            Err(TryLockError::WouldBlock) => {
+                if let Some(credentials) = self.cached_for(Duration::ZERO) {
+                    return Ok(credentials);
+                }
+                self.refresh.lock().unwrap_or_else(|e| e.into_inner())
+            }
+        };
+        // another caller may have refreshed while this one waited
+        if let Some(credentials) = self.fresh() {
+            return Ok(credentials);
+        }
+        match self.provider.fetch() {
+            Ok(credentials) => {
+                *self.cached.lock().unwrap_or_else(|e| e.into_inner()) = Some(credentials.clone());
+                Ok(credentials)
+            }
+            Err(error) => self.cached_for(Duration::ZERO).ok_or(error),
+        }
+    }
+
+    /// Async variant of `get` that runs the provider on the blocking pool when a fetch is needed.
+    ///
+    /// `get` 的异步版本，需要获取时在阻塞线程池中调用提供者。
+    #[cfg(feature = "_async-base")]
+    pub async fn get_async(self: &Arc<Self>) -> Result<Credentials, Error> {
+        if let Some(credentials) = self.fresh() {
+            return Ok(credentials);
+        }
+        let cache = self.clone();
+        tokio::task::spawn_blocking(move || cache.get())
+            .await
+            .map_err(|e| Error::CredentialsError(format!("credentials provider panicked: {}", e)))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingProvider {
+        calls: AtomicUsize,
+        lifetime: time::Duration,
+    }
+
+    impl CredentialsProvider for CountingProvider {
+        fn fetch(&self) -> Result<Credentials, Error> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Credentials::new(format!("id-{}", call), "secret")
+                .with_expiration(OffsetDateTime::now_utc() + self.lifetime))
+        }
+    }
+
+    #[test]
+    fn test_cache_refreshes_before_expiry() {
+        let provider = Arc::new(CountingProvider { calls: AtomicUsize::new(0), lifetime: time::Duration::hours(1) });
+        let cache = CredentialsCache::new(provider.clone());
+        assert_eq!(cache.get().unwrap().access_key_id(), "id-0");
+        assert_eq!(cache.get().unwrap().access_key_id(), "id-0");
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+
+        let provider = Arc::new(CountingProvider { calls: AtomicUsize::new(0), lifetime: time::Duration::minutes(2) });
+        let cache = CredentialsCache::new(provider.clone());
+        assert_eq!(cache.get().unwrap().access_key_id(), "id-0");
+        assert!(cache.fresh().is_none());
+        assert_eq!(cache.get().unwrap().access_key_id(), "id-1");
+    }
+
+    #[test]
+    fn test_cache_serves_valid_credentials_during_refresh() {
+        use std::sync::atomic::AtomicBool;
+
+        // Blocks every fetch after the first until released
+        struct SlowProvider {
+            calls: AtomicUsize,
+            release: AtomicBool,
+        }
+        impl CredentialsProvider for SlowProvider {
+            fn fetch(&self) -> Result<Credentials, Error> {
+                let call = self.calls.fetch_add(1, Ordering::SeqCst);
+                while call > 0 && !self.release.load(Ordering::SeqCst) {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                Ok(Credentials::new(format!("id-{}", call), "secret")
+                    .with_expiration(OffsetDateTime::now_utc() + time::Duration::minutes(2)))
+            }
+        }
+
+        let provider = Arc::new(SlowProvider { calls: AtomicUsize::new(0), release: AtomicBool::new(false) });
+        let cache = Arc::new(CredentialsCache::new(provider.clone()));
+        assert_eq!(cache.get().unwrap().access_key_id(), "id-0");
+        let refreshing = std::thread::spawn({
+            let cache = cache.clone();
+            move || cache.get().unwrap()
+        });
+        while provider.calls.load(Ordering::SeqCst) < 2 {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        // the cached credentials are due for a refresh but still valid
+        assert_eq!(cache.get().unwrap().access_key_id(), "id-0");
+        provider.release.store(true, Ordering::SeqCst);
+        assert_eq!(refreshing.join().unwrap().access_key_id(), "id-1");
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_environment_provider() {
+        let vars = [("ALIBABA_CLOUD_ACCESS_KEY_ID", "id"), ("ALIBABA_CLOUD_ACCESS_KEY_SECRET", "secret")];
+        let lookup = |key: &str| vars.iter().find(|(k, _)| *k == key).map(|(_, v)| v.to_string());
+        let credentials = EnvironmentCredentialsProvider::fetch_with(lookup).unwrap();
+        assert_eq!(credentials, Credentials::new("id", "secret"));
+        assert!(matches!(EnvironmentCredentialsProvider::fetch_with(|_| None), Err(Error::CredentialsError(_))));
+    }
+
+    #[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+    #[test]
+    fn test_ecs_ram_role_provider() {
+        use crate::test_util::{MockResponse, MockServer};
+
+        let server = MockServer::start(vec![
+            MockResponse::new(200, "token"),
+            MockResponse::new(200, "EcsRole"),
+            MockResponse::new(
+                200,
+                r#"{"AccessKeyId":"STS.id","AccessKeySecret":"secret","SecurityToken":"sts-token","Expiration":"2030-01-01T00:00:00Z","LastUpdated":"2029-12-31T18:00:00Z","Code":"Success"}"#,
+            ),
+        ]);
+        let provider =
+            EcsRamRoleCredentialsProvider::new().set_endpoint(format!("http://{}", server.host())).set_imds_v2(true);
+        let provider = EcsRamRoleCredentialsProvider { role_name: None, ..provider };
+        let credentials = provider.fetch().unwrap();
+        assert_eq!(credentials.access_key_id(), "STS.id");
+        assert_eq!(credentials.security_token(), Some("sts-token"));
+        assert_eq!(credentials.expiration().map(|e| e.year()), Some(2030));
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "PUT");
+        assert_eq!(requests[1].target, "/latest/meta-data/ram/security-credentials/");
+        assert_eq!(requests[2].target, "/latest/meta-data/ram/security-credentials/EcsRole");
+        assert_eq!(requests[2].header("x-aliyun-ecs-metadata-token"), Some("token"));
+        // the metadata service is never reached through a proxy
+        let direct = provider_transport(Route::Direct).config().proxy.as_ref().map(|proxy| &proxy.kind);
+        assert_eq!(direct, Some(&crate::proxy::ProxyKind::Disabled));
+        assert!(provider_transport(Route::Proxied).config().proxy.is_none());
+    }
+
+    #[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+    #[test]
+    fn test_oidc_role_provider() {
+        use crate::test_util::{MockResponse, MockServer};
+
+        let token_file = std::env::temp_dir().join(format!("oss-oidc-token-{}", std::process::id()));
+        std::fs::write(&token_file, "oidc-token\n").unwrap();
+        let server = MockServer::start(vec![MockResponse::new(
+            200,
+            r#"{"RequestId":"rid","Credentials":{"AccessKeyId":"STS.oidc","AccessKeySecret":"secret","SecurityToken":"sts-token","Expiration":"2030-01-01T00:00:00Z"}}"#,
+        )]);
+        let provider = OidcRoleCredentialsProvider::new(
+            "acs:ram::1:role/r",
+            "acs:ram::1:oidc-provider/p",
+            token_file.to_string_lossy(),
+        )
+        .set_role_session_name("session")
+        .set_sts_endpoint(format!("http://{}", server.host()));
+        let credentials = provider.fetch().unwrap();
+        std::fs::remove_file(&token_file).ok();
+        assert_eq!(credentials.access_key_id(), "STS.oidc");
+        let request = &server.requests()[0];
+        assert_eq!(request.method, "POST");
+        let form = String::from_utf8(request.body.clone()).unwrap();
+        assert!(form.contains("Action=AssumeRoleWithOIDC"));
+        assert!(form.contains("OIDCToken=oidc-token&"));
+        assert!(form.contains("RoleSessionName=session"));
+    }
+}
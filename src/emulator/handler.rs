@@ -0,0 +1,887 @@
+//! In-memory OSS state and the operations served on it.
+//!
+//! 内存中的 OSS 状态及其上提供的操作。
+use super::http::{Request, Response, decode};
+use crate::common::{format_gmt, parse_gmt};
+use crate::crc64;
+use base64::{Engine, engine::general_purpose};
+use http::StatusCode;
+use md5::{Digest, Md5};
+use serde_derive::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use time::{OffsetDateTime, UtcOffset, format_description};
+
+// Every part except the last must be at least 100 KiB
+const MIN_PART_SIZE: usize = 100 * 1024;
+const OWNER_ID: u64 = 1_000_000_000;
+
+/// An OSS error response.
+pub(super) struct Fault {
+    status: StatusCode,
+    code: &'static str,
+    message: String,
+    details: Vec<(&'static str, String)>,
+    headers: Vec<(String, String)>,
+}
+
+impl Fault {
+    pub fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Fault { status, code, message: message.into(), details: Vec::new(), headers: Vec::new() }
+    }
+
+    fn detail(mut self, name: &'static str, value: impl Into<String>) -> Self {
+        self.details.push((name, value.into()));
+        self
+    }
+
+    fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn access_denied(message: &str) -> Self {
+        Fault::new(StatusCode::FORBIDDEN, "AccessDenied", message)
+    }
+
+    pub fn invalid_access_key_id() -> Self {
+        Fault::new(
+            StatusCode::FORBIDDEN,
+            "InvalidAccessKeyId",
+            "The OSS Access Key Id you provided does not exist in our records.",
+        )
+    }
+
+    pub fn signature_does_not_match(access_key_id: &str, string_to_sign: String) -> Self {
+        Fault::new(
+            StatusCode::FORBIDDEN,
+            "SignatureDoesNotMatch",
+            "The request signature we calculated does not match the signature you provided. Check your key and signing method.",
+        )
+        .detail("OSSAccessKeyId", access_key_id)
+        .detail("StringToSign", string_to_sign)
+    }
+
+    pub fn request_time_too_skewed() -> Self {
+        Fault::new(
+            StatusCode::FORBIDDEN,
+            "RequestTimeTooSkewed",
+            "The difference between the request time and the current time is too large.",
+        )
+    }
+
+    pub fn content_sha256_mismatch() -> Self {
+        Fault::new(
+            StatusCode::BAD_REQUEST,
+            "InvalidDigest",
+            "The x-oss-content-sha256 you specified did not match what we received.",
+        )
+    }
+
+    pub fn invalid_argument(message: impl Into<String>) -> Self {
+        Fault::new(StatusCode::BAD_REQUEST, "InvalidArgument", message)
+    }
+
+    pub fn not_implemented() -> Self {
+        Fault::new(StatusCode::NOT_IMPLEMENTED, "NotImplemented", "The emulator does not implement this operation.")
+    }
+
+    fn no_such_bucket(bucket: &str) -> Self {
+        Fault::new(StatusCode::NOT_FOUND, "NoSuchBucket", "The specified bucket does not exist.")
+            .detail("BucketName", bucket)
+    }
+
+    fn no_such_key(key: &str) -> Self {
+        Fault::new(StatusCode::NOT_FOUND, "NoSuchKey", "The specified key does not exist.").detail("Key", key)
+    }
+
+    fn no_such_upload(upload_id: &str) -> Self {
+        Fault::new(
+            StatusCode::NOT_FOUND,
+            "NoSuchUpload",
+            "The specified upload does not exist. The upload ID may be invalid, or the upload may have been aborted or completed.",
+        )
+        .detail("UploadId", upload_id)
+    }
+
+    fn malformed_xml() -> Self {
+        Fault::new(
+            StatusCode::BAD_REQUEST,
+            "MalformedXML",
+            "The XML you provided was not well-formed or did not validate against our published schema.",
+        )
+    }
+
+    fn precondition_failed(condition: &'static str) -> Self {
+        Fault::new(
+            StatusCode::PRECONDITION_FAILED,
+            "PreconditionFailed",
+            "At least one of the pre-conditions you specified did not hold.",
+        )
+        .detail("Condition", condition)
+    }
+
+    /// Render the error as an XML body, or as the Base64 `x-oss-err` header for HEAD requests.
+    pub fn into_response(self, request_id: &str, head: bool) -> Response {
+        let details: String =
+            self.details.iter().map(|(name, value)| format!("<{0}>{1}</{0}>", name, escape(value))).collect();
+        let body = format!(
+            "<Error><Code>{}</Code><Message>{}</Message><RequestId>{}</RequestId><HostId>emulator</HostId>{}<EC>0000-00000000</EC></Error>",
+            self.code,
+            escape(&self.message),
+            request_id,
+            details
+        );
+        let mut response = Response::new(self.status).header("x-oss-ec", "0000-00000000");
+        response.headers.extend(self.headers);
+        if head {
+            let body = format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n{}", body);
+            response.header("x-oss-err", general_purpose::STANDARD.encode(body))
+        } else {
+            response.xml(body)
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct State {
+    buckets: BTreeMap<String, Bucket>,
+    uploads: HashMap<String, Upload>,
+    next_upload_id: u64,
+}
+
+struct Bucket {
+    created: OffsetDateTime,
+    objects: BTreeMap<String, Object>,
+}
+
+#[derive(Clone)]
+struct Object {
+    data: Vec<u8>,
+    etag: String,
+    object_type: &'static str,
+    last_modified: OffsetDateTime,
+    content_type: String,
+    storage_class: String,
+    // cache-control, content-disposition, x-oss-meta-* and similar headers returned on reads
+    headers: Vec<(String, String)>,
+    tags: Vec<(String, String)>,
+}
+
+struct Upload {
+    bucket: String,
+    key: String,
+    template: Object,
+    parts: BTreeMap<u32, Part>,
+}
+
+struct Part {
+    data: Vec<u8>,
+    md5: Vec<u8>,
+    last_modified: OffsetDateTime,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CompleteMultipartUpload {
+    #[serde(default)]
+    part: Vec<CompletePart>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CompletePart {
+    part_number: u32,
+    e_tag: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Tagging {
+    tag_set: TagSet,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TagSet {
+    #[serde(default)]
+    tag: Vec<Tag>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Tag {
+    key: String,
+    #[serde(default)]
+    value: String,
+}
+
+/// Request context shared by the operations.
+pub(super) struct Context<'a> {
+    pub request: &'a Request,
+    pub region: &'a str,
+    pub now: OffsetDateTime,
+}
+
+impl State {
+    pub fn create_bucket(&mut self, bucket: &str, now: OffsetDateTime) -> bool {
+        if self.buckets.contains_key(bucket) {
+            return false;
+        }
+        self.buckets.insert(bucket.to_string(), Bucket { created: now, objects: BTreeMap::new() });
+        true
+    }
+
+    pub fn object_data(&self, bucket: &str, key: &str) -> Option<Vec<u8>> {
+        self.buckets.get(bucket)?.objects.get(key).map(|object| object.data.clone())
+    }
+
+    pub fn handle(&mut self, cx: &Context, bucket: Option<&str>, key: Option<&str>) -> Result<Response, Fault> {
+        let request = cx.request;
+        if let Some(content_md5) = request.header("content-md5")
+            && content_md5 != general_purpose::STANDARD.encode(Md5::digest(&request.body))
+        {
+            return Err(Fault::new(
+                StatusCode::BAD_REQUEST,
+                "InvalidDigest",
+                "The Content-MD5 you specified did not match what we received.",
+            ));
+        }
+        match (bucket, key) {
+            (None, _) if request.method == "GET" => self.list_buckets(cx),
+            (None, _) => Err(Fault::not_implemented()),
+            (Some(bucket), None) => match request.method.as_str() {
+                "PUT" if request.query.is_empty() => self.put_bucket(cx, bucket),
+                "DELETE" if request.query.is_empty() => self.delete_bucket(bucket),
+                "GET" if request.query("list-type") == Some("2") => self.list_objects_v2(cx, bucket),
+                _ => Err(Fault::not_implemented()),
+            },
+            (Some(bucket), Some(key)) => {
+                self.bucket(bucket)?;
+                match request.method.as_str() {
+                    "PUT" if request.has_query("uploadId") => self.upload_part(cx, bucket, key),
+                    "PUT" if request.has_query("tagging") => self.put_object_tagging(bucket, key, &request.body),
+                    "PUT" if request.header("x-oss-copy-source").is_some() => self.copy_object(cx, bucket, key),
+                    "PUT" if request.query.is_empty() => self.put_object(cx, bucket, key),
+                    "GET" if request.has_query("uploadId") => self.list_parts(cx, bucket, key),
+                    "GET" if request.has_query("tagging") => self.get_object_tagging(bucket, key),
+                    "GET" | "HEAD" if request.has_query("objectMeta") => self.get_object_meta(bucket, key),
+                    "GET" | "HEAD" => self.get_object(cx, bucket, key),
+                    "POST" if request.has_query("uploads") => self.init_upload(cx, bucket, key),
+                    "POST" if request.has_query("uploadId") => self.complete_upload(cx, bucket, key),
+                    "POST" if request.has_query("append") => self.append_object(cx, bucket, key),
+                    "DELETE" if request.has_query("uploadId") => self.abort_upload(cx, bucket, key),
+                    "DELETE" if request.has_query("tagging") => self.delete_object_tagging(bucket, key),
+                    "DELETE" if request.query.is_empty() => self.delete_object(bucket, key),
+                    _ => Err(Fault::not_implemented()),
+                }
+            }
+        }
+    }
+
+    fn bucket(&mut self, bucket: &str) -> Result<&mut Bucket, Fault> {
+        self.buckets.get_mut(bucket).ok_or_else(|| Fault::no_such_bucket(bucket))
+    }
+
+    fn object(&mut self, bucket: &str, key: &str) -> Result<&mut Object, Fault> {
+        self.bucket(bucket)?.objects.get_mut(key).ok_or_else(|| Fault::no_such_key(key))
+    }
+
+    fn upload(&mut self, upload_id: &str, bucket: &str, key: &str) -> Result<&mut Upload, Fault> {
+        self.uploads
+            .get_mut(upload_id)
+            .filter(|upload| upload.bucket == bucket && upload.key == key)
+            .ok_or_else(|| Fault::no_such_upload(upload_id))
+    }
+
+    // -------------------------- Service and bucket --------------------------
+
+    fn list_buckets(&mut self, cx: &Context) -> Result<Response, Fault> {
+        let request = cx.request;
+        let prefix = request.query("prefix").unwrap_or_default();
+        let marker = request.query("marker").unwrap_or_default();
+        let max_keys = max_keys(request, "max-keys", 100)?;
+        let names: Vec<&String> =
+            self.buckets.keys().filter(|name| name.as_str() > marker && name.starts_with(prefix)).collect();
+        let truncated = names.len() > max_keys;
+        let names = &names[..names.len().min(max_keys)];
+        let buckets: String = names
+            .iter()
+            .map(|name| {
+                format!(
+                    "<Bucket><CreationDate>{}</CreationDate><ExtranetEndpoint>oss-{region}.aliyuncs.com</ExtranetEndpoint>\
+                     <IntranetEndpoint>oss-{region}-internal.aliyuncs.com</IntranetEndpoint><Location>oss-{region}</Location>\
+                     <Name>{}</Name><Region>{region}</Region><StorageClass>Standard</StorageClass></Bucket>",
+                    format_iso(self.buckets[name.as_str()].created),
+                    name,
+                    region = cx.region
+                )
+            })
+            .collect();
+        Ok(Response::new(StatusCode::OK).xml(format!(
+            "<ListAllMyBucketsResult><Prefix>{}</Prefix><Marker>{}</Marker><MaxKeys>{}</MaxKeys><IsTruncated>{}</IsTruncated>{}\
+             <Owner><ID>{}</ID><DisplayName>{}</DisplayName></Owner><Buckets>{}</Buckets></ListAllMyBucketsResult>",
+            escape(prefix),
+            escape(marker),
+            max_keys,
+            truncated,
+            match names.last() {
+                Some(last) if truncated => format!("<NextMarker>{}</NextMarker>", last),
+                _ => String::new(),
+            },
+            OWNER_ID,
+            OWNER_ID,
+            buckets
+        )))
+    }
+
+    fn put_bucket(&mut self, cx: &Context, bucket: &str) -> Result<Response, Fault> {
+        let valid = (3..=63).contains(&bucket.len())
+            && bucket.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+            && !bucket.starts_with('-')
+            && !bucket.ends_with('-');
+        if !valid {
+            return Err(Fault::new(StatusCode::BAD_REQUEST, "InvalidBucketName", "The specified bucket is not valid.")
+                .detail("BucketName", bucket));
+        }
+        if !self.create_bucket(bucket, cx.now) {
+            return Err(Fault::new(
+                StatusCode::CONFLICT,
+                "BucketAlreadyExists",
+                "The requested bucket name is not available. The bucket namespace is shared by all users of the system. Please select a different name and try again.",
+            )
+            .detail("BucketName", bucket));
+        }
+        Ok(Response::new(StatusCode::OK).header("Location", format!("/{}", bucket)))
+    }
+
+    fn delete_bucket(&mut self, bucket: &str) -> Result<Response, Fault> {
+        let has_uploads = self.uploads.values().any(|upload| upload.bucket == bucket);
+        if !self.bucket(bucket)?.objects.is_empty() || has_uploads {
+            return Err(Fault::new(
+                StatusCode::CONFLICT,
+                "BucketNotEmpty",
+                "The bucket has objects. Please delete them first.",
+            )
+            .detail("BucketName", bucket));
+        }
+        self.buckets.remove(bucket);
+        Ok(Response::new(StatusCode::NO_CONTENT))
+    }
+
+    fn list_objects_v2(&mut self, cx: &Context, bucket: &str) -> Result<Response, Fault> {
+        let request = cx.request;
+        let prefix = request.query("prefix").unwrap_or_default();
+        let delimiter = request.query("delimiter").unwrap_or_default();
+        let start_after = request.query("start-after").unwrap_or_default();
+        let token = request.query("continuation-token").unwrap_or_default();
+        let max_keys = max_keys(request, "max-keys", 100)?;
+        let fetch_owner = request.query("fetch-owner") == Some("true");
+        let start = start_after.max(token);
+        // a token ending with the delimiter is a common prefix that was already returned
+        let skipped_prefix = (!delimiter.is_empty() && start.ends_with(delimiter)).then_some(start);
+
+        let objects = &self.bucket(bucket)?.objects;
+        let mut contents = String::new();
+        let mut common_prefixes: Vec<&str> = Vec::new();
+        let (mut count, mut last, mut truncated) = (0, "", false);
+        for (key, object) in objects.range::<str, _>((std::ops::Bound::Excluded(start), std::ops::Bound::Unbounded)) {
+            if !key.starts_with(prefix) || skipped_prefix.is_some_and(|skipped| key.starts_with(skipped)) {
+                continue;
+            }
+            let common_prefix = (!delimiter.is_empty())
+                .then(|| {
+                    key[prefix.len()..].find(delimiter).map(|index| &key[..prefix.len() + index + delimiter.len()])
+                })
+                .flatten();
+            if let Some(common_prefix) = common_prefix
+                && common_prefixes.last() == Some(&common_prefix)
+            {
+                continue;
+            }
+            if count == max_keys {
+                truncated = true;
+                break;
+            }
+            count += 1;
+            match common_prefix {
+                Some(common_prefix) => {
+                    common_prefixes.push(common_prefix);
+                    last = common_prefix;
+                }
+                None => {
+                    contents.push_str(&format!(
+                        "<Contents><Key>{}</Key><LastModified>{}</LastModified><ETag>{}</ETag><Type>{}</Type><Size>{}</Size>\
+                         <StorageClass>{}</StorageClass>{}</Contents>",
+                        escape(key),
+                        format_iso(object.last_modified),
+                        object.etag,
+                        object.object_type,
+                        object.data.len(),
+                        object.storage_class,
+                        if fetch_owner { owner() } else { String::new() }
+                    ));
+                    last = key;
+                }
+            }
+        }
+        let common_prefixes: String = common_prefixes
+            .iter()
+            .map(|prefix| format!("<CommonPrefixes><Prefix>{}</Prefix></CommonPrefixes>", escape(prefix)))
+            .collect();
+        Ok(Response::new(StatusCode::OK).xml(format!(
+            "<ListBucketResult><Name>{}</Name><Prefix>{}</Prefix><StartAfter>{}</StartAfter><MaxKeys>{}</MaxKeys>\
+             <Delimiter>{}</Delimiter><IsTruncated>{}</IsTruncated>{}<KeyCount>{}</KeyCount>{}{}</ListBucketResult>",
+            bucket,
+            escape(prefix),
+            escape(start_after),
+            max_keys,
+            escape(delimiter),
+            truncated,
+            if truncated {
+                format!("<NextContinuationToken>{}</NextContinuationToken>", escape(last))
+            } else {
+                String::new()
+            },
+            count,
+            contents,
+            common_prefixes
+        )))
+    }
+
+    // -------------------------- Objects --------------------------
+
+    fn put_object(&mut self, cx: &Context, bucket: &str, key: &str) -> Result<Response, Fault> {
+        let request = cx.request;
+        let objects = &mut self.bucket(bucket)?.objects;
+        if request.header("x-oss-forbid-overwrite").is_some_and(|value| value.eq_ignore_ascii_case("true"))
+            && objects.contains_key(key)
+        {
+            return Err(Fault::new(
+                StatusCode::CONFLICT,
+                "FileAlreadyExists",
+                "The object you specified already exists and can not be overwritten.",
+            ));
+        }
+        let object = new_object(request, request.body.clone(), "Normal", cx.now);
+        let response = Response::new(StatusCode::OK)
+            .header("ETag", object.etag.clone())
+            .header("x-oss-hash-crc64ecma", crc64::update(0, &object.data).to_string());
+        objects.insert(key.to_string(), object);
+        Ok(response)
+    }
+
+    fn get_object(&mut self, cx: &Context, bucket: &str, key: &str) -> Result<Response, Fault> {
+        let request = cx.request;
+        let object = self.object(bucket, key)?;
+        if let Some(response) = check_conditions(request, object, "")? {
+            return Ok(response);
+        }
+        let mut response = Response::new(StatusCode::OK);
+        response.headers = object_headers(object);
+        response.headers.push(("x-oss-hash-crc64ecma".to_string(), crc64::update(0, &object.data).to_string()));
+        let size = object.data.len();
+        match request.header("range").and_then(|range| parse_range(range, size)) {
+            Some(Ok((start, end))) => {
+                response.status = StatusCode::PARTIAL_CONTENT;
+                response.headers.push(("Content-Range".to_string(), format!("bytes {}-{}/{}", start, end, size)));
+                Ok(response.body(&object.data[start..=end]))
+            }
+            Some(Err(())) => Err(Fault::new(
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                "InvalidRange",
+                "The requested range cannot be satisfied",
+            )
+            .header("Content-Range", format!("bytes */{}", size))),
+            None => Ok(response.body(object.data.clone())),
+        }
+    }
+
+    fn get_object_meta(&mut self, bucket: &str, key: &str) -> Result<Response, Fault> {
+        let object = self.object(bucket, key)?;
+        Ok(Response::new(StatusCode::OK)
+            .header("ETag", object.etag.clone())
+            .header("Last-Modified", format_gmt(object.last_modified))
+            .header("Content-Length", object.data.len().to_string()))
+    }
+
+    fn delete_object(&mut self, bucket: &str, key: &str) -> Result<Response, Fault> {
+        self.bucket(bucket)?.objects.remove(key);
+        Ok(Response::new(StatusCode::NO_CONTENT))
+    }
+
+    fn copy_object(&mut self, cx: &Context, bucket: &str, key: &str) -> Result<Response, Fault> {
+        let request = cx.request;
+        let source = self.copy_source(request)?.clone();
+        if let Some(response) = check_conditions(request, &source, "x-oss-copy-source-")? {
+            return Ok(response);
+        }
+        let mut object = if request.header("x-oss-metadata-directive") == Some("REPLACE") {
+            new_object(request, source.data.clone(), "Normal", cx.now)
+        } else {
+            Object { last_modified: cx.now, object_type: "Normal", ..source.clone() }
+        };
+        object.tags = if request.header("x-oss-tagging-directive") == Some("Replace") {
+            parse_tagging_header(request.header("x-oss-tagging").unwrap_or_default())
+        } else {
+            source.tags
+        };
+        let response = Response::new(StatusCode::OK).header("ETag", object.etag.clone()).xml(format!(
+            "<CopyObjectResult><LastModified>{}</LastModified><ETag>{}</ETag></CopyObjectResult>",
+            format_iso(object.last_modified),
+            object.etag
+        ));
+        self.bucket(bucket)?.objects.insert(key.to_string(), object);
+        Ok(response)
+    }
+
+    fn copy_source(&mut self, request: &Request) -> Result<&mut Object, Fault> {
+        let source = request.header("x-oss-copy-source").unwrap_or_default();
+        let source = decode(source.split('?').next().unwrap_or_default());
+        let (bucket, key) = source.trim_start_matches('/').split_once('/').ok_or_else(|| {
+            Fault::invalid_argument("Copy Source must mention the source bucket and key: /sourcebucket/sourcekey.")
+        })?;
+        self.object(bucket, key)
+    }
+
+    fn append_object(&mut self, cx: &Context, bucket: &str, key: &str) -> Result<Response, Fault> {
+        let request = cx.request;
+        let position = request
+            .query("position")
+            .and_then(|position| position.parse::<usize>().ok())
+            .ok_or_else(|| Fault::invalid_argument("Position is invalid."))?;
+        let objects = &mut self.bucket(bucket)?.objects;
+        let object = match objects.get_mut(key) {
+            Some(object) if object.object_type != "Appendable" => {
+                return Err(Fault::new(StatusCode::CONFLICT, "ObjectNotAppendable", "The object is not appendable"));
+            }
+            Some(object) => object,
+            None => objects.entry(key.to_string()).or_insert(new_object(request, Vec::new(), "Appendable", cx.now)),
+        };
+        if position != object.data.len() {
+            return Err(Fault::new(
+                StatusCode::CONFLICT,
+                "PositionNotEqualToLength",
+                "Position is not equal to file length",
+            )
+            .header("x-oss-next-append-position", object.data.len().to_string()));
+        }
+        object.data.extend_from_slice(&request.body);
+        object.etag = etag(&object.data);
+        object.last_modified = cx.now;
+        Ok(Response::new(StatusCode::OK)
+            .header("ETag", object.etag.clone())
+            .header("x-oss-next-append-position", object.data.len().to_string())
+            .header("x-oss-hash-crc64ecma", crc64::update(0, &object.data).to_string()))
+    }
+
+    fn put_object_tagging(&mut self, bucket: &str, key: &str, body: &[u8]) -> Result<Response, Fault> {
+        let tagging: Tagging = serde_xml_rs::from_reader(body).map_err(|_| Fault::malformed_xml())?;
+        self.object(bucket, key)?.tags = tagging.tag_set.tag.into_iter().map(|tag| (tag.key, tag.value)).collect();
+        Ok(Response::new(StatusCode::OK))
+    }
+
+    fn get_object_tagging(&mut self, bucket: &str, key: &str) -> Result<Response, Fault> {
+        let tags: String = self
+            .object(bucket, key)?
+            .tags
+            .iter()
+            .map(|(key, value)| format!("<Tag><Key>{}</Key><Value>{}</Value></Tag>", escape(key), escape(value)))
+            .collect();
+        Ok(Response::new(StatusCode::OK).xml(format!("<Tagging><TagSet>{}</TagSet></Tagging>", tags)))
+    }
+
+    fn delete_object_tagging(&mut self, bucket: &str, key: &str) -> Result<Response, Fault> {
+        self.object(bucket, key)?.tags.clear();
+        Ok(Response::new(StatusCode::NO_CONTENT))
+    }
+
+    // -------------------------- Multipart upload --------------------------
+
+    fn init_upload(&mut self, cx: &Context, bucket: &str, key: &str) -> Result<Response, Fault> {
+        self.next_upload_id += 1;
+        let upload_id = format!("{:032X}", self.next_upload_id);
+        let template = new_object(cx.request, Vec::new(), "Multipart", cx.now);
+        let upload = Upload { bucket: bucket.to_string(), key: key.to_string(), template, parts: BTreeMap::new() };
+        self.uploads.insert(upload_id.clone(), upload);
+        Ok(Response::new(StatusCode::OK).xml(format!(
+            "<InitiateMultipartUploadResult><Bucket>{}</Bucket><Key>{}</Key><UploadId>{}</UploadId></InitiateMultipartUploadResult>",
+            bucket,
+            escape(key),
+            upload_id
+        )))
+    }
+
+    fn upload_part(&mut self, cx: &Context, bucket: &str, key: &str) -> Result<Response, Fault> {
+        let request = cx.request;
+        let part_number = request
+            .query("partNumber")
+            .and_then(|number| number.parse::<u32>().ok())
+            .filter(|number| (1..=10000).contains(number))
+            .ok_or_else(|| Fault::invalid_argument("Part number must be an integer between 1 and 10000, inclusive."))?;
+        let upload_id = request.query("uploadId").unwrap_or_default();
+        self.upload(upload_id, bucket, key)?;
+        let (data, copied) = if request.header("x-oss-copy-source").is_some() {
+            let source = self.copy_source(request)?;
+            if let Some(response) = check_conditions(request, source, "x-oss-copy-source-")? {
+                return Ok(response);
+            }
+            let data =
+                match request.header("x-oss-copy-source-range").and_then(|range| parse_range(range, source.data.len()))
+                {
+                    Some(Ok((start, end))) => source.data[start..=end].to_vec(),
+                    _ => source.data.clone(),
+                };
+            (data, true)
+        } else {
+            (request.body.clone(), false)
+        };
+        let md5 = Md5::digest(&data).to_vec();
+        let etag = format!("\"{}\"", hex_upper(&md5));
+        let crc = crc64::update(0, &data).to_string();
+        let part = Part { data, md5, last_modified: cx.now };
+        self.upload(upload_id, bucket, key)?.parts.insert(part_number, part);
+        if copied {
+            Ok(Response::new(StatusCode::OK).header("x-oss-hash-crc64ecma", crc).xml(format!(
+                "<CopyPartResult><LastModified>{}</LastModified><ETag>{}</ETag></CopyPartResult>",
+                format_iso(cx.now),
+                etag
+            )))
+        } else {
+            Ok(Response::new(StatusCode::OK).header("ETag", etag).header("x-oss-hash-crc64ecma", crc))
+        }
+    }
+
+    fn complete_upload(&mut self, cx: &Context, bucket: &str, key: &str) -> Result<Response, Fault> {
+        let request = cx.request;
+        let upload_id = request.query("uploadId").unwrap_or_default();
+        let upload = self.upload(upload_id, bucket, key)?;
+        let complete: CompleteMultipartUpload =
+            serde_xml_rs::from_reader(request.body.as_slice()).map_err(|_| Fault::malformed_xml())?;
+        if complete.part.is_empty() {
+            return Err(Fault::malformed_xml());
+        }
+        if complete.part.windows(2).any(|pair| pair[0].part_number >= pair[1].part_number) {
+            return Err(Fault::new(
+                StatusCode::BAD_REQUEST,
+                "InvalidPartOrder",
+                "The list of parts was not in ascending order. Parts list must specified in order by part number.",
+            ));
+        }
+        let mut data = Vec::new();
+        let mut md5s = Vec::new();
+        for (index, listed) in complete.part.iter().enumerate() {
+            let part = upload
+                .parts
+                .get(&listed.part_number)
+                .filter(|part| hex_upper(&part.md5) == listed.e_tag.trim_matches('"').to_ascii_uppercase())
+                .ok_or_else(|| {
+                    Fault::new(
+                        StatusCode::BAD_REQUEST,
+                        "InvalidPart",
+                        "One or more of the specified parts could not be found or the specified entity tag might not have matched the part's entity tag.",
+                    )
+                    .detail("PartNumber", listed.part_number.to_string())
+                })?;
+            if index + 1 < complete.part.len() && part.data.len() < MIN_PART_SIZE {
+                return Err(Fault::new(
+                    StatusCode::BAD_REQUEST,
+                    "EntityTooSmall",
+                    "Your proposed upload is smaller than the minimum allowed size.",
+                )
+                .detail("PartNumber", listed.part_number.to_string()));
+            }
+            data.extend_from_slice(&part.data);
+            md5s.extend_from_slice(&part.md5);
+        }
+        let upload = self.uploads.remove(upload_id).expect("upload checked above");
+        let etag = format!("\"{}-{}\"", hex_upper(&Md5::digest(&md5s)), complete.part.len());
+        let object = Object { data, etag: etag.clone(), last_modified: cx.now, ..upload.template };
+        self.bucket(bucket)?.objects.insert(key.to_string(), object);
+        Ok(Response::new(StatusCode::OK).xml(format!(
+            "<CompleteMultipartUploadResult><Location>/{}/{}</Location><Bucket>{}</Bucket><Key>{}</Key><ETag>{}</ETag></CompleteMultipartUploadResult>",
+            bucket,
+            escape(key),
+            bucket,
+            escape(key),
+            etag
+        )))
+    }
+
+    fn list_parts(&mut self, cx: &Context, bucket: &str, key: &str) -> Result<Response, Fault> {
+        let request = cx.request;
+        let upload_id = request.query("uploadId").unwrap_or_default();
+        let marker = request.query("part-number-marker").and_then(|marker| marker.parse::<u32>().ok()).unwrap_or(0);
+        let max_parts = max_keys(request, "max-parts", 1000)?;
+        let upload = self.upload(upload_id, bucket, key)?;
+        let mut parts = upload.parts.range(marker + 1..).peekable();
+        let mut listed = String::new();
+        let mut next_marker = marker;
+        for _ in 0..max_parts {
+            let Some((number, part)) = parts.next() else { break };
+            listed.push_str(&format!(
+                "<Part><PartNumber>{}</PartNumber><LastModified>{}</LastModified><ETag>\"{}\"</ETag>\
+                 <HashCrc64ecma>{}</HashCrc64ecma><Size>{}</Size></Part>",
+                number,
+                format_iso(part.last_modified),
+                hex_upper(&part.md5),
+                crc64::update(0, &part.data),
+                part.data.len()
+            ));
+            next_marker = *number;
+        }
+        Ok(Response::new(StatusCode::OK).xml(format!(
+            "<ListPartsResult><Bucket>{}</Bucket><Key>{}</Key><UploadId>{}</UploadId><StorageClass>{}</StorageClass>\
+             <PartNumberMarker>{}</PartNumberMarker><NextPartNumberMarker>{}</NextPartNumberMarker><MaxParts>{}</MaxParts>\
+             <IsTruncated>{}</IsTruncated>{}</ListPartsResult>",
+            bucket,
+            escape(key),
+            upload_id,
+            upload.template.storage_class,
+            marker,
+            next_marker,
+            max_parts,
+            parts.peek().is_some(),
+            listed
+        )))
+    }
+
+    fn abort_upload(&mut self, cx: &Context, bucket: &str, key: &str) -> Result<Response, Fault> {
+        let upload_id = cx.request.query("uploadId").unwrap_or_default();
+        self.upload(upload_id, bucket, key)?;
+        self.uploads.remove(upload_id);
+        Ok(Response::new(StatusCode::NO_CONTENT))
+    }
+}
+
+fn max_keys(request: &Request, name: &str, default: usize) -> Result<usize, Fault> {
+    match request.query(name) {
+        None => Ok(default),
+        Some(value) => value.parse::<usize>().ok().filter(|value| (1..=1000).contains(value)).ok_or_else(|| {
+            Fault::invalid_argument(format!("Argument {} must be an integer between 1 and 1000.", name))
+        }),
+    }
+}
+
+fn new_object(request: &Request, data: Vec<u8>, object_type: &'static str, now: OffsetDateTime) -> Object {
+    let headers = request
+        .headers
+        .iter()
+        .map(|(key, value)| (key.to_ascii_lowercase(), value.clone()))
+        .filter(|(key, _)| {
+            matches!(
+                key.as_str(),
+                "cache-control" | "content-disposition" | "content-encoding" | "content-language" | "expires"
+            ) || key.starts_with("x-oss-meta-")
+        })
+        .collect();
+    Object {
+        etag: etag(&data),
+        data,
+        object_type,
+        last_modified: now,
+        content_type: request.header("content-type").unwrap_or("application/octet-stream").to_string(),
+        storage_class: request.header("x-oss-storage-class").unwrap_or("Standard").to_string(),
+        headers,
+        tags: parse_tagging_header(request.header("x-oss-tagging").unwrap_or_default()),
+    }
+}
+
+fn object_headers(object: &Object) -> Vec<(String, String)> {
+    let mut headers = vec![
+        ("Content-Type".to_string(), object.content_type.clone()),
+        ("ETag".to_string(), object.etag.clone()),
+        ("Last-Modified".to_string(), format_gmt(object.last_modified)),
+        ("Accept-Ranges".to_string(), "bytes".to_string()),
+        ("x-oss-object-type".to_string(), object.object_type.to_string()),
+        ("x-oss-storage-class".to_string(), object.storage_class.clone()),
+    ];
+    if object.object_type == "Appendable" {
+        headers.push(("x-oss-next-append-position".to_string(), object.data.len().to_string()));
+    }
+    if !object.tags.is_empty() {
+        headers.push(("x-oss-tagging-count".to_string(), object.tags.len().to_string()));
+    }
+    headers.extend(object.headers.iter().cloned());
+    headers
+}
+
+// Evaluate If-Match, If-Unmodified-Since, If-None-Match and If-Modified-Since, in that order;
+// `prefix` selects the `x-oss-copy-source-` variants
+fn check_conditions(request: &Request, object: &Object, prefix: &str) -> Result<Option<Response>, Fault> {
+    let header = |name: &str| request.header(&format!("{}{}", prefix, name));
+    let etag = object.etag.trim_matches('"');
+    let modified = object.last_modified.unix_timestamp();
+    if let Some(expected) = header("if-match")
+        && expected.trim_matches('"') != etag
+    {
+        return Err(Fault::precondition_failed("If-Match"));
+    }
+    if let Some(since) = header("if-unmodified-since").and_then(parse_gmt)
+        && modified > since.unix_timestamp()
+    {
+        return Err(Fault::precondition_failed("If-Unmodified-Since"));
+    }
+    let not_modified = match header("if-none-match") {
+        Some(unexpected) => unexpected.trim_matches('"') == etag,
+        None => header("if-modified-since").and_then(parse_gmt).is_some_and(|since| modified <= since.unix_timestamp()),
+    };
+    match (not_modified, prefix.is_empty()) {
+        (false, _) => Ok(None),
+        (true, true) => Ok(Some(Response::new(StatusCode::NOT_MODIFIED).header("ETag", object.etag.clone()))),
+        (true, false) => Err(Fault::precondition_failed("If-None-Match")),
+    }
+}
+
+// `bytes=start-end`, `bytes=start-` or `bytes=-suffix`; malformed ranges are ignored as OSS does
+fn parse_range(range: &str, size: usize) -> Option<Result<(usize, usize), ()>> {
+    let (start, end) = range.trim().strip_prefix("bytes=")?.split_once('-')?;
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let suffix = suffix.parse::<usize>().ok()?;
+            (size.saturating_sub(suffix), size.checked_sub(1)?)
+        }
+        (start, "") => (start.parse().ok()?, size.saturating_sub(1)),
+        (start, end) => {
+            let (start, end): (usize, usize) = (start.parse().ok()?, end.parse().ok()?);
+            if end < start {
+                return None;
+            }
+            (start, end.min(size.saturating_sub(1)))
+        }
+    };
+    if start >= size { Some(Err(())) } else { Some(Ok((start, end))) }
+}
+
+fn parse_tagging_header(tagging: &str) -> Vec<(String, String)> {
+    tagging
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode(key), decode(value))
+        })
+        .collect()
+}
+
+fn owner() -> String {
+    format!("<Owner><ID>{0}</ID><DisplayName>{0}</DisplayName></Owner>", OWNER_ID)
+}
+
+fn etag(data: &[u8]) -> String {
+    format!("\"{}\"", hex_upper(&Md5::digest(data)))
+}
+
+fn hex_upper(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+fn format_iso(datetime: OffsetDateTime) -> String {
+    datetime
+        .to_offset(UtcOffset::UTC)
+        .format(&format_description::parse("[year]-[month]-[day]T[hour]:[minute]:[second].000Z").expect("valid format"))
+        .expect("formatting")
+}
+
+fn escape(input: &str) -> String {
+    input.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
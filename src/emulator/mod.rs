@@ -0,0 +1,664 @@
+//! In-process OSS emulator for integration tests, enabled by the `test-support` feature.
+//!
+//! It serves a subset of the OSS API from memory on a local port: creating, listing and deleting
+//! buckets; Put/Get/Head/Delete object and GetObjectMeta; ListObjectsV2 with pagination; copy,
+//! append and tagging; and multipart upload. Every request must carry a valid Signature V4, either in
+//! the `Authorization` header or as a pre-signed URL, and errors are returned as OSS XML error bodies.
+//!
+//! The emulator listens on `127.0.0.1` and is addressed path-style (`endpoint/bucket/key`) through a
+//! custom endpoint; [`OssEmulator::client`] returns a client configured that way. Requests whose `Host`
+//! is `bucket.<host>` are also accepted as virtual-hosted style.
+//!
+//! 用于集成测试的进程内 OSS 模拟器，由 `test-support` feature 启用。
+//!
+//! 它在本地端口上以内存方式提供部分 OSS API：创建、列举与删除 Bucket；上传、下载、HEAD、删除对象与
+//! GetObjectMeta；支持分页的 ListObjectsV2；拷贝、追加与标签；以及分片上传。每个请求都必须携带有效的
+//! V4 签名（位于 `Authorization` 头或预签名 URL 中），错误以 OSS XML 错误体返回。
+//!
+//! 模拟器监听 `127.0.0.1`，通过自定义 Endpoint 以路径风格（`endpoint/bucket/key`）访问；
+//! [`OssEmulator::client`] 返回按此配置的客户端。`Host` 为 `bucket.<host>` 的请求也会按虚拟主机风格处理。
+//!
+//! ```ignore
+//! let emulator = OssEmulator::start("id", "secret", "cn-hangzhou")?;
+//! emulator.create_bucket("bucket");
+//! let object = emulator.client().bucket("bucket").object("a.txt");
+//! object.put_object().send_content(b"hello".to_vec()).await?;
+//! assert_eq!(emulator.object("bucket", "a.txt").unwrap(), b"hello");
+//! ```
+mod auth;
+mod handler;
+mod http;
+
+use self::handler::{Context, Fault, State};
+use self::http::{Request, read_request, write_response};
+use crate::common::AddressingStyle;
+use crate::common::format_gmt;
+use crate::{Credentials, OssClient};
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use time::OffsetDateTime;
+
+// Query parameters of pre-signed URLs, removed once the signature is verified
+const SIGNING_QUERIES: &[&str] = &[
+    "x-oss-signature-version",
+    "x-oss-credential",
+    "x-oss-date",
+    "x-oss-expires",
+    "x-oss-signature",
+    "x-oss-additional-headers",
+    "x-oss-security-token",
+];
+
+struct Shared {
+    credentials: Credentials,
+    region: String,
+    state: Mutex<State>,
+    request_count: AtomicU64,
+    shutdown: AtomicBool,
+}
+
+/// In-memory OSS server running on a background thread; it stops when dropped.
+///
+/// 在后台线程中运行的内存 OSS 服务，被 drop 时停止。
+pub struct OssEmulator {
+    addr: SocketAddr,
+    shared: Arc<Shared>,
+}
+
+impl OssEmulator {
+    /// Start an emulator that accepts requests signed with the given AccessKey pair for `region`.
+    ///
+    /// 启动模拟器，接受使用指定 AccessKey 针对 `region` 签名的请求。
+    pub fn start(
+        access_key_id: impl Into<String>,
+        access_key_secret: impl Into<String>,
+        region: impl Into<String>,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let shared = Arc::new(Shared {
+            credentials: Credentials::new(access_key_id, access_key_secret),
+            region: region.into(),
+            state: Mutex::new(State::default()),
+            request_count: AtomicU64::new(0),
+            shutdown: AtomicBool::new(false),
+        });
+        let server = Arc::clone(&shared);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if server.shutdown.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(stream) = stream else { continue };
+                let server = Arc::clone(&server);
+                thread::spawn(move || server.serve(stream));
+            }
+        });
+        Ok(OssEmulator { addr, shared })
+    }
+    /// Host and port to use as the client endpoint, e.g. `127.0.0.1:34567`.
+    ///
+    /// 用作客户端 Endpoint 的主机与端口，例如 `127.0.0.1:34567`。
+    pub fn endpoint(&self) -> String {
+        self.addr.to_string()
+    }
+    /// Region that request signatures must be scoped to.
+    ///
+    /// 请求签名所需的地域。
+    pub fn region(&self) -> &str {
+        &self.shared.region
+    }
+    /// A client using the emulator credentials, endpoint, plain HTTP and path-style addressing.
+    ///
+    /// 使用模拟器凭证、Endpoint、HTTP 与路径风格寻址的客户端。
+    pub fn client(&self) -> OssClient {
+        let mut client = OssClient::new(
+            self.shared.credentials.access_key_id(),
+            self.shared.credentials.access_key_secret(),
+            &self.shared.region,
+        )
+        .disable_https()
+        .with_addressing_style(AddressingStyle::Path);
+        client.set_endpoint(self.endpoint());
+        client
+    }
+    /// Create a bucket directly, bypassing the HTTP API; returns `false` if it already exists.
+    ///
+    /// 绕过 HTTP API 直接创建 Bucket；已存在时返回 `false`。
+    pub fn create_bucket(&self, bucket: &str) -> bool {
+        self.shared.state().create_bucket(bucket, OffsetDateTime::now_utc())
+    }
+    /// Content of a stored object, read directly from memory.
+    ///
+    /// 直接从内存读取已存储对象的内容。
+    pub fn object(&self, bucket: &str, key: &str) -> Option<Vec<u8>> {
+        self.shared.state().object_data(bucket, key)
+    }
+    /// Number of requests received so far, including rejected ones.
+    ///
+    /// 目前已收到的请求数，包括被拒绝的请求。
+    pub fn request_count(&self) -> u64 {
+        self.shared.request_count.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for OssEmulator {
+    fn drop(&mut self) {
+        self.shared.shutdown.store(true, Ordering::SeqCst);
+        // wake the accept loop so it sees the flag
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+impl Shared {
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn serve(&self, mut stream: TcpStream) {
+        let Ok(Some(mut request)) = read_request(&mut stream) else { return };
+        let count = self.request_count.fetch_add(1, Ordering::SeqCst) + 1;
+        let request_id = format!("{:024X}", count);
+        let now = OffsetDateTime::now_utc();
+        let head = request.method == "HEAD";
+        let mut response = match self.dispatch(&mut request, now) {
+            Ok(response) => response,
+            Err(fault) => fault.into_response(&request_id, head),
+        };
+        response.headers.push(("Server".to_string(), "AliyunOSS".to_string()));
+        response.headers.push(("Date".to_string(), format_gmt(now)));
+        response.headers.push(("x-oss-request-id".to_string(), request_id));
+        let _ = write_response(&mut stream, &response, head);
+    }
+
+    fn dispatch(&self, request: &mut Request, now: OffsetDateTime) -> Result<self::http::Response, Fault> {
+        let (bucket, key) = route(request)?;
+        self::auth::verify(&self.credentials, &self.region, request, bucket.as_deref(), key.as_deref(), now)?;
+        request.query.retain(|(name, _)| !SIGNING_QUERIES.contains(&name.as_str()));
+        let cx = Context { request, region: &self.region, now };
+        self.state().handle(&cx, bucket.as_deref(), key.as_deref())
+    }
+}
+
+// Bucket and key from the `Host` header (virtual-hosted style) or the first path segment (path style)
+fn route(request: &Request) -> Result<(Option<String>, Option<String>), Fault> {
+    let path = self::http::decode(request.path.strip_prefix('/').unwrap_or(&request.path));
+    let host = request.header("host").unwrap_or_default();
+    let host = host.rsplit_once(':').map(|(host, _)| host).unwrap_or(host);
+    let virtual_bucket = match host.split_once('.') {
+        Some((bucket, _)) if host.parse::<std::net::IpAddr>().is_err() => Some(bucket.to_string()),
+        _ => None,
+    };
+    let (bucket, key) = match virtual_bucket {
+        Some(bucket) => (Some(bucket), Some(path)),
+        None => match path.split_once('/') {
+            Some((bucket, key)) => (Some(bucket.to_string()), Some(key.to_string())),
+            None => (Some(path), None),
+        },
+    };
+    let bucket = bucket.filter(|bucket| !bucket.is_empty());
+    if bucket.is_none() && key.as_deref().is_some_and(|key| !key.is_empty()) {
+        return Err(Fault::invalid_argument("Object key requires a bucket."));
+    }
+    Ok((bucket, key.filter(|key| !key.is_empty())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    #[cfg(feature = "_async-base")]
+    use crate::{OssObject, RetryPolicy};
+    #[cfg(feature = "_async-base")]
+    use ::http::Method;
+    #[cfg(feature = "_async-base")]
+    use std::io::{Read, Write};
+    #[cfg(feature = "_async-base")]
+    use time::Duration;
+
+    fn oss_code<T: std::fmt::Debug>(result: Result<T, Error>) -> String {
+        match result {
+            Err(Error::OssError(_, error)) => error.code,
+            other => panic!("expected an OSS error, got {:?}", other),
+        }
+    }
+
+    // Send a bare HTTP/1.1 request to a pre-signed URL and return the status code and body
+    #[cfg(feature = "_async-base")]
+    fn fetch(method: &str, url: &str) -> (u16, String) {
+        let rest = url.split_once("://").unwrap().1;
+        let (host, path) = rest.split_at(rest.find('/').unwrap());
+        let mut stream = TcpStream::connect(host).unwrap();
+        write!(stream, "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Length: 0\r\n\r\n", method, path, host).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response[9..12].parse().unwrap();
+        let body = response.split_once("\r\n\r\n").map(|(_, body)| body.to_string()).unwrap_or_default();
+        (status, body)
+    }
+
+    #[test]
+    fn test_route_path_and_virtual_hosted() {
+        let request = |path: &str, host: &str| Request {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            query: Vec::new(),
+            headers: vec![("Host".to_string(), host.to_string())],
+            body: Vec::new(),
+        };
+        let route = |path, host| route(&request(path, host)).ok().unwrap();
+        assert_eq!(route("/", "127.0.0.1:9000"), (None, None));
+        assert_eq!(route("/bucket/", "127.0.0.1:9000"), (Some("bucket".to_string()), None));
+        assert_eq!(
+            route("/bucket/dir/a%20b.txt", "127.0.0.1:9000"),
+            (Some("bucket".to_string()), Some("dir/a b.txt".to_string()))
+        );
+        assert_eq!(
+            route("/a.txt", "bucket.oss-cn-hangzhou.aliyuncs.com"),
+            (Some("bucket".to_string()), Some("a.txt".to_string()))
+        );
+        assert_eq!(route("/", "localhost:9000"), (None, None));
+    }
+
+    #[cfg(feature = "_async-base")]
+    #[test]
+    fn test_emulator_objects() {
+        let emulator = OssEmulator::start("id", "secret", "cn-hangzhou").unwrap();
+        let client = emulator.client();
+        let bucket = client.bucket("bucket");
+        let object = bucket.object("dir/hello world.txt");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            assert_eq!(oss_code(object.put_object().send_content(b"x".to_vec()).await), "NoSuchBucket");
+            bucket.put_bucket().send().await.unwrap();
+            assert_eq!(oss_code(bucket.put_bucket().send().await), "BucketAlreadyExists");
+            let names: Vec<String> =
+                client.list_buckets().send().await.unwrap().buckets.unwrap().into_iter().map(|b| b.name).collect();
+            assert_eq!(names, ["bucket"]);
+
+            let put = object
+                .put_object()
+                .set_mime("text/plain")
+                .set_meta("author", "alice")
+                .set_tagging("team", "storage")
+                .send_content(b"hello world".to_vec())
+                .await
+                .unwrap();
+            assert!(put.request_id.is_some());
+            assert_eq!(put.etag.as_deref(), Some("\"5EB63BBBE01EEED093CB22BB8F5ACDC3\""));
+            assert_eq!(put.crc64, Some(crate::crc64::update(0, b"hello world")));
+            assert_eq!(emulator.object("bucket", "dir/hello world.txt").unwrap(), b"hello world");
+            assert_eq!(object.get_object().download().await.unwrap().as_ref(), b"hello world");
+            assert_eq!(object.get_object().set_range(6, None).download().await.unwrap().as_ref(), b"world");
+            let head = object.head_object().send().await.unwrap();
+            assert_eq!(head["x-oss-object-type"], "Normal");
+            assert_eq!(head["x-oss-meta-author"], "alice");
+            let meta = object.get_object_meta().send().await.unwrap();
+            assert_eq!(meta.content_length, "11");
+            assert_eq!(oss_code(object.get_object().set_if_match("\"0\"").download().await), "PreconditionFailed");
+            assert_eq!(oss_code(bucket.object("missing").head_object().send().await), "NoSuchKey");
+
+            let tags = object.get_object_tagging().send().await.unwrap().unwrap();
+            assert_eq!((tags[0].key.as_str(), tags[0].value.as_str()), ("team", "storage"));
+            object.put_object_tagging(vec![("env", "test")]).send().await.unwrap();
+            assert_eq!(object.get_object_tagging().send().await.unwrap().unwrap()[0].key, "env");
+            object.del_object_tagging().send().await.unwrap();
+            assert!(object.get_object_tagging().send().await.unwrap().is_none());
+
+            let copy = bucket.object("copy.txt");
+            let copied = copy.copy_object("/bucket/dir/hello%20world.txt").send().await.unwrap();
+            assert_eq!(copied.meta.etag, put.etag);
+            assert!(copied.last_modified.is_some());
+            assert_eq!(emulator.object("bucket", "copy.txt").unwrap(), b"hello world");
+            assert_eq!(copy.head_object().send().await.unwrap()["x-oss-meta-author"], "alice");
+
+            let appendable = bucket.object("log.txt");
+            let first = appendable.append_object().send_content(b"abc".to_vec()).await.unwrap();
+            assert_eq!(first.next_position, Some(3));
+            let next = appendable
+                .append_object()
+                .set_position(3)
+                .set_init_crc64(first.meta.crc64.unwrap())
+                .send_content(b"def".to_vec())
+                .await
+                .unwrap();
+            assert_eq!(next.next_position, Some(6));
+            assert_eq!(next.meta.crc64, Some(crate::crc64::update(0, b"abcdef")));
+            assert_eq!(
+                oss_code(appendable.append_object().set_position(1).send_content(b"g".to_vec()).await),
+                "PositionNotEqualToLength"
+            );
+            assert_eq!(
+                oss_code(object.append_object().set_position(11).send_content(b"g".to_vec()).await),
+                "ObjectNotAppendable"
+            );
+            assert_eq!(emulator.object("bucket", "log.txt").unwrap(), b"abcdef");
+
+            assert_eq!(oss_code(bucket.del_bucket().send().await), "BucketNotEmpty");
+            for key in ["dir/hello world.txt", "copy.txt", "log.txt"] {
+                bucket.object(key).del_object().send().await.unwrap();
+            }
+            assert_eq!(oss_code(object.get_object().download().await), "NoSuchKey");
+            bucket.del_bucket().send().await.unwrap();
+            assert!(client.list_buckets().send().await.unwrap().buckets.is_none());
+        });
+    }
+
+    #[cfg(feature = "_async-base")]
+    #[test]
+    fn test_emulator_list_objects_pagination() {
+        let emulator = OssEmulator::start("id", "secret", "cn-hangzhou").unwrap();
+        emulator.create_bucket("bucket");
+        let bucket = emulator.client().bucket("bucket");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            for key in ["a.txt", "b/1.txt", "b/2.txt", "c.txt", "d/1.txt", "e.txt"] {
+                bucket.object(key).put_object().send_content(key.as_bytes().to_vec()).await.unwrap();
+            }
+            let mut keys = Vec::new();
+            let mut token = None;
+            loop {
+                let mut list = bucket.list_objects().set_max_keys(2);
+                if let Some(token) = token.take() {
+                    list = list.set_continuation_token(token);
+                }
+                let page = list.send().await.unwrap();
+                keys.extend(page.contents.unwrap_or_default().into_iter().map(|object| object.key));
+                match page.next_continuation_token {
+                    Some(next) => token = Some(next),
+                    None => break,
+                }
+            }
+            assert_eq!(keys, ["a.txt", "b/1.txt", "b/2.txt", "c.txt", "d/1.txt", "e.txt"]);
+
+            let mut entries = Vec::new();
+            let mut token = None;
+            loop {
+                let mut list = bucket.list_objects().set_delimiter("/").set_max_keys(2);
+                if let Some(token) = token.take() {
+                    list = list.set_continuation_token(token);
+                }
+                let page = list.send().await.unwrap();
+                entries.extend(page.contents.unwrap_or_default().into_iter().map(|object| object.key));
+                entries.extend(page.common_prefixes.unwrap_or_default().into_iter().map(|prefix| prefix.prefix));
+                match page.next_continuation_token {
+                    Some(next) => token = Some(next),
+                    None => break,
+                }
+            }
+            entries.sort();
+            assert_eq!(entries, ["a.txt", "b/", "c.txt", "d/", "e.txt"]);
+
+            let page =
+                bucket.list_objects().set_prefix("b/").set_start_after("b/1.txt").fetch_owner().send().await.unwrap();
+            let object = &page.contents.unwrap()[0];
+            assert_eq!((object.key.as_str(), object.size), ("b/2.txt", 7));
+            assert!(object.owner.is_some());
+        });
+    }
+
+    #[cfg(feature = "_async-base")]
+    #[test]
+    fn test_emulator_multipart_upload() {
+        let emulator = OssEmulator::start("id", "secret", "cn-hangzhou").unwrap();
+        emulator.create_bucket("bucket");
+        let object = emulator.client().bucket("bucket").object("big.bin");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let upload_id = object.multipart_init_upload().set_meta("kind", "big").send().await.unwrap().upload_id;
+            let first = vec![b'a'; 100 * 1024];
+            let etag1 = object.multipart_upload_part(1, &upload_id).send_content(first.clone()).await.unwrap().etag;
+            let etag2 = object.multipart_upload_part(2, &upload_id).send_content(b"tail".to_vec()).await.unwrap().etag;
+            let parts = object.multipart_list_parts(&upload_id).send().await.unwrap();
+            let sizes: Vec<u64> = parts.part.unwrap().iter().map(|part| part.size).collect();
+            assert_eq!(sizes, [100 * 1024, 4]);
+            assert_eq!(
+                oss_code(
+                    object
+                        .multipart_complete_upload(&upload_id)
+                        .add_parts(vec![("2", &etag2), ("1", &etag1)])
+                        .send()
+                        .await
+                ),
+                "InvalidPartOrder"
+            );
+            assert_eq!(
+                oss_code(object.multipart_complete_upload(&upload_id).add_parts(vec![("1", &etag2)]).send().await),
+                "InvalidPart"
+            );
+            let completed = object
+                .multipart_complete_upload(&upload_id)
+                .add_parts(vec![("1", &etag1), ("2", &etag2)])
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(completed.key.as_deref(), Some("big.bin"));
+            assert!(completed.meta.etag.is_some_and(|etag| etag.ends_with("-2\"")));
+            let mut expected = first;
+            expected.extend_from_slice(b"tail");
+            assert_eq!(emulator.object("bucket", "big.bin").unwrap(), expected);
+            let head = object.head_object().send().await.unwrap();
+            assert!(head["etag"].ends_with("-2"));
+            assert_eq!(head["x-oss-meta-kind"], "big");
+            assert_eq!(oss_code(object.multipart_list_parts(&upload_id).send().await), "NoSuchUpload");
+
+            let upload_id = object.multipart_init_upload().send().await.unwrap().upload_id;
+            object.multipart_abort_upload(&upload_id).send().await.unwrap();
+            assert_eq!(oss_code(object.multipart_abort_upload(&upload_id).send().await), "NoSuchUpload");
+        });
+    }
+
+    #[cfg(feature = "_async-base")]
+    #[test]
+    fn test_emulator_content_md5() {
+        let emulator = OssEmulator::start("id", "secret", "cn-hangzhou").unwrap();
+        emulator.create_bucket("bucket");
+        let bucket = emulator.client().with_retry_policy(RetryPolicy::none()).bucket("bucket");
+        let path = std::env::temp_dir().join(format!("oss-emulator-md5-{}", std::process::id()));
+        std::fs::write(&path, b"from file").unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let object = bucket.object("a.txt");
+            object.put_object().set_content_md5(true).send_content(b"hello".to_vec()).await.unwrap();
+            object.put_object().set_content_md5(true).send_file(path.to_str().unwrap()).await.unwrap();
+            assert_eq!(emulator.object("bucket", "a.txt").unwrap(), b"from file");
+            let log = bucket.object("log.txt");
+            log.append_object().set_content_md5(true).send_content(b"abc".to_vec()).await.unwrap();
+            let upload_id = object.multipart_init_upload().send().await.unwrap().upload_id;
+            object
+                .multipart_upload_part(1, &upload_id)
+                .set_content_md5(true)
+                .send_content(b"x".to_vec())
+                .await
+                .unwrap();
+
+            let corrupted = object
+                .raw_request(Method::PUT)
+                .insert_header("Content-MD5", crate::common::md5_base64(b"hello"))
+                .set_body(b"hellO".to_vec())
+                .send()
+                .await;
+            assert_eq!(oss_code(corrupted), "InvalidDigest");
+        });
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[cfg(feature = "_async-base")]
+    #[test]
+    fn test_emulator_resumable_upload() {
+        // Corrupts the upload of part 2, so the first attempt fails halfway
+        struct FailPart;
+        impl crate::Interceptor for FailPart {
+            fn on_request(&self, request: &mut ::http::Request<()>) {
+                if request.uri().query().is_some_and(|query| query.contains("partNumber=2")) {
+                    request.headers_mut().insert("Content-MD5", "AAAAAAAAAAAAAAAAAAAAAA==".parse().unwrap());
+                }
+            }
+        }
+
+        // Corrupts only the first upload of part 2
+        struct FailOnce(std::sync::atomic::AtomicBool);
+        impl crate::Interceptor for FailOnce {
+            fn on_request(&self, request: &mut ::http::Request<()>) {
+                if request.uri().query().is_some_and(|query| query.contains("partNumber=2"))
+                    && self.0.swap(false, std::sync::atomic::Ordering::SeqCst)
+                {
+                    FailPart.on_request(request);
+                }
+            }
+        }
+
+        let emulator = OssEmulator::start("id", "secret", "cn-hangzhou").unwrap();
+        emulator.create_bucket("bucket");
+        let dir = std::env::temp_dir().join(format!("oss-emulator-resume-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("big.bin");
+        let checkpoint = dir.join("big.bin.checkpoint");
+        let data: Vec<u8> = (0..250 * 1024).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&file, &data).unwrap();
+        let (file, checkpoint) = (file.to_str().unwrap(), checkpoint.to_str().unwrap());
+        let failing = emulator.client().with_retry_policy(RetryPolicy::none()).with_interceptor(FailPart);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let upload = |client: OssClient| {
+                client.bucket("bucket").object("big.bin").upload_file(file).set_part_size(100 * 1024).set_concurrency(1)
+            };
+            assert!(upload(failing.clone()).set_checkpoint(checkpoint).send().await.is_err());
+            let saved: serde_json::Value = serde_json::from_slice(&std::fs::read(checkpoint).unwrap()).unwrap();
+            let parts = saved["parts"].as_array().unwrap();
+            assert_eq!(parts.len(), 1);
+            assert_eq!(parts[0]["crc64"], crate::crc64::update(0, &data[..100 * 1024]));
+
+            // list parts, upload parts 2 and 3, complete
+            let before = emulator.request_count();
+            let progress = Arc::new(Mutex::new(Vec::new()));
+            let seen = progress.clone();
+            let completed = upload(emulator.client())
+                .set_checkpoint(checkpoint)
+                .set_callback(Box::new(move |uploaded, total| seen.lock().unwrap().push((uploaded, total))))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(emulator.request_count() - before, 4);
+            assert!(completed.meta.etag.is_some_and(|etag| etag.ends_with("-3\"")));
+            assert_eq!(*progress.lock().unwrap(), [(200 * 1024, 250 * 1024), (250 * 1024, 250 * 1024)]);
+            assert_eq!(emulator.object("bucket", "big.bin").unwrap(), data);
+            assert!(!std::path::Path::new(checkpoint).exists());
+
+            // without a checkpoint the failed upload is aborted
+            assert!(upload(failing.clone()).set_concurrency(3).send().await.is_err());
+            assert!(!std::path::Path::new(checkpoint).exists());
+
+            // a streamed part is read from the file again when the retry policy allows another attempt
+            let flaky = emulator
+                .client()
+                .with_retry_policy(
+                    RetryPolicy::new(2)
+                        .set_base_delay(std::time::Duration::from_millis(1))
+                        .set_classifier(|e| e.code() == Some(crate::OssErrorCode::SignatureDoesNotMatch)),
+                )
+                .with_interceptor(FailOnce(std::sync::atomic::AtomicBool::new(true)));
+            let before = emulator.request_count();
+            upload(flaky).send().await.unwrap();
+            // init, four part uploads and complete
+            assert_eq!(emulator.request_count() - before, 6);
+            assert_eq!(emulator.object("bucket", "big.bin").unwrap(), data);
+        });
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[cfg(feature = "_async-base")]
+    #[test]
+    fn test_emulator_interceptor_headers() {
+        struct AddHeader(&'static str);
+        impl crate::Interceptor for AddHeader {
+            fn on_request(&self, request: &mut ::http::Request<()>) {
+                request.headers_mut().insert(self.0, ::http::HeaderValue::from_static("trace"));
+            }
+        }
+        let emulator = OssEmulator::start("id", "secret", "cn-hangzhou").unwrap();
+        emulator.create_bucket("bucket");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let put = |header: &'static str| {
+            let client = emulator.client().with_retry_policy(RetryPolicy::none()).with_interceptor(AddHeader(header));
+            runtime.block_on(client.bucket("bucket").object("a.txt").put_object().send_content(b"data".to_vec()))
+        };
+        put("x-trace-id").unwrap();
+        put("user-agent").unwrap();
+        assert_eq!(oss_code(put("x-oss-meta-trace")), "SignatureDoesNotMatch");
+        assert_eq!(oss_code(put("content-type")), "SignatureDoesNotMatch");
+    }
+
+    #[cfg(feature = "_async-base")]
+    #[test]
+    fn test_emulator_verifies_signatures() {
+        let emulator = OssEmulator::start("id", "secret", "cn-hangzhou").unwrap();
+        emulator.create_bucket("bucket");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let put = |client: OssClient| {
+            let object: OssObject = client.with_retry_policy(RetryPolicy::none()).bucket("bucket").object("a.txt");
+            runtime.block_on(object.put_object().send_content(b"data".to_vec()))
+        };
+        let mut wrong_secret = OssClient::new("id", "wrong", "cn-hangzhou").disable_https();
+        wrong_secret.set_endpoint(emulator.endpoint());
+        let wrong_secret = wrong_secret.with_addressing_style(AddressingStyle::Path);
+        assert_eq!(oss_code(put(wrong_secret)), "SignatureDoesNotMatch");
+        let mut wrong_id = OssClient::new("other", "secret", "cn-hangzhou").disable_https();
+        wrong_id.set_endpoint(emulator.endpoint());
+        assert_eq!(oss_code(put(wrong_id.with_addressing_style(AddressingStyle::Path))), "InvalidAccessKeyId");
+        put(emulator.client().with_signed_payload(true)).unwrap();
+
+        let object = emulator.client().bucket("bucket").object("a.txt");
+        let tampered = object
+            .raw_request(Method::PUT)
+            .insert_header("x-oss-content-sha256", crate::common::sha256_hex(b"other"))
+            .set_body(b"data".to_vec())
+            .send();
+        assert_eq!(oss_code(runtime.block_on(tampered)), "InvalidDigest");
+        let expires = OffsetDateTime::now_utc() + Duration::minutes(5);
+        let url = object.get_object_url().url(expires).unwrap();
+        assert_eq!(fetch("GET", &url), (200, "data".to_string()));
+        assert_eq!(fetch("GET", &url.replace("a.txt", "b.txt")).0, 403);
+        let url = object.presign(Method::DELETE).url(expires).unwrap();
+        assert_eq!(fetch("DELETE", &url).0, 204);
+        assert!(emulator.object("bucket", "a.txt").is_none());
+        let (status, body) = fetch("GET", &format!("http://{}/bucket/a.txt", emulator.endpoint()));
+        assert_eq!(status, 403);
+        assert!(body.contains("<Code>AccessDenied</Code>"));
+    }
+
+    #[cfg(feature = "_sync-base")]
+    #[test]
+    fn test_emulator_sync() {
+        let emulator = OssEmulator::start("id", "secret", "cn-hangzhou").unwrap();
+        let bucket = emulator.client().bucket("bucket");
+        bucket.put_bucket_sync().send().unwrap();
+        let object = bucket.object("a.txt");
+        object.put_object_sync().set_content_md5(true).send_content(b"sync".to_vec()).unwrap();
+        assert_eq!(object.get_object_sync().download().unwrap().as_ref(), b"sync");
+        let page = bucket.list_objects_sync().send().unwrap();
+        assert_eq!(page.contents.unwrap()[0].key, "a.txt");
+        object.del_object_sync().send().unwrap();
+        assert_eq!(oss_code(object.head_object_sync().send()), "NoSuchKey");
+
+        let dir = std::env::temp_dir().join(format!("oss-emulator-sync-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (file, checkpoint) = (dir.join("big.bin"), dir.join("big.bin.checkpoint"));
+        let data: Vec<u8> = (0..250 * 1024).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&file, &data).unwrap();
+        let completed = bucket
+            .object("big.bin")
+            .upload_file_sync(file.to_str().unwrap())
+            .set_part_size(100 * 1024)
+            .set_checkpoint(checkpoint.to_str().unwrap())
+            .send()
+            .unwrap();
+        assert!(completed.meta.etag.is_some_and(|etag| etag.ends_with("-3\"")));
+        assert_eq!(emulator.object("bucket", "big.bin").unwrap(), data);
+        assert!(!checkpoint.exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,9 +1,13 @@
 #[cfg(feature = "_async-base")]
 use crate::common::body_to_bytes;
+#[cfg(feature = "_async-base")]
+use crate::transport::ResponseBody;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use base64::{Engine, engine::general_purpose};
 use bytes::Bytes;
-use http::StatusCode;
 #[cfg(feature = "_async-base")]
-use hyper::{Response, body::Incoming};
+use http::Response;
+use http::StatusCode;
 #[cfg(feature = "_async-base")]
 use hyper_util::client::legacy::Error as HyperClientError;
 use serde_derive::Deserialize;
@@ -17,12 +21,13 @@ use ureq::{self, Body};
 ///
 /// OSS 操作错误类型。
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// I/O error.
     ///
     /// I/O 错误。
     #[error("{0}")]
-    IoError(#[from] std::io::Error),
+    IoError(std::io::Error),
     /// Network path is not supported.
     ///
     /// 不支持网络路径。
@@ -81,6 +86,51 @@ pub enum Error {
     /// 请求缺少必要的消息体。
     #[error("请求缺少必要的消息体，请检查调用参数")]
     MissingRequestBody,
+    /// Connecting, waiting for data, or the whole request exceeded its timeout.
+    ///
+    /// 建立连接、等待数据或整个请求超过了超时时间。
+    #[error("请求超时")]
+    Timeout,
+    /// Credentials could not be resolved from the configured provider.
+    ///
+    /// 无法从配置的提供者获取访问凭证。
+    #[error("获取访问凭证失败：{0}")]
+    CredentialsError(String),
+    /// Proxy URL is malformed or uses an unsupported scheme.
+    ///
+    /// 代理地址格式错误或使用了不支持的协议。
+    #[error("代理地址无效：{0}")]
+    InvalidProxy(String),
+    /// TLS settings are invalid or not supported by the enabled TLS backend.
+    ///
+    /// TLS 配置无效，或所启用的 TLS 后端不支持。
+    #[error("TLS 配置无效：{0}")]
+    TlsConfigError(String),
+    /// The CRC-64 computed locally differs from the one OSS reported for the transfer.
+    ///
+    /// 本地计算的 CRC-64 与 OSS 为本次传输返回的值不一致。
+    #[error("CRC64 校验不一致：OSS 返回 {expected}，本地计算 {actual}")]
+    CrcMismatch { expected: u64, actual: u64 },
+}
+
+// Sync body reads report timeouts as I/O errors; surface them as `Timeout`, like async requests
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        if is_timeout(&error) { Error::Timeout } else { Error::IoError(error) }
+    }
+}
+
+// ureq wraps its own timeout errors in an `Other` I/O error
+fn is_timeout(error: &std::io::Error) -> bool {
+    #[cfg(feature = "_sync-base")]
+    if error
+        .get_ref()
+        .and_then(|inner| inner.downcast_ref::<ureq::Error>())
+        .is_some_and(|inner| matches!(inner, ureq::Error::Timeout(_)))
+    {
+        return true;
+    }
+    error.kind() == std::io::ErrorKind::TimedOut
 }
 
 /// Structured OSS error response.
@@ -97,29 +147,311 @@ pub struct OssError {
     /// Error message.
     ///
     /// 错误信息。
-    #[serde(rename = "Message")]
+    #[serde(rename = "Message", default)]
     pub message: String,
-    /// Request ID.
+    /// Request ID, missing from some error bodies.
+    ///
+    /// 请求 ID，部分错误体中不包含。
+    #[serde(rename = "RequestId", default)]
+    pub request_id: Option<String>,
+    /// Extended code (EC), missing from some error bodies.
+    ///
+    /// 扩展错误码（EC），部分错误体中不包含。
+    #[serde(rename = "EC", default)]
+    pub ec: Option<String>,
+    /// Endpoint the bucket must be accessed through, reported with redirect errors.
+    ///
+    /// 访问该 Bucket 应使用的 Endpoint，随重定向类错误返回。
+    #[serde(rename = "Endpoint", default)]
+    pub endpoint: Option<String>,
+}
+
+impl OssError {
+    /// Typed form of [`OssError::code`].
+    ///
+    /// [`OssError::code`] 的类型化形式。
+    pub fn error_code(&self) -> OssErrorCode {
+        OssErrorCode::from(self.code.as_str())
+    }
+}
+
+/// Error codes documented by OSS; codes this crate does not know are kept in `Other`.
+///
+/// See the [Alibaba Cloud documentation](https://help.aliyun.com/zh/oss/support/oss-error-center) for details.
+///
+/// OSS 文档中列出的错误码；本库未收录的错误码保存在 `Other` 中。
+///
+/// 详情参见 [阿里云文档](https://help.aliyun.com/zh/oss/support/oss-error-center)。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum OssErrorCode {
+    AccessDenied,
+    AccessForbidden,
+    BucketAlreadyExists,
+    BucketNotEmpty,
+    CallbackFailed,
+    EntityTooLarge,
+    EntityTooSmall,
+    FileAlreadyExists,
+    FileGroupTooLarge,
+    FilePartNotExist,
+    FilePartStale,
+    InternalError,
+    InvalidAccessKeyId,
+    InvalidArgument,
+    InvalidBucketName,
+    InvalidDigest,
+    InvalidEncryptionAlgorithmError,
+    InvalidObjectName,
+    InvalidObjectState,
+    InvalidPart,
+    InvalidPartOrder,
+    InvalidRange,
+    InvalidSecurityToken,
+    InvalidTargetBucketForLogging,
+    MalformedXml,
+    MethodNotAllowed,
+    MissingArgument,
+    MissingContentLength,
+    NoSuchBucket,
+    NoSuchBucketPolicy,
+    NoSuchCorsConfiguration,
+    NoSuchKey,
+    NoSuchLifecycle,
+    NoSuchUpload,
+    NoSuchVersion,
+    NoSuchWebsiteConfiguration,
+    NotImplemented,
+    ObjectNotAppendable,
+    PermanentRedirect,
+    PositionNotEqualToLength,
+    PreconditionFailed,
+    RequestTimeTooSkewed,
+    RequestTimeout,
+    SecurityTokenExpired,
+    ServiceUnavailable,
+    SignatureDoesNotMatch,
+    Throttling,
+    TooManyBuckets,
+    /// Any other error code.
+    ///
+    /// 其他错误码。
+    Other(String),
+}
+
+impl OssErrorCode {
+    /// The code as returned by OSS.
+    ///
+    /// OSS 返回的错误码字符串。
+    pub fn as_str(&self) -> &str {
+        match self {
+            OssErrorCode::AccessDenied => "AccessDenied",
+            OssErrorCode::AccessForbidden => "AccessForbidden",
+            OssErrorCode::BucketAlreadyExists => "BucketAlreadyExists",
+            OssErrorCode::BucketNotEmpty => "BucketNotEmpty",
+            OssErrorCode::CallbackFailed => "CallbackFailed",
+            OssErrorCode::EntityTooLarge => "EntityTooLarge",
+            OssErrorCode::EntityTooSmall => "EntityTooSmall",
+            OssErrorCode::FileAlreadyExists => "FileAlreadyExists",
+            OssErrorCode::FileGroupTooLarge => "FileGroupTooLarge",
+            OssErrorCode::FilePartNotExist => "FilePartNotExist",
+            OssErrorCode::FilePartStale => "FilePartStale",
+            OssErrorCode::InternalError => "InternalError",
+            OssErrorCode::InvalidAccessKeyId => "InvalidAccessKeyId",
+            OssErrorCode::InvalidArgument => "InvalidArgument",
+            OssErrorCode::InvalidBucketName => "InvalidBucketName",
+            OssErrorCode::InvalidDigest => "InvalidDigest",
+            OssErrorCode::InvalidEncryptionAlgorithmError => "InvalidEncryptionAlgorithmError",
+            OssErrorCode::InvalidObjectName => "InvalidObjectName",
+            OssErrorCode::InvalidObjectState => "InvalidObjectState",
+            OssErrorCode::InvalidPart => "InvalidPart",
+            OssErrorCode::InvalidPartOrder => "InvalidPartOrder",
+            OssErrorCode::InvalidRange => "InvalidRange",
+            OssErrorCode::InvalidSecurityToken => "InvalidSecurityToken",
+            OssErrorCode::InvalidTargetBucketForLogging => "InvalidTargetBucketForLogging",
+            OssErrorCode::MalformedXml => "MalformedXML",
+            OssErrorCode::MethodNotAllowed => "MethodNotAllowed",
+            OssErrorCode::MissingArgument => "MissingArgument",
+            OssErrorCode::MissingContentLength => "MissingContentLength",
+            OssErrorCode::NoSuchBucket => "NoSuchBucket",
+            OssErrorCode::NoSuchBucketPolicy => "NoSuchBucketPolicy",
+            OssErrorCode::NoSuchCorsConfiguration => "NoSuchCORSConfiguration",
+            OssErrorCode::NoSuchKey => "NoSuchKey",
+            OssErrorCode::NoSuchLifecycle => "NoSuchLifecycle",
+            OssErrorCode::NoSuchUpload => "NoSuchUpload",
+            OssErrorCode::NoSuchVersion => "NoSuchVersion",
+            OssErrorCode::NoSuchWebsiteConfiguration => "NoSuchWebsiteConfiguration",
+            OssErrorCode::NotImplemented => "NotImplemented",
+            OssErrorCode::ObjectNotAppendable => "ObjectNotAppendable",
+            OssErrorCode::PermanentRedirect => "PermanentRedirect",
+            OssErrorCode::PositionNotEqualToLength => "PositionNotEqualToLength",
+            OssErrorCode::PreconditionFailed => "PreconditionFailed",
+            OssErrorCode::RequestTimeTooSkewed => "RequestTimeTooSkewed",
+            OssErrorCode::RequestTimeout => "RequestTimeout",
+            OssErrorCode::SecurityTokenExpired => "SecurityTokenExpired",
+            OssErrorCode::ServiceUnavailable => "ServiceUnavailable",
+            OssErrorCode::SignatureDoesNotMatch => "SignatureDoesNotMatch",
+            OssErrorCode::Throttling => "Throttling",
+            OssErrorCode::TooManyBuckets => "TooManyBuckets",
+            OssErrorCode::Other(code) => code,
+        }
+    }
+}
+
+impl From<&str> for OssErrorCode {
+    fn from(code: &str) -> Self {
+        match code {
+            "AccessDenied" => OssErrorCode::AccessDenied,
+            "AccessForbidden" => OssErrorCode::AccessForbidden,
+            "BucketAlreadyExists" => OssErrorCode::BucketAlreadyExists,
+            "BucketNotEmpty" => OssErrorCode::BucketNotEmpty,
+            "CallbackFailed" => OssErrorCode::CallbackFailed,
+            "EntityTooLarge" => OssErrorCode::EntityTooLarge,
+            "EntityTooSmall" => OssErrorCode::EntityTooSmall,
+            "FileAlreadyExists" => OssErrorCode::FileAlreadyExists,
+            "FileGroupTooLarge" => OssErrorCode::FileGroupTooLarge,
+            "FilePartNotExist" => OssErrorCode::FilePartNotExist,
+            "FilePartStale" => OssErrorCode::FilePartStale,
+            "InternalError" => OssErrorCode::InternalError,
+            "InvalidAccessKeyId" => OssErrorCode::InvalidAccessKeyId,
+            "InvalidArgument" => OssErrorCode::InvalidArgument,
+            "InvalidBucketName" => OssErrorCode::InvalidBucketName,
+            "InvalidDigest" => OssErrorCode::InvalidDigest,
+            "InvalidEncryptionAlgorithmError" => OssErrorCode::InvalidEncryptionAlgorithmError,
+            "InvalidObjectName" => OssErrorCode::InvalidObjectName,
+            "InvalidObjectState" => OssErrorCode::InvalidObjectState,
+            "InvalidPart" => OssErrorCode::InvalidPart,
+            "InvalidPartOrder" => OssErrorCode::InvalidPartOrder,
+            "InvalidRange" => OssErrorCode::InvalidRange,
+            "InvalidSecurityToken" => OssErrorCode::InvalidSecurityToken,
+            "InvalidTargetBucketForLogging" => OssErrorCode::InvalidTargetBucketForLogging,
+            "MalformedXML" => OssErrorCode::MalformedXml,
+            "MethodNotAllowed" => OssErrorCode::MethodNotAllowed,
+            "MissingArgument" => OssErrorCode::MissingArgument,
+            "MissingContentLength" => OssErrorCode::MissingContentLength,
+            "NoSuchBucket" => OssErrorCode::NoSuchBucket,
+            "NoSuchBucketPolicy" => OssErrorCode::NoSuchBucketPolicy,
+            "NoSuchCORSConfiguration" => OssErrorCode::NoSuchCorsConfiguration,
+            "NoSuchKey" => OssErrorCode::NoSuchKey,
+            "NoSuchLifecycle" => OssErrorCode::NoSuchLifecycle,
+            "NoSuchUpload" => OssErrorCode::NoSuchUpload,
+            "NoSuchVersion" => OssErrorCode::NoSuchVersion,
+            "NoSuchWebsiteConfiguration" => OssErrorCode::NoSuchWebsiteConfiguration,
+            "NotImplemented" => OssErrorCode::NotImplemented,
+            "ObjectNotAppendable" => OssErrorCode::ObjectNotAppendable,
+            "PermanentRedirect" => OssErrorCode::PermanentRedirect,
+            "PositionNotEqualToLength" => OssErrorCode::PositionNotEqualToLength,
+            "PreconditionFailed" => OssErrorCode::PreconditionFailed,
+            "RequestTimeTooSkewed" => OssErrorCode::RequestTimeTooSkewed,
+            "RequestTimeout" => OssErrorCode::RequestTimeout,
+            "SecurityTokenExpired" => OssErrorCode::SecurityTokenExpired,
+            "ServiceUnavailable" => OssErrorCode::ServiceUnavailable,
+            "SignatureDoesNotMatch" => OssErrorCode::SignatureDoesNotMatch,
+            "Throttling" => OssErrorCode::Throttling,
+            "TooManyBuckets" => OssErrorCode::TooManyBuckets,
+            other => OssErrorCode::Other(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for OssErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Error {
+    /// HTTP status of an OSS error response.
     ///
-    /// 请求 ID。
-    #[serde(rename = "RequestId")]
-    pub request_id: String,
-    /// Extended code (EC).
+    /// OSS 错误响应的 HTTP 状态码。
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Error::OssError(status, _) | Error::OssInvalidError(status, _) => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// Typed OSS error code, when the error body could be parsed.
+    ///
+    /// 错误体解析成功时的类型化 OSS 错误码。
+    pub fn code(&self) -> Option<OssErrorCode> {
+        match self {
+            Error::OssError(_, oss_error) => Some(oss_error.error_code()),
+            _ => None,
+        }
+    }
+
+    /// Request ID reported in the OSS error body.
+    ///
+    /// OSS 错误体中返回的请求 ID。
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            Error::OssError(_, oss_error) => oss_error.request_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// The bucket, object, upload, version or configuration does not exist; bodiless `HEAD`
+    /// responses are recognised by their 404 status.
+    ///
+    /// Bucket、对象、分片上传、版本或配置不存在；无消息体的 `HEAD` 响应按 404 状态码识别。
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            Error::OssError(status, oss_error) => {
+                *status == StatusCode::NOT_FOUND || oss_error.code.starts_with("NoSuch")
+            }
+            Error::OssInvalidError(status, _) => *status == StatusCode::NOT_FOUND,
+            _ => false,
+        }
+    }
+
+    /// The request was authenticated but not authorized; bodiless responses are recognised by
+    /// their 403 status.
+    ///
+    /// 请求已通过认证但无权访问；无消息体的响应按 403 状态码识别。
+    pub fn is_access_denied(&self) -> bool {
+        match self {
+            Error::OssError(_, oss_error) => {
+                matches!(oss_error.error_code(), OssErrorCode::AccessDenied | OssErrorCode::AccessForbidden)
+            }
+            Error::OssInvalidError(status, _) => *status == StatusCode::FORBIDDEN,
+            _ => false,
+        }
+    }
+
+    /// OSS is limiting the request rate.
     ///
-    /// 扩展错误码（EC）。
-    #[serde(rename = "EC")]
-    pub ec: String,
+    /// OSS 正在限制请求速率。
+    pub fn is_throttled(&self) -> bool {
+        match self {
+            Error::OssError(status, oss_error) => {
+                *status == StatusCode::TOO_MANY_REQUESTS || oss_error.error_code() == OssErrorCode::Throttling
+            }
+            Error::OssInvalidError(status, _) => *status == StatusCode::TOO_MANY_REQUESTS,
+            _ => false,
+        }
+    }
+
+    /// Whether the default retry policy considers the error transient.
+    ///
+    /// 默认重试策略是否认为该错误是暂时性的。
+    pub fn is_retryable(&self) -> bool {
+        crate::RetryPolicy::is_retryable_error(self)
+    }
 }
 
 #[cfg(feature = "_async-base")]
 /// Convert an async OSS response into an `Error`.
 ///
 /// 将异步 OSS 响应转换为 `Error`。
-pub async fn normal_error(response: Response<Incoming>) -> Error {
+pub async fn normal_error(response: Response<ResponseBody>) -> Error {
     let status_code = response.status();
+    if let Some(error) = header_error(status_code, response.headers()) {
+        return error;
+    }
     let response_bytes = body_to_bytes(response.into_body()).await;
     match response_bytes {
-        Err(e) => Error::HyperError(e),
+        Err(e) => e,
         Ok(response_bytes) => {
             let oss_error = serde_xml_rs::from_reader(response_bytes.as_ref());
             match oss_error {
@@ -136,10 +468,13 @@ pub async fn normal_error(response: Response<Incoming>) -> Error {
 /// 将同步 OSS 响应转换为 `Error`。
 pub fn normal_error_sync(response: http::Response<Body>) -> Error {
     let status_code = response.status();
+    if let Some(error) = header_error(status_code, response.headers()) {
+        return error;
+    }
     let mut reader = response.into_body().into_reader();
     let mut buf = Vec::new();
     if let Err(e) = reader.read_to_end(&mut buf) {
-        return Error::IoError(e);
+        return e.into();
     }
     let bytes = Bytes::from(buf);
     match serde_xml_rs::from_reader(bytes.as_ref()) {
@@ -147,3 +482,60 @@ pub fn normal_error_sync(response: http::Response<Body>) -> Error {
         Err(_) => Error::OssInvalidError(status_code, bytes),
     }
 }
+
+// Responses without a body, such as HEAD, carry the error document Base64-encoded in `x-oss-err`
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+fn header_error(status_code: StatusCode, headers: &http::HeaderMap) -> Option<Error> {
+    let bytes = general_purpose::STANDARD.decode(headers.get("x-oss-err")?).ok().map(Bytes::from)?;
+    Some(match serde_xml_rs::from_reader(bytes.as_ref()) {
+        Ok(oss_error) => Error::OssError(status_code, oss_error),
+        Err(_) => Error::OssInvalidError(status_code, bytes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_and_helpers() {
+        let body = "<Error><Code>NoSuchKey</Code><Message>missing</Message><RequestId>rid</RequestId></Error>";
+        let error = Error::OssError(StatusCode::NOT_FOUND, serde_xml_rs::from_reader(body.as_bytes()).unwrap());
+        assert_eq!(error.code(), Some(OssErrorCode::NoSuchKey));
+        assert_eq!(error.request_id(), Some("rid"));
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+        assert!(error.is_not_found() && !error.is_access_denied() && !error.is_retryable());
+
+        let body = "<Error><Code>Throttling</Code><Message>slow down</Message></Error>";
+        let error =
+            Error::OssError(StatusCode::SERVICE_UNAVAILABLE, serde_xml_rs::from_reader(body.as_bytes()).unwrap());
+        assert_eq!(error.request_id(), None);
+        assert!(error.is_throttled() && error.is_retryable());
+
+        assert!(Error::OssInvalidError(StatusCode::FORBIDDEN, Bytes::new()).is_access_denied());
+        assert!(Error::OssInvalidError(StatusCode::NOT_FOUND, Bytes::new()).is_not_found());
+        assert_eq!(Error::Timeout.status(), None);
+        assert_eq!(OssErrorCode::from("NoSuchCORSConfiguration"), OssErrorCode::NoSuchCorsConfiguration);
+        assert_eq!(OssErrorCode::from("Brand-New").as_str(), "Brand-New");
+        assert_eq!(OssErrorCode::MalformedXml.to_string(), "MalformedXML");
+    }
+
+    #[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+    #[test]
+    fn test_header_error_decodes_x_oss_err() {
+        let body = "<Error><Code>NoSuchKey</Code><Message>missing</Message><RequestId>rid</RequestId></Error>";
+        let mut headers = http::HeaderMap::new();
+        assert!(header_error(StatusCode::NOT_FOUND, &headers).is_none());
+        headers.insert("x-oss-err", general_purpose::STANDARD.encode(body).parse().unwrap());
+        let error = header_error(StatusCode::NOT_FOUND, &headers).unwrap();
+        assert_eq!(error.code(), Some(OssErrorCode::NoSuchKey));
+        assert_eq!(error.request_id(), Some("rid"));
+
+        // a header that is not an error document keeps the raw bytes
+        headers.insert("x-oss-err", general_purpose::STANDARD.encode("oops").parse().unwrap());
+        let error = header_error(StatusCode::FORBIDDEN, &headers).unwrap();
+        assert!(matches!(error, Error::OssInvalidError(StatusCode::FORBIDDEN, ref bytes) if bytes.as_ref() == b"oops"));
+        headers.insert("x-oss-err", "not base64!".parse().unwrap());
+        assert!(header_error(StatusCode::FORBIDDEN, &headers).is_none());
+    }
+}
@@ -0,0 +1,115 @@
+//! Hooks around every HTTP exchange with OSS.
+//!
+//! 围绕每次与 OSS 的 HTTP 交互的钩子。
+use crate::Error;
+use http::{Request, Response};
+use std::fmt;
+use std::sync::Arc;
+
+/// Middleware hook registered on an `OssClient` and inherited by every bucket and object handle.
+///
+/// Hooks see the request and response heads only; bodies are left untouched. They run for every
+/// attempt, so a retried request is observed once per attempt.
+///
+/// - `on_request` receives the request after signing and may add headers or stash data (such as a
+///   start time) in its extensions. OSS signs every `x-oss-*` header, `Content-Type` and
+///   `Content-MD5` it receives, so adding or changing any of them here, or rewriting the URI, fails
+///   with `SignatureDoesNotMatch`; other headers, such as a trace ID, are safe to add.
+/// - `on_response` receives the response before errors are decoded, with the request head it answers.
+/// - `on_error` receives transport failures and timeouts that produced no response.
+///
+/// 注册在 `OssClient` 上、由所有 Bucket 与对象句柄继承的中间件钩子。
+///
+/// 钩子仅能看到请求与响应的头部，不涉及消息体。每次尝试都会调用，因此重试的请求会按尝试次数被观察到。
+///
+/// - `on_request` 在签名后收到请求，可添加头，或在扩展中存放数据（例如开始时间）。OSS 会对收到的所有
+///   `x-oss-*` 头、`Content-Type` 与 `Content-MD5` 校验签名，因此在此添加或修改这些头、或改写 URI
+///   会导致 `SignatureDoesNotMatch`；添加其他头（例如追踪 ID）是安全的。
+/// - `on_response` 在解析错误之前收到响应，并附带其对应的请求头部。
+/// - `on_error` 收到未产生响应的传输失败与超时。
+pub trait Interceptor: Send + Sync {
+    /// Called with the signed request before it is sent.
+    ///
+    /// 请求签名后、发送前调用。
+    fn on_request(&self, request: &mut Request<()>) {
+        let _ = request;
+    }
+    /// Called with the response before its status is checked or its error body decoded.
+    ///
+    /// 在检查响应状态或解析错误体之前调用。
+    fn on_response(&self, request: &Request<()>, response: &mut Response<()>) {
+        let _ = (request, response);
+    }
+    /// Called when the request failed without a response.
+    ///
+    /// 请求失败且没有响应时调用。
+    fn on_error(&self, request: &Request<()>, error: &Error) {
+        let _ = (request, error);
+    }
+}
+
+/// Interceptors registered on a client, run in registration order.
+///
+/// 客户端上注册的拦截器，按注册顺序执行。
+#[derive(Clone, Default)]
+pub(crate) struct Interceptors(Vec<Arc<dyn Interceptor>>);
+
+impl fmt::Debug for Interceptors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interceptors").field("len", &self.0.len()).finish()
+    }
+}
+
+impl Interceptors {
+    pub fn push(&mut self, interceptor: Arc<dyn Interceptor>) {
+        self.0.push(interceptor);
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Run `on_request` hooks and return the request with the head to pass to later hooks.
+    ///
+    /// 执行 `on_request` 钩子，返回请求以及供后续钩子使用的请求头部。
+    pub fn request<B>(&self, request: Request<B>) -> (Request<B>, Option<Request<()>>) {
+        if self.0.is_empty() {
+            return (request, None);
+        }
+        let (parts, body) = request.into_parts();
+        let mut head = Request::from_parts(parts, ());
+        for interceptor in &self.0 {
+            interceptor.on_request(&mut head);
+        }
+        let (parts, ()) = head.clone().into_parts();
+        (Request::from_parts(parts, body), Some(head))
+    }
+
+    /// Run `on_response` hooks.
+    ///
+    /// 执行 `on_response` 钩子。
+    pub fn response<B>(&self, head: Option<&Request<()>>, response: Response<B>) -> Response<B> {
+        let Some(head) = head else {
+            return response;
+        };
+        let (parts, body) = response.into_parts();
+        let mut response = Response::from_parts(parts, ());
+        for interceptor in &self.0 {
+            interceptor.on_response(head, &mut response);
+        }
+        let (parts, ()) = response.into_parts();
+        Response::from_parts(parts, body)
+    }
+
+    /// Run `on_error` hooks.
+    ///
+    /// 执行 `on_error` 钩子。
+    pub fn error(&self, head: Option<&Request<()>>, error: &Error) {
+        if let Some(head) = head {
+            for interceptor in &self.0 {
+                interceptor.on_error(head, error);
+            }
+        }
+    }
+}
@@ -23,8 +23,8 @@
 //! ## Regions and endpoints
 //!
 //! Signature V4 requires a region. `OssClient::new` takes a region and derives the default
-//! public endpoint as `oss-<region>.aliyuncs.com`. Use `set_endpoint` for internal,
-//! dualstack, or custom domains.
+//! public endpoint as `oss-<region>.aliyuncs.com`. Use `with_endpoint_kind` for internal,
+//! acceleration or dual-stack endpoints, and `set_endpoint` for custom ones.
 //!
 //! ## Quick start (async)
 //!
@@ -34,8 +34,9 @@
 //! #[tokio::main]
 //! async fn main() {
 //!     let mut client = OssClient::new("<AccessKeyId>", "<AccessKeySecret>", "cn-zhangjiakou");
-//!     // Optional: internal/dualstack/custom endpoints
-//!     // client.set_endpoint("oss-cn-zhangjiakou-internal.aliyuncs.com");
+//!     // Optional: internal/acceleration/dual-stack endpoints, or a custom one
+//!     // let client = client.with_endpoint_kind(EndpointKind::Internal);
+//!     // client.set_endpoint("oss.example.com");
 //!
 //!     let buckets = client.list_buckets().set_prefix("rust").send().await;
 //!     println!("buckets = {:?}", buckets);
@@ -65,15 +66,16 @@
 //! use time::{Duration, OffsetDateTime};
 //!
 //! #[tokio::main]
-//! async fn main() {
+//! async fn main() -> Result<(), aliyun_oss_rs::Error> {
 //!     let client = OssClient::new("<AccessKeyId>", "<AccessKeySecret>", "cn-zhangjiakou");
 //!     let url = client
 //!         .bucket("example-bucket")
 //!         .object("rust.png")
 //!         .get_object_url()
-//!         .url(OffsetDateTime::now_utc() + Duration::hours(24));
+//!         .url(OffsetDateTime::now_utc() + Duration::hours(24))?;
 //!
 //!     println!("url = {}", url);
+//!     Ok(())
 //! }
 //! ```
 //!
@@ -105,7 +107,7 @@
 //!
 //! Signature V4 需要提供 region。`OssClient::new` 会根据 region
 //! 推导默认公网 Endpoint：`oss-<region>.aliyuncs.com`。
-//! 如需内网、双栈或自定义域名，请使用 `set_endpoint` 覆盖。
+//! 如需内网、传输加速或双栈 Endpoint，请使用 `with_endpoint_kind`；自定义 Endpoint 请使用 `set_endpoint`。
 //!
 //! ## 快速开始（异步）
 //!
@@ -115,8 +117,9 @@
 //! #[tokio::main]
 //! async fn main() {
 //!     let mut client = OssClient::new("<AccessKeyId>", "<AccessKeySecret>", "cn-zhangjiakou");
-//!     // 可选：内网/双栈/自定义 Endpoint
-//!     // client.set_endpoint("oss-cn-zhangjiakou-internal.aliyuncs.com");
+//!     // 可选：内网/传输加速/双栈 Endpoint，或自定义 Endpoint
+//!     // let client = client.with_endpoint_kind(EndpointKind::Internal);
+//!     // client.set_endpoint("oss.example.com");
 //!
 //!     let buckets = client.list_buckets().set_prefix("rust").send().await;
 //!     println!("buckets = {:?}", buckets);
@@ -146,15 +149,16 @@
 //! use time::{Duration, OffsetDateTime};
 //!
 //! #[tokio::main]
-//! async fn main() {
+//! async fn main() -> Result<(), aliyun_oss_rs::Error> {
 //!     let client = OssClient::new("<AccessKeyId>", "<AccessKeySecret>", "cn-zhangjiakou");
 //!     let url = client
 //!         .bucket("example-bucket")
 //!         .object("rust.png")
 //!         .get_object_url()
-//!         .url(OffsetDateTime::now_utc() + Duration::hours(24));
+//!         .url(OffsetDateTime::now_utc() + Duration::hours(24))?;
 //!
 //!     println!("url = {}", url);
+//!     Ok(())
 //! }
 //! ```
 
@@ -163,7 +167,25 @@ pub use crate::bucket::OssBucket;
 #[doc(inline)]
 pub use crate::client::OssClient;
 #[doc(inline)]
-pub use crate::error::Error;
+pub use crate::credentials::{Credentials, CredentialsProvider};
+#[doc(inline)]
+pub use crate::error::{Error, OssError, OssErrorCode};
+#[doc(inline)]
+pub use crate::interceptor::Interceptor;
+#[doc(inline)]
+pub use crate::proxy::Proxy;
+#[doc(inline)]
+pub use crate::retry::RetryPolicy;
+#[doc(inline)]
+pub use crate::signer::Signer;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+#[doc(inline)]
+pub use crate::tls::{TlsConfig, TlsVersion};
+#[cfg(feature = "_async-base")]
+#[doc(inline)]
+pub use crate::transport::ResponseBody;
+#[doc(inline)]
+pub use crate::verifier::{Verification, Verifier};
 
 #[cfg(all(feature = "_async-base", not(any(feature = "async", feature = "async-native-tls"))))]
 compile_error!("Internal feature `_async-base` is not supported directly; enable `async` or `async-native-tls`.");
@@ -182,12 +204,34 @@ pub use crate::object::OssObject;
 
 pub mod bucket;
 pub mod client;
+mod clock;
 pub mod common;
+#[cfg(any(feature = "_async-base", feature = "_sync-base", test, feature = "test-support"))]
+mod crc64;
+pub mod credentials;
+#[cfg(any(test, feature = "test-support"))]
+pub mod emulator;
 mod error;
+mod interceptor;
 #[cfg(any(feature = "_async-base", feature = "_sync-base"))]
 pub mod object;
 mod oss;
+mod proxy;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+mod region;
 #[cfg(feature = "_async-base")]
 mod request;
 #[cfg(feature = "_sync-base")]
 pub mod request_sync;
+mod retry;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+mod signature_v1;
+mod signer;
+#[cfg(test)]
+mod test_util;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+mod tls;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+mod trace;
+mod transport;
+mod verifier;
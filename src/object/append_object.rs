@@ -1,14 +1,18 @@
 use crate::{
-    common::{Acl, CacheControl, ContentDisposition, StorageClass, invalid_metadata_key, url_encode},
+    common::{
+        Acl, AppendObjectOutput, CacheControl, ContentDisposition, ResponseMeta, StorageClass, digest_file,
+        invalid_metadata_key, md5_base64, url_encode,
+    },
+    crc64::{self, SharedCrc},
     error::{Error, normal_error},
     request::{Oss, OssRequest},
 };
-use bytes::Bytes;
 use futures_util::StreamExt;
 use http::{Method, header};
 use http_body::Frame;
-use http_body_util::{Full, StreamBody};
+use http_body_util::StreamBody;
 use std::collections::HashMap;
+use std::time::Duration;
 use tokio::{fs::File, io::BufReader};
 use tokio_util::io::ReaderStream;
 
@@ -32,22 +36,49 @@ pub struct AppendObject {
     mime: Option<String>,
     tags: HashMap<String, String>,
     callback: Option<Box<dyn Fn(u64, u64) + Send + Sync + 'static>>,
+    position: u32,
+    init_crc64: Option<u64>,
+    content_md5: bool,
 }
 
 impl AppendObject {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "AppendObject");
         req.insert_query("append", "");
         req.insert_query("position", "0");
-        AppendObject { req, mime: None, tags: HashMap::new(), callback: None }
+        AppendObject {
+            req,
+            mime: None,
+            tags: HashMap::new(),
+            callback: None,
+            position: 0,
+            init_crc64: None,
+            content_md5: false,
+        }
     }
     /// Set the starting position for the append content.
     ///
     /// 设置追加内容的起始位置。
     pub fn set_position(mut self, position: u32) -> Self {
         self.req.insert_query("position", position.to_string());
+        self.position = position;
+        self
+    }
+    /// Set the CRC-64 of the object before this append, e.g. `meta.crc64` of the previous append.
+    ///
+    /// Appends at a non-zero position are only verified when it is known.
+    ///
+    /// 设置本次追加前对象的 CRC-64，例如上一次追加返回的 `meta.crc64`。
+    ///
+    /// 仅在已知该值时才校验非零位置的追加。
+    pub fn set_init_crc64(mut self, crc64: u64) -> Self {
+        self.init_crc64 = Some(crc64);
         self
     }
+    // CRC-64 the object had before this append, when known
+    fn initial_crc64(&self) -> Option<u64> {
+        if self.position == 0 { Some(0) } else { self.init_crc64 }
+    }
     /// Set the object's MIME type.
     ///
     /// If not set, the MIME type is inferred; fallback is `application/octet-stream`.
@@ -135,6 +166,42 @@ impl AppendObject {
         self.callback = Some(callback);
         self
     }
+    /// Override the client's overall timeout for this operation.
+    ///
+    /// 为本次操作覆盖客户端的整体超时时间。
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.req.set_timeout(timeout);
+        self
+    }
+    /// Verify the upload against the CRC-64 OSS reports (on by default, see `OssClient::with_crc_check`).
+    ///
+    /// 使用 OSS 返回的 CRC-64 校验上传内容（默认开启，参见 `OssClient::with_crc_check`）。
+    pub fn set_crc_check(mut self, enable: bool) -> Self {
+        self.req.oss.crc_check = enable;
+        self
+    }
+    /// Sign the SHA-256 of the upload instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// Files are hashed in a streaming pre-pass before the upload starts.
+    ///
+    /// 对上传内容的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    ///
+    /// 文件会在上传前以流式方式预先计算哈希。
+    pub fn set_signed_payload(mut self, enable: bool) -> Self {
+        self.req.set_signed_payload(enable);
+        self
+    }
+    /// Send the MD5 of the upload as `Content-MD5` so OSS rejects content corrupted in transit.
+    ///
+    /// Files are hashed in a streaming pre-pass before the upload starts.
+    ///
+    /// 以 `Content-MD5` 发送上传内容的 MD5，使 OSS 拒绝传输中损坏的内容。
+    ///
+    /// 文件会在上传开始前以流式方式预先计算 MD5。
+    pub fn set_content_md5(mut self, enable: bool) -> Self {
+        self.content_md5 = enable;
+        self
+    }
     /// Upload a local file to OSS.
     ///
     /// If a progress callback is set, it receives real-time updates.
@@ -142,20 +209,19 @@ impl AppendObject {
     /// 上传本地文件到 OSS。
     ///
     /// 若设置回调，将获得实时进度回调。
-    pub async fn send_file(mut self, file: impl Into<String>) -> Result<Option<String>, Error> {
+    pub async fn send_file(mut self, file: impl Into<String>) -> Result<AppendObjectOutput, Error> {
+        let initial_crc64 = self.initial_crc64();
         let file = file.into();
         // Determine file MIME type
         let file_type = match self.mime {
             Some(mime) => mime,
             None => match infer::get_from_path(&file)? {
                 Some(ext) => ext.mime_type().to_owned(),
-                None => mime_guess::from_path(
-                    &self.req.oss.object.clone().map(|v| v.to_string()).unwrap_or_else(|| String::new()),
-                )
-                .first()
-                .map(|v| v.to_string())
-                .unwrap_or_else(|| "application/octet-stream".to_owned())
-                .to_string(),
+                None => mime_guess::from_path(self.req.oss.object.clone().map(|v| v.to_string()).unwrap_or_default())
+                    .first()
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "application/octet-stream".to_owned())
+                    .to_string(),
             },
         };
         self.req.insert_header(header::CONTENT_TYPE.as_str(), file_type);
@@ -176,21 +242,35 @@ impl AppendObject {
             self.req.insert_header("x-oss-tagging", tags);
         }
         // Open the file
-        let file = File::open(&file).await?;
+        let mut file = File::open(&file).await?;
         // Read the file size
         let file_size = file.metadata().await?.len();
         if file_size >= 5_368_709_120 {
             return Err(Error::InvalidFileSize);
         }
         self.req.insert_header(header::CONTENT_LENGTH.as_str(), file_size.to_string());
+        // Hash the file in one pre-pass for a signed payload and `Content-MD5`
+        let digests = digest_file(&mut file, file_size, self.req.signs_payload(), self.content_md5).await?;
+        if let Some(hash) = digests.sha256 {
+            self.req.set_payload_hash(hash);
+        }
+        if let Some(md5) = digests.md5 {
+            self.req.insert_header("Content-MD5", md5);
+        }
         // Initialize the data stream for reading file content
         let buf = BufReader::with_capacity(131072, file);
         let stream = ReaderStream::with_capacity(buf, 16384);
         // Initialize the uploaded content size
         let mut uploaded_size = 0;
+        let crc_check = self.req.oss.crc_check && initial_crc64.is_some();
+        let crc = SharedCrc::new(initial_crc64.unwrap_or_default());
+        let sink = crc.clone();
         // Create body object
         let body = StreamBody::new(stream.map(move |result| match result {
             Ok(chunk) => {
+                if crc_check {
+                    sink.update(&chunk);
+                }
                 if let Some(callback) = &self.callback {
                     let upload_size = chunk.len() as u64;
                     uploaded_size += upload_size;
@@ -200,18 +280,22 @@ impl AppendObject {
             }
             Err(err) => Err(err),
         }));
-        self.req.set_body(body);
+        self.req.set_stream_body(body);
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
+                if crc_check {
+                    crc64::verify(response.headers(), crc.value())?;
+                }
                 let next_position = response
                     .headers()
                     .get("x-oss-next-append-position")
-                    .and_then(|header| header.to_str().ok().map(|s| s.to_owned()));
-                Ok(next_position)
+                    .and_then(|header| header.to_str().ok())
+                    .and_then(|position| position.parse().ok());
+                Ok(AppendObjectOutput { next_position, meta: ResponseMeta::from_headers(response.headers()) })
             }
             _ => Err(normal_error(response).await),
         }
@@ -219,7 +303,8 @@ impl AppendObject {
     /// Upload in-memory data to OSS.
     ///
     /// 上传内存数据到 OSS。
-    pub async fn send_content(mut self, content: Vec<u8>) -> Result<Option<String>, Error> {
+    pub async fn send_content(mut self, content: Vec<u8>) -> Result<AppendObjectOutput, Error> {
+        let initial_crc64 = self.initial_crc64();
         // Read the file size
         let content_size = content.len();
         if content_size >= 5_368_709_120 {
@@ -231,13 +316,11 @@ impl AppendObject {
             Some(mime) => mime,
             None => match infer::get(&content) {
                 Some(ext) => ext.mime_type().to_string(),
-                None => mime_guess::from_path(
-                    self.req.oss.object.clone().map(|v| v.to_string()).unwrap_or_else(|| String::new().into()),
-                )
-                .first()
-                .map(|v| v.to_string())
-                .unwrap_or_else(|| "application/octet-stream".to_owned())
-                .to_string(),
+                None => mime_guess::from_path(self.req.oss.object.clone().map(|v| v.to_string()).unwrap_or_default())
+                    .first()
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "application/octet-stream".to_owned())
+                    .to_string(),
             },
         };
         self.req.insert_header(header::CONTENT_TYPE.as_str(), content_type);
@@ -258,18 +341,26 @@ impl AppendObject {
             self.req.insert_header("x-oss-tagging", tags);
         }
         // Insert body
-        self.req.set_body(Full::new(Bytes::from(content)));
+        let crc = initial_crc64.filter(|_| self.req.oss.crc_check).map(|initial| crc64::update(initial, &content));
+        if self.content_md5 {
+            self.req.insert_header("Content-MD5", md5_base64(&content));
+        }
+        self.req.set_body(content);
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
+                if let Some(crc) = crc {
+                    crc64::verify(response.headers(), crc)?;
+                }
                 let next_position = response
                     .headers()
                     .get("x-oss-next-append-position")
-                    .and_then(|header| header.to_str().ok().map(|s| s.to_owned()));
-                Ok(next_position)
+                    .and_then(|header| header.to_str().ok())
+                    .and_then(|position| position.parse().ok());
+                Ok(AppendObjectOutput { next_position, meta: ResponseMeta::from_headers(response.headers()) })
             }
             _ => Err(normal_error(response).await),
         }
@@ -1,5 +1,7 @@
 use crate::{
-    common::{Acl, StorageClass, format_gmt, invalid_metadata_key, url_encode},
+    common::{
+        Acl, CopyObjectOutput, ResponseMeta, StorageClass, body_to_bytes, format_gmt, invalid_metadata_key, url_encode,
+    },
     error::{Error, normal_error},
     request::{Oss, OssRequest},
 };
@@ -25,7 +27,7 @@ pub struct CopyObject {
 
 impl CopyObject {
     pub(super) fn new(oss: Oss, copy_source: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "CopyObject");
         req.insert_header("x-oss-copy-source", copy_source.into());
         CopyObject { req, tags: HashMap::new() }
     }
@@ -125,7 +127,7 @@ impl CopyObject {
     /// Send the copy request.
     ///
     /// 发送复制请求。
-    pub async fn send(mut self) -> Result<(), Error> {
+    pub async fn send(mut self) -> Result<CopyObjectOutput, Error> {
         // Insert tags
         let tags = self
             .tags
@@ -143,11 +145,15 @@ impl CopyObject {
             self.req.insert_header("x-oss-tagging", tags);
         }
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => {
+                let meta = ResponseMeta::from_headers(response.headers());
+                let body = body_to_bytes(response.into_body()).await?;
+                Ok(CopyObjectOutput::new(meta, &body))
+            }
             _ => Err(normal_error(response).await),
         }
     }
@@ -1,5 +1,8 @@
 use crate::{
-    common::{Acl, StorageClass, format_gmt, invalid_metadata_key, url_encode},
+    common::{
+        Acl, CopyObjectOutput, ResponseMeta, StorageClass, body_to_bytes_sync, format_gmt, invalid_metadata_key,
+        url_encode,
+    },
     error::{Error, normal_error_sync},
     request_sync::{Oss, OssRequest},
 };
@@ -25,7 +28,7 @@ pub struct CopyObjectSync {
 
 impl CopyObjectSync {
     pub(super) fn new(oss: Oss, copy_source: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "CopyObject");
         req.insert_header("x-oss-copy-source", copy_source.into());
         CopyObjectSync { req, tags: HashMap::new() }
     }
@@ -125,7 +128,7 @@ impl CopyObjectSync {
     /// Send the copy request.
     ///
     /// 发送复制请求。
-    pub fn send(mut self) -> Result<(), Error> {
+    pub fn send(mut self) -> Result<CopyObjectOutput, Error> {
         // Insert tags
         let tags = self
             .tags
@@ -147,7 +150,11 @@ impl CopyObjectSync {
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => {
+                let meta = ResponseMeta::from_headers(response.headers());
+                let body = body_to_bytes_sync(response.into_body())?;
+                Ok(CopyObjectOutput::new(meta, &body))
+            }
             _ => Err(normal_error_sync(response)),
         }
     }
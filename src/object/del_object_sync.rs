@@ -1,5 +1,6 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
@@ -25,7 +26,7 @@ pub struct DelObjectSync {
 }
 impl DelObjectSync {
     pub(super) fn new(oss: Oss) -> Self {
-        DelObjectSync { req: OssRequest::new(oss, Method::DELETE) }
+        DelObjectSync { req: OssRequest::new(oss, Method::DELETE, "DeleteObject") }
     }
     /// Send the delete request.
     ///
@@ -38,13 +39,13 @@ impl DelObjectSync {
     /// 仅在开启版本控制时响应头才有意义。
     ///
     /// `x-oss-delete-marker` 表示删除标记；`x-oss-version-id` 表示删除的版本 ID。
-    pub fn send(self) -> Result<(), Error> {
+    pub fn send(self) -> Result<ResponseMeta, Error> {
         // Build the HTTP request
         let response = self.req.send_to_oss()?;
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
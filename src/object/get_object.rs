@@ -1,18 +1,25 @@
 use crate::common::body_to_bytes;
 use crate::{
     Error,
-    common::format_gmt,
+    common::{ResponseMeta, format_gmt},
+    crc64::{self, SharedCrc},
     error::normal_error,
     request::{Oss, OssRequest},
+    transport::ResponseBody,
 };
 use bytes::Bytes;
-use futures_util::{Stream, StreamExt};
-use http::Method;
+use futures_util::{Stream, StreamExt, stream};
+use http::{Method, Response, StatusCode};
 use http_body_util::BodyExt;
 use std::pin::Pin;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use std::time::Duration;
 use time::OffsetDateTime;
 use tokio::{
-    fs::{OpenOptions, create_dir_all},
+    fs::{File, OpenOptions, create_dir_all, remove_file},
     io::{AsyncWriteExt, BufWriter},
 };
 
@@ -28,7 +35,7 @@ pub struct GetObject {
 }
 impl GetObject {
     pub(super) fn new(oss: Oss) -> Self {
-        GetObject { req: OssRequest::new(oss, Method::GET) }
+        GetObject { req: OssRequest::new(oss, Method::GET, "GetObject") }
     }
     /// Set the response byte range.
     ///
@@ -42,10 +49,25 @@ impl GetObject {
     ///
     /// 字节从 0 开始计数；500 字节文件范围为 0-499。
     pub fn set_range(mut self, start: usize, end: Option<usize>) -> Self {
-        self.req.insert_header(
-            "Range",
-            format!("bytes={}-{}", start, end.map(|v| v.to_string()).unwrap_or_else(|| String::new())),
-        );
+        self.req.insert_header("Range", format!("bytes={}-{}", start, end.map(|v| v.to_string()).unwrap_or_default()));
+        self
+    }
+    /// Override the client's overall timeout for this operation.
+    ///
+    /// 为本次操作覆盖客户端的整体超时时间。
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.req.set_timeout(timeout);
+        self
+    }
+    /// Verify the download against the CRC-64 OSS reports (on by default, see `OssClient::with_crc_check`).
+    ///
+    /// Range downloads are not verified.
+    ///
+    /// 使用 OSS 返回的 CRC-64 校验下载内容（默认开启，参见 `OssClient::with_crc_check`）。
+    ///
+    /// 范围下载不做校验。
+    pub fn set_crc_check(mut self, enable: bool) -> Self {
+        self.req.oss.crc_check = enable;
         self
     }
     /// Succeeds if the object was modified after the given time.
@@ -87,18 +109,21 @@ impl GetObject {
     /// 下载对象到本地磁盘。
     ///
     /// 不支持网络路径；请先挂载 SMB/NFS 等再使用本地路径。
-    pub async fn download_to_file(self, save_path: impl Into<String>) -> Result<(), Error> {
+    pub async fn download_to_file(self, save_path: impl Into<String>) -> Result<ResponseMeta, Error> {
         let save_path = save_path.into();
         // Validate path
         if save_path.contains("://") {
             return Err(Error::PathNotSupported);
         }
+        let crc_check = self.req.oss.crc_check;
         // Send request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
+                let meta = ResponseMeta::from_headers(response.headers());
+                let expected_crc = expected_crc(crc_check, &response);
                 // Create directory
                 let parent_dir = std::path::Path::new(&save_path).parent();
                 if let Some(dir) = parent_dir {
@@ -106,19 +131,12 @@ impl GetObject {
                 }
                 // Create file
                 let file = OpenOptions::new().write(true).create_new(true).open(&save_path).await?;
-                // Create write buffer
-                let mut writer = BufWriter::with_capacity(131072, file);
-                // Read byte stream
-                let mut response_bytes = response.into_body().into_data_stream();
-                while let Some(chunk) = response_bytes.next().await {
-                    match chunk {
-                        Ok(data) => writer.write_all(data.as_ref()).await?,
-                        Err(e) => return Err(Error::HyperError(e)),
-                    }
+                let result = write_body(file, response.into_body(), expected_crc).await;
+                if result.is_err() {
+                    // don't leave partial or corrupted content behind, so a retry can create the file again
+                    let _ = remove_file(&save_path).await;
                 }
-                writer.flush().await?;
-                writer.shutdown().await?;
-                Ok(())
+                result.map(|()| meta)
             }
             _ => Err(normal_error(response).await),
         }
@@ -131,18 +149,27 @@ impl GetObject {
     ///
     /// 大对象可能占用大量内存，请谨慎使用。
     pub async fn download(self) -> Result<Bytes, Error> {
+        let crc_check = self.req.oss.crc_check;
         // Send request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(body_to_bytes(response.into_body()).await?),
+            code if code.is_success() => {
+                let expected_crc = expected_crc(crc_check, &response);
+                let bytes = body_to_bytes(response.into_body()).await?;
+                if expected_crc.is_some() {
+                    crc64::check(expected_crc, crc64::update(0, &bytes))?;
+                }
+                Ok(bytes)
+            }
             _ => Err(normal_error(response).await),
         }
     }
     /// Download the object as a stream.
     ///
     /// Use this for large objects and process the stream yourself.
+    /// A CRC-64 mismatch is reported as the last item of the stream.
     ///
     /// ```ignore
     /// use futures_util::StreamExt;
@@ -161,6 +188,7 @@ impl GetObject {
     /// 以流式方式下载对象。
     ///
     /// 适用于大对象，调用方自行处理流。
+    /// CRC-64 不一致时以流的最后一项返回错误。
     ///
     /// ```ignore
     /// use futures_util::StreamExt;
@@ -178,19 +206,59 @@ impl GetObject {
     pub async fn download_to_stream(
         self,
     ) -> Result<Pin<Box<dyn Stream<Item = Result<bytes::Bytes, Error>> + Send>>, Error> {
+        let crc_check = self.req.oss.crc_check;
         // Send request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
-                let stream = response.into_body().into_data_stream().map(|item| match item {
-                    Ok(bytes) => Ok(bytes),
-                    Err(e) => Err(e.into()),
+                let expected_crc = expected_crc(crc_check, &response);
+                let stream = response.into_body().into_data_stream();
+                let Some(expected) = expected_crc else {
+                    return Ok(Box::pin(stream));
+                };
+                let crc = SharedCrc::new(0);
+                let sink = crc.clone();
+                let failed = Arc::new(AtomicBool::new(false));
+                let seen = failed.clone();
+                let stream = stream.inspect(move |chunk| match chunk {
+                    Ok(data) => sink.update(data),
+                    Err(_) => seen.store(true, Ordering::Relaxed),
                 });
-                Ok(Box::pin(stream))
+                // yields nothing when the checksum matches, or when the body already failed and the
+                // checksum only covers part of it
+                let tail = stream::once(async move {
+                    if failed.load(Ordering::Relaxed) { Ok(()) } else { crc64::check(Some(expected), crc.value()) }
+                })
+                .filter_map(|result| async move { result.err().map(Err) });
+                Ok(Box::pin(stream.chain(tail)))
             }
             _ => Err(normal_error(response).await),
         }
     }
 }
+
+// Write the body to the file, verifying it against the expected checksum
+async fn write_body(file: File, body: ResponseBody, expected_crc: Option<u64>) -> Result<(), Error> {
+    // Create write buffer
+    let mut writer = BufWriter::with_capacity(131072, file);
+    // Read byte stream
+    let mut response_bytes = body.into_data_stream();
+    let mut crc = 0;
+    while let Some(chunk) = response_bytes.next().await {
+        let data = chunk?;
+        if expected_crc.is_some() {
+            crc = crc64::update(crc, &data);
+        }
+        writer.write_all(data.as_ref()).await?
+    }
+    writer.flush().await?;
+    writer.shutdown().await?;
+    crc64::check(expected_crc, crc)
+}
+
+// Checksum to verify the body against; only complete (200) responses carry the whole object's CRC-64
+fn expected_crc<B>(crc_check: bool, response: &Response<B>) -> Option<u64> {
+    if crc_check && response.status() == StatusCode::OK { crc64::from_headers(response.headers()) } else { None }
+}
@@ -36,7 +36,7 @@ pub struct GetObjectAcl {
 }
 impl GetObjectAcl {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetObjectAcl");
         req.insert_query("acl", "");
         GetObjectAcl { req }
     }
@@ -45,7 +45,7 @@ impl GetObjectAcl {
     /// 发送请求并返回对象 ACL。
     pub async fn send(self) -> Result<Acl, Error> {
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
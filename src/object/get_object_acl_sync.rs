@@ -36,7 +36,7 @@ pub struct GetObjectAclSync {
 }
 impl GetObjectAclSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetObjectAcl");
         req.insert_query("acl", "");
         GetObjectAclSync { req }
     }
@@ -44,7 +44,7 @@ pub struct GetObjectMeta {
 }
 impl GetObjectMeta {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::HEAD);
+        let mut req = OssRequest::new(oss, Method::HEAD, "GetObjectMeta");
         req.insert_query("objectMeta", "");
         GetObjectMeta { req }
     }
@@ -53,7 +53,7 @@ impl GetObjectMeta {
     /// 发送请求并返回元数据。
     pub async fn send(self) -> Result<ObjectMeta, Error> {
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
@@ -62,24 +62,24 @@ impl GetObjectMeta {
                 let content_length = headers
                     .get("Content-Length")
                     .and_then(|header| header.to_str().ok().map(|s| s.to_owned()))
-                    .unwrap_or_else(|| String::new());
+                    .unwrap_or_else(String::new);
                 let e_tag = headers
                     .get("ETag")
                     .and_then(|header| header.to_str().ok().map(|s| s.trim_matches('"').to_owned()))
-                    .unwrap_or_else(|| String::new());
+                    .unwrap_or_else(String::new);
                 let last_access_time =
                     headers.get("x-oss-last-access-time").and_then(|header| header.to_str().ok().map(|s| s.to_owned()));
                 let last_modified = headers
                     .get("Last-Modified")
                     .and_then(|header| header.to_str().ok().map(|s| s.to_owned()))
-                    .unwrap_or_else(|| String::new());
+                    .unwrap_or_else(String::new);
                 Ok(ObjectMeta { content_length, e_tag, last_access_time, last_modified })
             }
             _ => {
                 let x_oss_error = response
                     .headers()
                     .get("x-oss-err")
-                    .and_then(|header| general_purpose::STANDARD.decode(header).ok().map(|v| Bytes::from(v)));
+                    .and_then(|header| general_purpose::STANDARD.decode(header).ok().map(Bytes::from));
                 match x_oss_error {
                     None => Err(Error::OssInvalidError(status_code, Bytes::new())),
                     Some(response_bytes) => {
@@ -1,15 +1,18 @@
 use crate::common::body_to_bytes_sync;
 use crate::{
     Error,
-    common::format_gmt,
+    common::{ResponseMeta, format_gmt},
+    crc64::{self, CrcReader, SharedCrc},
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
 use bytes::Bytes;
-use http::Method;
-use std::fs::{OpenOptions, create_dir_all};
+use http::{HeaderMap, Method, StatusCode};
+use std::fs::{File, OpenOptions, create_dir_all, remove_file};
 use std::io::{BufWriter, Write};
 use std::path::Path;
+use std::time::Duration;
+use ureq::Body;
 
 /// Retrieve the object's content (sync).
 ///
@@ -23,7 +26,7 @@ pub struct GetObjectSync {
 }
 impl GetObjectSync {
     pub(super) fn new(oss: Oss) -> Self {
-        GetObjectSync { req: OssRequest::new(oss, Method::GET) }
+        GetObjectSync { req: OssRequest::new(oss, Method::GET, "GetObject") }
     }
     /// Set the response byte range.
     ///
@@ -37,10 +40,25 @@ impl GetObjectSync {
     ///
     /// 字节从 0 开始计数；500 字节文件范围为 0-499。
     pub fn set_range(mut self, start: usize, end: Option<usize>) -> Self {
-        self.req.insert_header(
-            "Range",
-            format!("bytes={}-{}", start, end.map(|v| v.to_string()).unwrap_or_else(|| String::new())),
-        );
+        self.req.insert_header("Range", format!("bytes={}-{}", start, end.map(|v| v.to_string()).unwrap_or_default()));
+        self
+    }
+    /// Override the client's overall timeout for this operation.
+    ///
+    /// 为本次操作覆盖客户端的整体超时时间。
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.req.set_timeout(timeout);
+        self
+    }
+    /// Verify the download against the CRC-64 OSS reports (on by default, see `OssClient::with_crc_check`).
+    ///
+    /// Range downloads and `download_to_reader` are not verified.
+    ///
+    /// 使用 OSS 返回的 CRC-64 校验下载内容（默认开启，参见 `OssClient::with_crc_check`）。
+    ///
+    /// 范围下载与 `download_to_reader` 不做校验。
+    pub fn set_crc_check(mut self, enable: bool) -> Self {
+        self.req.oss.crc_check = enable;
         self
     }
     /// Succeeds if the object was modified after the given time.
@@ -82,24 +100,28 @@ impl GetObjectSync {
     /// 下载对象到本地磁盘。
     ///
     /// 不支持网络路径；请先挂载 SMB/NFS 等再使用本地路径。
-    pub fn download_to_file(self, save_path: impl Into<String>) -> Result<(), Error> {
+    pub fn download_to_file(self, save_path: impl Into<String>) -> Result<ResponseMeta, Error> {
         let save_path = save_path.into();
         if save_path.contains("://") {
             return Err(Error::PathNotSupported);
         }
+        let crc_check = self.req.oss.crc_check;
         let response = self.req.send_to_oss()?;
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
+                let meta = ResponseMeta::from_headers(response.headers());
+                let expected_crc = expected_crc(crc_check, status_code, response.headers());
                 if let Some(dir) = Path::new(&save_path).parent() {
                     create_dir_all(dir)?;
                 }
                 let file = OpenOptions::new().write(true).create_new(true).open(&save_path)?;
-                let mut writer = BufWriter::with_capacity(131072, file);
-                let mut reader = response.into_body().into_reader();
-                std::io::copy(&mut reader, &mut writer)?;
-                writer.flush()?;
-                Ok(())
+                let result = write_body(file, response.into_body(), expected_crc);
+                if result.is_err() {
+                    // don't leave partial or corrupted content behind, so a retry can create the file again
+                    let _ = remove_file(&save_path);
+                }
+                result.map(|()| meta)
             }
             _ => Err(normal_error_sync(response)),
         }
@@ -112,10 +134,18 @@ impl GetObjectSync {
     ///
     /// 大对象可能占用大量内存，请谨慎使用。
     pub fn download(self) -> Result<Bytes, Error> {
+        let crc_check = self.req.oss.crc_check;
         let response = self.req.send_to_oss()?;
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(body_to_bytes_sync(response.into_body())?),
+            code if code.is_success() => {
+                let expected_crc = expected_crc(crc_check, status_code, response.headers());
+                let bytes = body_to_bytes_sync(response.into_body())?;
+                if expected_crc.is_some() {
+                    crc64::check(expected_crc, crc64::update(0, &bytes))?;
+                }
+                Ok(bytes)
+            }
             _ => Err(normal_error_sync(response)),
         }
     }
@@ -135,3 +165,18 @@ impl GetObjectSync {
         }
     }
 }
+
+// Write the body to the file, verifying it against the expected checksum
+fn write_body(file: File, body: Body, expected_crc: Option<u64>) -> Result<(), Error> {
+    let mut writer = BufWriter::with_capacity(131072, file);
+    let crc = SharedCrc::new(0);
+    let mut reader = CrcReader::new(body.into_reader(), crc.clone());
+    std::io::copy(&mut reader, &mut writer)?;
+    writer.flush()?;
+    crc64::check(expected_crc, crc.value())
+}
+
+// Checksum to verify the body against; only complete (200) responses carry the whole object's CRC-64
+fn expected_crc(crc_check: bool, status: StatusCode, headers: &HeaderMap) -> Option<u64> {
+    if crc_check && status == StatusCode::OK { crc64::from_headers(headers) } else { None }
+}
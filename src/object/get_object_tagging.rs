@@ -49,7 +49,7 @@ pub struct GetObjectTagging {
 }
 impl GetObjectTagging {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetObjectTagging");
         req.insert_query("tagging", "");
         GetObjectTagging { req }
     }
@@ -58,7 +58,7 @@ impl GetObjectTagging {
     /// 发送请求并返回标签（如有）。
     pub async fn send(self) -> Result<Option<Vec<Tag>>, Error> {
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
@@ -49,7 +49,7 @@ pub struct GetObjectTaggingSync {
 }
 impl GetObjectTaggingSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetObjectTagging");
         req.insert_query("tagging", "");
         GetObjectTaggingSync { req }
     }
@@ -1,4 +1,5 @@
 use crate::{
+    Error,
     common::{CacheControl, ContentDisposition},
     request::{Oss, OssRequest},
 };
@@ -22,7 +23,7 @@ pub struct GetObjectUrl {
 }
 impl GetObjectUrl {
     pub(super) fn new(oss: Oss) -> Self {
-        GetObjectUrl { req: OssRequest::new(oss, Method::GET) }
+        GetObjectUrl { req: OssRequest::new(oss, Method::GET, "GetObject") }
     }
     /// Restrict access by source IP.
     ///
@@ -86,9 +87,13 @@ impl GetObjectUrl {
     }
     /// Generate the signed URL with the given expiration time.
     ///
+    /// With a credentials provider configured this may block while expired credentials are refreshed.
+    ///
     /// 使用给定的过期时间生成签名 URL。
-    pub fn url(mut self, expires: OffsetDateTime) -> String {
-        self.req.query_sign(expires);
-        self.req.uri()
+    ///
+    /// 配置了凭证提供者时，刷新过期凭证可能会阻塞当前线程。
+    pub fn url(mut self, expires: OffsetDateTime) -> Result<String, Error> {
+        self.req.query_sign(expires)?;
+        Ok(self.req.uri())
     }
 }
@@ -18,7 +18,7 @@ pub struct GetSymlink {
 }
 impl GetSymlink {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetSymlink");
         req.insert_query("symlink", "");
         GetSymlink { req }
     }
@@ -27,7 +27,7 @@ impl GetSymlink {
     /// 发送请求并返回符号链接目标。
     pub async fn send(self) -> Result<String, Error> {
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
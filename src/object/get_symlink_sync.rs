@@ -18,7 +18,7 @@ pub struct GetSymlinkSync {
 }
 impl GetSymlinkSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "GetSymlink");
         req.insert_query("symlink", "");
         GetSymlinkSync { req }
     }
@@ -21,7 +21,7 @@ pub struct HeadObject {
 }
 impl HeadObject {
     pub(super) fn new(oss: Oss) -> Self {
-        HeadObject { req: OssRequest::new(oss, Method::HEAD) }
+        HeadObject { req: OssRequest::new(oss, Method::HEAD, "HeadObject") }
     }
     /// Succeed only if the object is modified after the given time.
     ///
@@ -60,7 +60,7 @@ impl HeadObject {
     /// 发送请求并返回过滤后的响应头。
     pub async fn send(self) -> Result<HashMap<String, String>, Error> {
         // Build the HTTP request
-        let mut response = self.req.send_to_oss()?.await?;
+        let mut response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
@@ -90,7 +90,7 @@ impl HeadObject {
                 let x_oss_error = response
                     .headers()
                     .get("x-oss-err")
-                    .and_then(|header| general_purpose::STANDARD.decode(header).ok().map(|v| Bytes::from(v)));
+                    .and_then(|header| general_purpose::STANDARD.decode(header).ok().map(Bytes::from));
                 match x_oss_error {
                     None => Err(Error::OssInvalidError(status_code, Bytes::new())),
                     Some(response_bytes) => {
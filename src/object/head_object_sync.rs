@@ -21,7 +21,7 @@ pub struct HeadObjectSync {
 }
 impl HeadObjectSync {
     pub(super) fn new(oss: Oss) -> Self {
-        HeadObjectSync { req: OssRequest::new(oss, Method::HEAD) }
+        HeadObjectSync { req: OssRequest::new(oss, Method::HEAD, "HeadObject") }
     }
     /// Succeed only if the object is modified after the given time.
     ///
@@ -90,7 +90,7 @@ impl HeadObjectSync {
                 let x_oss_error = response
                     .headers()
                     .get("x-oss-err")
-                    .and_then(|header| general_purpose::STANDARD.decode(header).ok().map(|v| Bytes::from(v)));
+                    .and_then(|header| general_purpose::STANDARD.decode(header).ok().map(Bytes::from));
                 match x_oss_error {
                     None => Err(Error::OssInvalidError(status_code, Bytes::new())),
                     Some(response_bytes) => {
@@ -11,9 +11,9 @@ pub use self::{
     get_object_tagging::GetObjectTagging, get_object_url::GetObjectUrl, get_symlink::GetSymlink,
     head_object::HeadObject, multipart_abort_upload::AbortUpload, multipart_complete_upload::CompleteUpload,
     multipart_copyto_part::CopyToPart, multipart_init_upload::InitUpload, multipart_list_parts::ListParts,
-    multipart_upload_part::UploadPart, put_object::PutObject, put_object_acl::PutObjectAcl,
+    multipart_upload_part::UploadPart, presign_url::PresignUrl, put_object::PutObject, put_object_acl::PutObjectAcl,
     put_object_tagging::PutObjectTagging, put_symlink::PutSymlink, restore_object::RestoreObject,
-    select_object::SelectObject,
+    select_object::SelectObject, upload_file::UploadFile,
 };
 #[cfg(feature = "_sync-base")]
 pub use self::{
@@ -24,9 +24,10 @@ pub use self::{
     get_symlink_sync::GetSymlinkSync, head_object_sync::HeadObjectSync, multipart_abort_upload_sync::AbortUploadSync,
     multipart_complete_upload_sync::CompleteUploadSync, multipart_copyto_part_sync::CopyToPartSync,
     multipart_init_upload_sync::InitUploadSync, multipart_list_parts_sync::ListPartsSync,
-    multipart_upload_part_sync::UploadPartSync, put_object_acl_sync::PutObjectAclSync, put_object_sync::PutObjectSync,
+    multipart_upload_part_sync::UploadPartSync, presign_url_sync::PresignUrlSync,
+    put_object_acl_sync::PutObjectAclSync, put_object_sync::PutObjectSync,
     put_object_tagging_sync::PutObjectTaggingSync, put_symlink_sync::PutSymlinkSync,
-    restore_object_sync::RestoreObjectSync, select_object_sync::SelectObjectSync,
+    restore_object_sync::RestoreObjectSync, select_object_sync::SelectObjectSync, upload_file_sync::UploadFileSync,
 };
 
 #[cfg(feature = "_async-base")]
@@ -99,6 +100,10 @@ mod multipart_upload_part;
 mod multipart_upload_part_sync;
 mod oss_object;
 #[cfg(feature = "_async-base")]
+mod presign_url;
+#[cfg(feature = "_sync-base")]
+mod presign_url_sync;
+#[cfg(feature = "_async-base")]
 mod put_object;
 #[cfg(feature = "_async-base")]
 mod put_object_acl;
@@ -122,3 +127,9 @@ mod restore_object_sync;
 mod select_object;
 #[cfg(feature = "_sync-base")]
 mod select_object_sync;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+mod upload_checkpoint;
+#[cfg(feature = "_async-base")]
+mod upload_file;
+#[cfg(feature = "_sync-base")]
+mod upload_file_sync;
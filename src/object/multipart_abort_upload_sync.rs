@@ -1,4 +1,5 @@
 use crate::{
+    common::ResponseMeta,
     error::{Error, normal_error_sync},
     request_sync::{Oss, OssRequest},
 };
@@ -17,20 +18,20 @@ pub struct AbortUploadSync {
 
 impl AbortUploadSync {
     pub(super) fn new(oss: Oss, upload_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::DELETE);
+        let mut req = OssRequest::new(oss, Method::DELETE, "AbortMultipartUpload");
         req.insert_query("uploadId", upload_id.into());
         AbortUploadSync { req }
     }
     /// Send the abort request.
     ///
     /// 发送取消请求。
-    pub fn send(self) -> Result<(), Error> {
+    pub fn send(self) -> Result<ResponseMeta, Error> {
         // Upload file
         let response = self.req.send_to_oss()?;
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
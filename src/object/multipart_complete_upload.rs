@@ -1,10 +1,9 @@
 use crate::{
+    common::{CompleteUploadOutput, ResponseMeta, body_to_bytes},
     error::{Error, normal_error},
     request::{Oss, OssRequest},
 };
-use bytes::Bytes;
 use http::Method;
-use http_body_util::Full;
 
 /// Complete a multipart upload.
 ///
@@ -19,7 +18,7 @@ pub struct CompleteUpload<'a> {
 }
 impl<'a> CompleteUpload<'a> {
     pub(super) fn new(oss: Oss, upload_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "CompleteMultipartUpload");
         req.insert_query("uploadId", upload_id.into());
         CompleteUpload { req, parts: Vec::new() }
     }
@@ -33,7 +32,7 @@ impl<'a> CompleteUpload<'a> {
     /// Send the complete request.
     ///
     /// 发送完成请求。
-    pub async fn send(mut self) -> Result<(), Error> {
+    pub async fn send(mut self) -> Result<CompleteUploadOutput, Error> {
         // Build body
         let body = format!(
             "<CompleteMultipartUpload>{}</CompleteMultipartUpload>",
@@ -47,14 +46,18 @@ impl<'a> CompleteUpload<'a> {
                 .join("")
         );
         let body_len = body.len();
-        self.req.set_body(Full::new(Bytes::from(body)));
+        self.req.set_body(body);
         self.req.insert_header("Content-Length", body_len.to_string());
         // Upload file
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => {
+                let meta = ResponseMeta::from_headers(response.headers());
+                let body = body_to_bytes(response.into_body()).await?;
+                Ok(CompleteUploadOutput::new(meta, &body))
+            }
             _ => Err(normal_error(response).await),
         }
     }
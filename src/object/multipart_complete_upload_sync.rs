@@ -1,4 +1,5 @@
 use crate::{
+    common::{CompleteUploadOutput, ResponseMeta, body_to_bytes_sync},
     error::{Error, normal_error_sync},
     request_sync::{Oss, OssRequest},
 };
@@ -17,7 +18,7 @@ pub struct CompleteUploadSync<'a> {
 }
 impl<'a> CompleteUploadSync<'a> {
     pub(super) fn new(oss: Oss, upload_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "CompleteMultipartUpload");
         req.insert_query("uploadId", upload_id.into());
         CompleteUploadSync { req, parts: Vec::new() }
     }
@@ -31,7 +32,7 @@ impl<'a> CompleteUploadSync<'a> {
     /// Send the complete request.
     ///
     /// 发送完成请求。
-    pub fn send(mut self) -> Result<(), Error> {
+    pub fn send(mut self) -> Result<CompleteUploadOutput, Error> {
         // Build body
         let body = format!(
             "<CompleteMultipartUpload>{}</CompleteMultipartUpload>",
@@ -52,7 +53,11 @@ impl<'a> CompleteUploadSync<'a> {
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => {
+                let meta = ResponseMeta::from_headers(response.headers());
+                let body = body_to_bytes_sync(response.into_body())?;
+                Ok(CompleteUploadOutput::new(meta, &body))
+            }
             _ => Err(normal_error_sync(response)),
         }
     }
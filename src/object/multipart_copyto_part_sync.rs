@@ -1,5 +1,5 @@
 use crate::{
-    common::format_gmt,
+    common::{ResponseMeta, UploadPartOutput, format_gmt},
     error::{Error, normal_error_sync},
     request_sync::{Oss, OssRequest},
 };
@@ -23,7 +23,7 @@ impl CopyToPartSync {
         upload_id: impl Into<String>,
         copy_source: impl Into<String>,
     ) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "UploadPartCopy");
         req.insert_query("partNumber", part_number.to_string());
         req.insert_query("uploadId", upload_id.into());
         req.insert_header("x-oss-copy-source", copy_source.into());
@@ -39,7 +39,7 @@ impl CopyToPartSync {
     pub fn set_source_range(mut self, start: usize, end: Option<usize>) -> Self {
         self.req.insert_header(
             "x-oss-copy-source-range",
-            format!("bytes={}-{}", start, end.map(|v| v.to_string()).unwrap_or_else(|| String::new())),
+            format!("bytes={}-{}", start, end.map(|v| v.to_string()).unwrap_or_default()),
         );
         self
     }
@@ -82,20 +82,15 @@ impl CopyToPartSync {
     /// Send the copy request and return the ETag.
     ///
     /// 发送复制请求并返回 ETag。
-    pub fn send(self) -> Result<String, Error> {
+    pub fn send(self) -> Result<UploadPartOutput, Error> {
         // Upload file
         let response = self.req.send_to_oss()?;
         // Parse the response
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
-                let e_tag = response
-                    .headers()
-                    .get("ETag")
-                    .map(|v| String::from_utf8(v.as_bytes().to_vec()).ok())
-                    .flatten()
-                    .unwrap_or_else(|| String::new());
-                Ok(e_tag)
+                let meta = ResponseMeta::from_headers(response.headers());
+                Ok(UploadPartOutput { etag: meta.etag.clone().unwrap_or_default(), meta })
             }
             _ => Err(normal_error_sync(response)),
         }
@@ -1,6 +1,9 @@
 use crate::common::body_to_bytes;
 use crate::{
-    common::{Acl, CacheControl, ContentDisposition, StorageClass, invalid_metadata_key, url_encode},
+    common::{
+        Acl, CacheControl, ContentDisposition, InitUploadOutput, ResponseMeta, StorageClass, invalid_metadata_key,
+        url_encode,
+    },
     error::{Error, normal_error},
     request::{Oss, OssRequest},
 };
@@ -28,7 +31,7 @@ pub struct InitUpload {
 }
 impl InitUpload {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "InitiateMultipartUpload");
         req.insert_query("uploads", "");
         InitUpload { req, tags: HashMap::new() }
     }
@@ -102,7 +105,7 @@ impl InitUpload {
     /// Send the request and return the upload ID.
     ///
     /// 发送请求并返回上传 ID。
-    pub async fn send(mut self) -> Result<String, Error> {
+    pub async fn send(mut self) -> Result<InitUploadOutput, Error> {
         // Insert tags
         let tags = self
             .tags
@@ -120,16 +123,17 @@ impl InitUpload {
             self.req.insert_header("x-oss-tagging", tags);
         }
         // Upload file
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
+                let meta = ResponseMeta::from_headers(response.headers());
                 let response_bytes =
                     body_to_bytes(response.into_body()).await.map_err(|_| Error::OssInvalidResponse(None))?;
                 let result: InitiateMultipartUploadResult = serde_xml_rs::from_reader(&*response_bytes)
                     .map_err(|_| Error::OssInvalidResponse(Some(response_bytes)))?;
-                Ok(result.upload_id)
+                Ok(InitUploadOutput { upload_id: result.upload_id, meta })
             }
             _ => Err(normal_error(response).await),
         }
@@ -1,6 +1,9 @@
 use crate::common::body_to_bytes_sync;
 use crate::{
-    common::{Acl, CacheControl, ContentDisposition, StorageClass, invalid_metadata_key, url_encode},
+    common::{
+        Acl, CacheControl, ContentDisposition, InitUploadOutput, ResponseMeta, StorageClass, invalid_metadata_key,
+        url_encode,
+    },
     error::{Error, normal_error_sync},
     request_sync::{Oss, OssRequest},
 };
@@ -28,7 +31,7 @@ pub struct InitUploadSync {
 }
 impl InitUploadSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "InitiateMultipartUpload");
         req.insert_query("uploads", "");
         InitUploadSync { req, tags: HashMap::new() }
     }
@@ -102,7 +105,7 @@ impl InitUploadSync {
     /// Send the request and return the upload ID.
     ///
     /// 发送请求并返回上传 ID。
-    pub fn send(mut self) -> Result<String, Error> {
+    pub fn send(mut self) -> Result<InitUploadOutput, Error> {
         // Insert tags
         let tags = self
             .tags
@@ -125,11 +128,12 @@ impl InitUploadSync {
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
+                let meta = ResponseMeta::from_headers(response.headers());
                 let response_bytes =
                     body_to_bytes_sync(response.into_body()).map_err(|_| Error::OssInvalidResponse(None))?;
                 let result: InitiateMultipartUploadResult = serde_xml_rs::from_reader(&*response_bytes)
                     .map_err(|_| Error::OssInvalidResponse(Some(response_bytes)))?;
-                Ok(result.upload_id)
+                Ok(InitUploadOutput { upload_id: result.upload_id, meta })
             }
             _ => Err(normal_error_sync(response)),
         }
@@ -6,7 +6,6 @@ use crate::{
 };
 use http::Method;
 use serde_derive::Deserialize;
-use std::cmp;
 
 // Returned content
 #[derive(Debug, Deserialize)]
@@ -78,7 +77,7 @@ pub struct ListParts {
 
 impl ListParts {
     pub(super) fn new(oss: Oss, upload_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "ListParts");
         req.insert_query("uploadId", upload_id.into());
         ListParts { req }
     }
@@ -90,8 +89,8 @@ impl ListParts {
     ///
     /// 默认 1000，合法范围 1-1000。
     pub fn set_max_parts(mut self, max_keys: u32) -> Self {
-        let max_keys = cmp::min(1000, cmp::max(1, max_keys));
-        self.req.insert_query("max-uploads", max_keys.to_string());
+        let max_keys = max_keys.clamp(1, 1000);
+        self.req.insert_query("max-parts", max_keys.to_string());
         self
     }
     /// Specify the starting part number marker.
@@ -106,7 +105,7 @@ impl ListParts {
     /// 发送请求并返回分片列表。
     pub async fn send(self) -> Result<ListPartsResult, Error> {
         // Upload file
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
@@ -121,3 +120,26 @@ impl ListParts {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{MockResponse, MockServer};
+
+    #[test]
+    fn test_set_max_parts_sends_max_parts() {
+        let body = "<ListPartsResult><StorageClass>Standard</StorageClass><PartNumberMarker>0</PartNumberMarker>\
+                    <NextPartNumberMarker>0</NextPartNumberMarker><IsTruncated>false</IsTruncated></ListPartsResult>";
+        let server = MockServer::start(vec![MockResponse::new(200, body)]);
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_custom_domain(server.host());
+        oss.set_https(false);
+        oss.set_object("key");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let result = runtime.block_on(ListParts::new(oss, "upload-id").set_max_parts(5).send()).unwrap();
+        assert!(!result.is_truncated);
+        let target = &server.requests()[0].target;
+        assert!(target.contains("max-parts=5"), "{}", target);
+        assert!(!target.contains("max-uploads"), "{}", target);
+    }
+}
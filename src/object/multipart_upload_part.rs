@@ -1,13 +1,19 @@
 use crate::{
+    common::{ResponseMeta, UploadPartOutput, digest_file, md5_base64},
+    crc64::{self, SharedCrc},
     error::{Error, normal_error},
     request::{Oss, OssRequest},
 };
-use bytes::Bytes;
 use futures_util::StreamExt;
 use http::{Method, header};
 use http_body::Frame;
-use http_body_util::{Full, StreamBody};
-use tokio::{fs::File, io::BufReader};
+use http_body_util::StreamBody;
+use std::io::SeekFrom;
+use std::time::Duration;
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncSeekExt, BufReader},
+};
 use tokio_util::io::ReaderStream;
 
 /// Upload a part in a multipart upload.
@@ -20,13 +26,14 @@ use tokio_util::io::ReaderStream;
 pub struct UploadPart {
     req: OssRequest,
     callback: Option<Box<dyn Fn(u64, u64) + Send + Sync + 'static>>,
+    content_md5: bool,
 }
 impl UploadPart {
     pub(super) fn new(oss: Oss, part_number: u32, upload_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "UploadPart");
         req.insert_query("partNumber", part_number.to_string());
         req.insert_query("uploadId", upload_id.into());
-        UploadPart { req, callback: None }
+        UploadPart { req, callback: None, content_md5: false }
     }
     /// Set an upload progress callback, only effective for `send_file()`.
     /// ```
@@ -55,26 +62,86 @@ impl UploadPart {
         self.callback = Some(callback);
         self
     }
+    /// Override the client's overall timeout for this operation.
+    ///
+    /// 为本次操作覆盖客户端的整体超时时间。
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.req.set_timeout(timeout);
+        self
+    }
+    /// Verify the upload against the CRC-64 OSS reports (on by default, see `OssClient::with_crc_check`).
+    ///
+    /// 使用 OSS 返回的 CRC-64 校验上传内容（默认开启，参见 `OssClient::with_crc_check`）。
+    pub fn set_crc_check(mut self, enable: bool) -> Self {
+        self.req.oss.crc_check = enable;
+        self
+    }
+    /// Sign the SHA-256 of the upload instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// Files are hashed in a streaming pre-pass before the upload starts.
+    ///
+    /// 对上传内容的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    ///
+    /// 文件会在上传前以流式方式预先计算哈希。
+    pub fn set_signed_payload(mut self, enable: bool) -> Self {
+        self.req.set_signed_payload(enable);
+        self
+    }
+    /// Send the MD5 of the upload as `Content-MD5` so OSS rejects content corrupted in transit.
+    ///
+    /// Files are hashed in a streaming pre-pass before the upload starts.
+    ///
+    /// 以 `Content-MD5` 发送上传内容的 MD5，使 OSS 拒绝传输中损坏的内容。
+    ///
+    /// 文件会在上传开始前以流式方式预先计算 MD5。
+    pub fn set_content_md5(mut self, enable: bool) -> Self {
+        self.content_md5 = enable;
+        self
+    }
     /// Upload a local file as a part and return the ETag.
     ///
     /// 上传本地文件分片并返回 ETag。
-    pub async fn send_file(mut self, file: impl Into<String>) -> Result<String, Error> {
+    pub async fn send_file(self, file: impl Into<String>) -> Result<UploadPartOutput, Error> {
         let file = file.into();
         // Open the file
         let file = File::open(&file).await?;
         // Read the file size
         let file_size = file.metadata().await?.len();
-        if file_size >= 5_368_709_120 || file_size < 102_400 {
+        if !(102_400..5_368_709_120).contains(&file_size) {
             return Err(Error::InvalidFileSize);
         }
+        self.send_reader(file, file_size).await
+    }
+    // Stream `len` bytes starting at `offset`; the resumable uploader plans the sizes, so the last part may be small
+    pub(crate) async fn send_file_range(self, file: &str, offset: u64, len: u64) -> Result<UploadPartOutput, Error> {
+        let mut file = File::open(file).await?;
+        file.seek(SeekFrom::Start(offset)).await?;
+        self.send_reader(file, len).await
+    }
+    async fn send_reader(mut self, mut file: File, file_size: u64) -> Result<UploadPartOutput, Error> {
+        self.req.insert_header(header::CONTENT_LENGTH.as_str(), file_size.to_string());
+        // Hash the file in one pre-pass for a signed payload and `Content-MD5`
+        let digests = digest_file(&mut file, file_size, self.req.signs_payload(), self.content_md5).await?;
+        if let Some(hash) = digests.sha256 {
+            self.req.set_payload_hash(hash);
+        }
+        if let Some(md5) = digests.md5 {
+            self.req.insert_header("Content-MD5", md5);
+        }
         // Initialize the data stream for reading file content
-        let buf = BufReader::with_capacity(131072, file);
+        let buf = BufReader::with_capacity(131072, file.take(file_size));
         let stream = ReaderStream::with_capacity(buf, 16384);
         // Initialize the uploaded content size
         let mut uploaded_size = 0;
+        let crc_check = self.req.oss.crc_check;
+        let crc = SharedCrc::new(0);
+        let sink = crc.clone();
         // Initialize upload request
         let body = StreamBody::new(stream.map(move |result| match result {
             Ok(chunk) => {
+                if crc_check {
+                    sink.update(&chunk);
+                }
                 if let Some(callback) = &self.callback {
                     let upload_size = chunk.len() as u64;
                     uploaded_size += upload_size;
@@ -84,20 +151,18 @@ impl UploadPart {
             }
             Err(err) => Err(err),
         }));
-        self.req.set_body(body);
+        self.req.set_stream_body(body);
         // Upload file
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
-                let e_tag = response
-                    .headers()
-                    .get("ETag")
-                    .map(|v| String::from_utf8(v.as_bytes().to_vec()).ok())
-                    .flatten()
-                    .unwrap_or_else(|| String::new());
-                Ok(e_tag)
+                if crc_check {
+                    crc64::verify(response.headers(), crc.value())?;
+                }
+                let meta = ResponseMeta::from_headers(response.headers());
+                Ok(UploadPartOutput { etag: meta.etag.clone().unwrap_or_default(), meta })
             }
             _ => Err(normal_error(response).await),
         }
@@ -105,28 +170,30 @@ impl UploadPart {
     /// Upload in-memory data as a part and return the ETag.
     ///
     /// 上传内存分片并返回 ETag。
-    pub async fn send_content(mut self, content: Vec<u8>) -> Result<String, Error> {
+    pub async fn send_content(mut self, content: Vec<u8>) -> Result<UploadPartOutput, Error> {
         // Read size
         let content_size = content.len() as u64;
         if content_size >= 5_000_000_000 {
             return Err(Error::InvalidFileSize);
         }
         self.req.insert_header(header::CONTENT_LENGTH.as_str(), content_size.to_string());
+        let crc = self.req.oss.crc_check.then(|| crc64::update(0, &content));
+        if self.content_md5 {
+            self.req.insert_header("Content-MD5", md5_base64(&content));
+        }
         // Insert body
-        self.req.set_body(Full::new(Bytes::from(content)));
+        self.req.set_body(content);
         // Upload file
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
-                let e_tag = response
-                    .headers()
-                    .get("ETag")
-                    .map(|v| String::from_utf8(v.as_bytes().to_vec()).ok())
-                    .flatten()
-                    .unwrap_or_else(|| String::new());
-                Ok(e_tag)
+                if let Some(crc) = crc {
+                    crc64::verify(response.headers(), crc)?;
+                }
+                let meta = ResponseMeta::from_headers(response.headers());
+                Ok(UploadPartOutput { etag: meta.etag.clone().unwrap_or_default(), meta })
             }
             _ => Err(normal_error(response).await),
         }
@@ -1,10 +1,13 @@
 use crate::{
+    common::{ResponseMeta, UploadPartOutput, digest_file_sync, md5_base64},
+    crc64::{self, CrcReader, SharedCrc},
     error::{Error, normal_error_sync},
     request_sync::{Oss, OssRequest},
 };
 use http::{Method, header};
 use std::fs::File;
-use std::io::{BufReader, Read};
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::time::Duration;
 use ureq::SendBody;
 
 /// Upload a part in a multipart upload (sync).
@@ -17,6 +20,7 @@ use ureq::SendBody;
 pub struct UploadPartSync {
     req: OssRequest,
     callback: Option<Box<dyn Fn(u64, u64) + Send + Sync + 'static>>,
+    content_md5: bool,
 }
 
 struct ProgressReader<R> {
@@ -47,10 +51,10 @@ impl<R: Read> Read for ProgressReader<R> {
 
 impl UploadPartSync {
     pub(super) fn new(oss: Oss, part_number: u32, upload_id: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "UploadPart");
         req.insert_query("partNumber", part_number.to_string());
         req.insert_query("uploadId", upload_id.into());
-        UploadPartSync { req, callback: None }
+        UploadPartSync { req, callback: None, content_md5: false }
     }
     /// Set an upload progress callback, only effective for `send_file()`.
     /// ```
@@ -79,34 +83,88 @@ impl UploadPartSync {
         self.callback = Some(callback);
         self
     }
+    /// Override the client's overall timeout for this operation.
+    ///
+    /// 为本次操作覆盖客户端的整体超时时间。
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.req.set_timeout(timeout);
+        self
+    }
+    /// Verify the upload against the CRC-64 OSS reports (on by default, see `OssClient::with_crc_check`).
+    ///
+    /// 使用 OSS 返回的 CRC-64 校验上传内容（默认开启，参见 `OssClient::with_crc_check`）。
+    pub fn set_crc_check(mut self, enable: bool) -> Self {
+        self.req.oss.crc_check = enable;
+        self
+    }
+    /// Sign the SHA-256 of the upload instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// Files are hashed in a streaming pre-pass before the upload starts.
+    ///
+    /// 对上传内容的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    ///
+    /// 文件会在上传前以流式方式预先计算哈希。
+    pub fn set_signed_payload(mut self, enable: bool) -> Self {
+        self.req.set_signed_payload(enable);
+        self
+    }
+    /// Send the MD5 of the upload as `Content-MD5` so OSS rejects content corrupted in transit.
+    ///
+    /// Files are hashed in a streaming pre-pass before the upload starts.
+    ///
+    /// 以 `Content-MD5` 发送上传内容的 MD5，使 OSS 拒绝传输中损坏的内容。
+    ///
+    /// 文件会在上传开始前以流式方式预先计算 MD5。
+    pub fn set_content_md5(mut self, enable: bool) -> Self {
+        self.content_md5 = enable;
+        self
+    }
     /// Upload a local file as a part and return the ETag.
     ///
     /// 上传本地文件分片并返回 ETag。
-    pub fn send_file(mut self, file: impl Into<String>) -> Result<String, Error> {
+    pub fn send_file(self, file: impl Into<String>) -> Result<UploadPartOutput, Error> {
         let file = file.into();
         let file = File::open(&file)?;
         let file_size = file.metadata()?.len();
-        if file_size >= 5_368_709_120 || file_size < 102_400 {
+        if !(102_400..5_368_709_120).contains(&file_size) {
             return Err(Error::InvalidFileSize);
         }
+        self.send_reader(file, file_size)
+    }
+    // Stream `len` bytes starting at `offset`, see `UploadPart::send_file_range`
+    pub(crate) fn send_file_range(self, file: &str, offset: u64, len: u64) -> Result<UploadPartOutput, Error> {
+        let mut file = File::open(file)?;
+        file.seek(SeekFrom::Start(offset))?;
+        self.send_reader(file, len)
+    }
+    fn send_reader(mut self, mut file: File, file_size: u64) -> Result<UploadPartOutput, Error> {
         self.req.insert_header(header::CONTENT_LENGTH.as_str(), file_size.to_string());
-        let reader = BufReader::with_capacity(131072, file);
+        // Hash the file in one pre-pass for a signed payload and `Content-MD5`
+        let digests = digest_file_sync(&mut file, file_size, self.req.signs_payload(), self.content_md5)?;
+        if let Some(hash) = digests.sha256 {
+            self.req.set_payload_hash(hash);
+        }
+        if let Some(md5) = digests.md5 {
+            self.req.insert_header("Content-MD5", md5);
+        }
+        let reader = BufReader::with_capacity(131072, file.take(file_size));
         let reader: Box<dyn Read> = match self.callback {
             Some(callback) => Box::new(ProgressReader::new(reader, file_size, Some(callback))),
             None => Box::new(reader),
         };
+        let crc_check = self.req.oss.crc_check;
+        let crc = SharedCrc::new(0);
+        let reader: Box<dyn Read> = if crc_check { Box::new(CrcReader::new(reader, crc.clone())) } else { reader };
         let body = SendBody::from_owned_reader(reader);
         let response = self.req.send_to_oss_with_body(body)?;
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
-                let e_tag = response
-                    .headers()
-                    .get("ETag")
-                    .map(|v| String::from_utf8(v.as_bytes().to_vec()).ok())
-                    .flatten()
-                    .unwrap_or_else(|| String::new());
-                Ok(e_tag)
+                if crc_check {
+                    crc64::verify(response.headers(), crc.value())?;
+                }
+                let meta = ResponseMeta::from_headers(response.headers());
+                Ok(UploadPartOutput { etag: meta.etag.clone().unwrap_or_default(), meta })
             }
             _ => Err(normal_error_sync(response)),
         }
@@ -114,24 +172,26 @@ impl UploadPartSync {
     /// Upload in-memory data as a part and return the ETag.
     ///
     /// 上传内存分片并返回 ETag。
-    pub fn send_content(mut self, content: Vec<u8>) -> Result<String, Error> {
+    pub fn send_content(mut self, content: Vec<u8>) -> Result<UploadPartOutput, Error> {
         let content_size = content.len() as u64;
         if content_size >= 5_000_000_000 {
             return Err(Error::InvalidFileSize);
         }
         self.req.insert_header(header::CONTENT_LENGTH.as_str(), content_size.to_string());
+        let crc = self.req.oss.crc_check.then(|| crc64::update(0, &content));
+        if self.content_md5 {
+            self.req.insert_header("Content-MD5", md5_base64(&content));
+        }
         self.req.set_body(content);
         let response = self.req.send_to_oss()?;
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
-                let e_tag = response
-                    .headers()
-                    .get("ETag")
-                    .map(|v| String::from_utf8(v.as_bytes().to_vec()).ok())
-                    .flatten()
-                    .unwrap_or_else(|| String::new());
-                Ok(e_tag)
+                if let Some(crc) = crc {
+                    crc64::verify(response.headers(), crc)?;
+                }
+                let meta = ResponseMeta::from_headers(response.headers());
+                Ok(UploadPartOutput { etag: meta.etag.clone().unwrap_or_default(), meta })
             }
             _ => Err(normal_error_sync(response)),
         }
@@ -1,17 +1,24 @@
 #[cfg(feature = "_async-base")]
 use super::{
     AbortUpload, AppendObject, CompleteUpload, CopyObject, CopyToPart, DelObjectTagging, GetObject, GetObjectAcl,
-    GetObjectMeta, GetObjectTagging, GetObjectUrl, GetSymlink, HeadObject, InitUpload, ListParts, PutObject,
-    PutObjectAcl, PutObjectTagging, PutSymlink, RestoreObject, SelectObject, UploadPart, del_object::DelObject,
+    GetObjectMeta, GetObjectTagging, GetObjectUrl, GetSymlink, HeadObject, InitUpload, ListParts, PresignUrl,
+    PutObject, PutObjectAcl, PutObjectTagging, PutSymlink, RestoreObject, SelectObject, UploadFile, UploadPart,
+    del_object::DelObject,
 };
 #[cfg(feature = "_sync-base")]
 use super::{
     AbortUploadSync, AppendObjectSync, CompleteUploadSync, CopyObjectSync, CopyToPartSync, DelObjectSync,
     DelObjectTaggingSync, GetObjectAclSync, GetObjectMetaSync, GetObjectSync, GetObjectTaggingSync, GetObjectUrlSync,
-    GetSymlinkSync, HeadObjectSync, InitUploadSync, ListPartsSync, PutObjectAclSync, PutObjectSync,
-    PutObjectTaggingSync, PutSymlinkSync, RestoreObjectSync, SelectObjectSync, UploadPartSync,
+    GetSymlinkSync, HeadObjectSync, InitUploadSync, ListPartsSync, PresignUrlSync, PutObjectAclSync, PutObjectSync,
+    PutObjectTaggingSync, PutSymlinkSync, RestoreObjectSync, SelectObjectSync, UploadFileSync, UploadPartSync,
 };
+#[cfg(feature = "_async-base")]
+use crate::client::RawRequest;
+#[cfg(feature = "_sync-base")]
+use crate::client::RawRequestSync;
 use crate::{common::Acl, oss::Oss};
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use http::Method;
 
 /// Object handle exposing object-level APIs such as upload, download, and metadata.
 ///
@@ -95,6 +102,34 @@ impl OssObject {
     pub fn get_object_url_sync(&self) -> GetObjectUrlSync {
         GetObjectUrlSync::new(self.oss.clone())
     }
+    /// Pre-sign a request with any method, e.g. PUT, HEAD, DELETE or UploadPart.
+    ///
+    /// 为任意方法的请求生成预签名 URL，例如 PUT、HEAD、DELETE 或 UploadPart。
+    #[cfg(feature = "_async-base")]
+    pub fn presign(&self, method: Method) -> PresignUrl {
+        PresignUrl::new(self.oss.clone(), method)
+    }
+    /// Pre-sign a request with any method, e.g. PUT, HEAD, DELETE or UploadPart (sync).
+    ///
+    /// 为任意方法的请求生成预签名 URL，例如 PUT、HEAD、DELETE 或 UploadPart（同步）。
+    #[cfg(feature = "_sync-base")]
+    pub fn presign_sync(&self, method: Method) -> PresignUrlSync {
+        PresignUrlSync::new(self.oss.clone(), method)
+    }
+    /// Build a signed request to an OSS API not wrapped by this crate, addressed to this object.
+    ///
+    /// 构建发送至任意 OSS API 的签名请求（目标为当前对象），用于本库尚未封装的接口。
+    #[cfg(feature = "_async-base")]
+    pub fn raw_request(&self, method: Method) -> RawRequest {
+        RawRequest::new(self.oss.clone(), method)
+    }
+    /// Build a signed request to an OSS API not wrapped by this crate, addressed to this object (sync).
+    ///
+    /// 构建发送至任意 OSS API 的签名请求（目标为当前对象），用于本库尚未封装的接口（同步）。
+    #[cfg(feature = "_sync-base")]
+    pub fn raw_request_sync(&self, method: Method) -> RawRequestSync {
+        RawRequestSync::new(self.oss.clone(), method)
+    }
     /// Retrieve object tags.
     ///
     /// 获取对象标签。
@@ -371,4 +406,18 @@ impl OssObject {
     pub fn multipart_list_parts_sync(&self, upload_id: impl Into<String>) -> ListPartsSync {
         ListPartsSync::new(self.oss.clone(), upload_id)
     }
+    /// Upload a local file with concurrent multipart upload, resumable through a checkpoint file.
+    ///
+    /// 以并发分片上传方式上传本地文件，可通过检查点文件断点续传。
+    #[cfg(feature = "_async-base")]
+    pub fn upload_file(&self, file: impl Into<String>) -> UploadFile {
+        UploadFile::new(self.oss.clone(), file)
+    }
+    /// Upload a local file with concurrent multipart upload, resumable through a checkpoint file (sync).
+    ///
+    /// 以并发分片上传方式上传本地文件，可通过检查点文件断点续传（同步）。
+    #[cfg(feature = "_sync-base")]
+    pub fn upload_file_sync(&self, file: impl Into<String>) -> UploadFileSync {
+        UploadFileSync::new(self.oss.clone(), file)
+    }
 }
@@ -0,0 +1,84 @@
+use crate::{
+    Error,
+    common::invalid_metadata_key,
+    request_sync::{Oss, OssRequest},
+};
+use http::{Method, header};
+use time::OffsetDateTime;
+
+/// Build a pre-signed URL for any method, with headers and query parameters bound into the signature.
+///
+/// Created by `OssObject::presign_sync` for object operations (PUT, HEAD, DELETE, UploadPart,
+/// CompleteMultipartUpload, ...) and by `OssBucket::presign_sync` for bucket-level sub-resources. Every
+/// header set here must be sent unchanged by whoever uses the URL.
+///
+/// See the [Alibaba Cloud documentation](https://help.aliyun.com/document_detail/31952.html) for details.
+///
+/// 为任意方法生成预签名 URL，设置的请求头与查询参数会参与签名。
+///
+/// 由 `OssObject::presign_sync` 创建用于对象操作（PUT、HEAD、DELETE、UploadPart、CompleteMultipartUpload 等），
+/// 由 `OssBucket::presign_sync` 创建用于 Bucket 级子资源。使用该 URL 的一方必须原样发送此处设置的所有请求头。
+///
+/// 详情见 [阿里云文档](https://help.aliyun.com/document_detail/31952.html)。
+pub struct PresignUrlSync {
+    req: OssRequest,
+}
+impl PresignUrlSync {
+    pub(crate) fn new(oss: Oss, method: Method) -> Self {
+        PresignUrlSync { req: OssRequest::new(oss, method, "Presign") }
+    }
+    /// Bind the `Content-Type` the request must be sent with.
+    ///
+    /// 绑定请求必须携带的 `Content-Type`。
+    pub fn set_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.req.insert_header(header::CONTENT_TYPE.as_str(), content_type);
+        self
+    }
+    /// Bind the `Content-MD5` the request must be sent with.
+    ///
+    /// 绑定请求必须携带的 `Content-MD5`。
+    pub fn set_content_md5(mut self, content_md5: impl Into<String>) -> Self {
+        self.req.insert_header("Content-MD5", content_md5);
+        self
+    }
+    /// Bind custom metadata, sent as `x-oss-meta-<key>`.
+    ///
+    /// Keys must contain only letters, numbers, and hyphens; invalid keys are ignored.
+    ///
+    /// 绑定自定义元数据，以 `x-oss-meta-<key>` 发送。
+    ///
+    /// Key 仅支持字母、数字和连字符；无效 Key 会被忽略。
+    pub fn set_meta(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        let key = key.into();
+        if !invalid_metadata_key(&key) {
+            self.req.insert_header(format!("x-oss-meta-{}", key), value);
+        }
+        self
+    }
+    /// Bind any other request header, such as `x-oss-storage-class` or `x-oss-forbid-overwrite`.
+    ///
+    /// 绑定其他请求头，例如 `x-oss-storage-class` 或 `x-oss-forbid-overwrite`。
+    pub fn insert_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.req.insert_header(key, value);
+        self
+    }
+    /// Add a query parameter, such as `uploadId` and `partNumber` for UploadPart, or an empty-valued
+    /// sub-resource such as `acl`.
+    ///
+    /// 添加查询参数，例如 UploadPart 的 `uploadId` 与 `partNumber`，或值为空的子资源如 `acl`。
+    pub fn insert_query(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.req.insert_query(key, value);
+        self
+    }
+    /// Generate the signed URL with the given expiration time.
+    ///
+    /// With a credentials provider configured this may block while expired credentials are refreshed.
+    ///
+    /// 使用给定的过期时间生成签名 URL。
+    ///
+    /// 配置了凭证提供者时，刷新过期凭证可能会阻塞当前线程。
+    pub fn url(mut self, expires: OffsetDateTime) -> Result<String, Error> {
+        self.req.query_sign(expires)?;
+        Ok(self.req.uri())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     Error,
-    common::Acl,
+    common::{Acl, ResponseMeta},
     error::normal_error,
     request::{Oss, OssRequest},
 };
@@ -18,7 +18,7 @@ pub struct PutObjectAcl {
 }
 impl PutObjectAcl {
     pub(super) fn new(oss: Oss, acl: Acl) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "PutObjectAcl");
         req.insert_query("acl", "");
         req.insert_header("x-oss-object-acl", acl.to_string());
         PutObjectAcl { req }
@@ -26,13 +26,13 @@ impl PutObjectAcl {
     /// Send the request.
     ///
     /// 发送请求。
-    pub async fn send(self) -> Result<(), Error> {
+    pub async fn send(self) -> Result<ResponseMeta, Error> {
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error(response).await),
         }
     }
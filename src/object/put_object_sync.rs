@@ -1,5 +1,9 @@
 use crate::{
-    common::{Acl, CacheControl, ContentDisposition, StorageClass, invalid_metadata_key, url_encode},
+    common::{
+        Acl, CacheControl, ContentDisposition, ResponseMeta, StorageClass, digest_file_sync, invalid_metadata_key,
+        md5_base64, url_encode,
+    },
+    crc64::{self, CrcReader, SharedCrc},
     error::{Error, normal_error_sync},
     request_sync::{Oss, OssRequest},
 };
@@ -7,6 +11,7 @@ use http::{Method, header};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufReader, Read};
+use std::time::Duration;
 use ureq::SendBody;
 
 /// Upload an object to OSS (sync).
@@ -29,6 +34,7 @@ pub struct PutObjectSync {
     mime: Option<String>,
     tags: HashMap<String, String>,
     callback: Option<Box<dyn Fn(u64, u64) + Send + Sync + 'static>>,
+    content_md5: bool,
 }
 
 struct ProgressReader<R> {
@@ -59,7 +65,13 @@ impl<R: Read> Read for ProgressReader<R> {
 
 impl PutObjectSync {
     pub(super) fn new(oss: Oss) -> Self {
-        PutObjectSync { req: OssRequest::new(oss, Method::PUT), mime: None, tags: HashMap::new(), callback: None }
+        PutObjectSync {
+            req: OssRequest::new(oss, Method::PUT, "PutObject"),
+            mime: None,
+            tags: HashMap::new(),
+            callback: None,
+            content_md5: false,
+        }
     }
     /// Set the object's MIME type.
     ///
@@ -155,22 +167,56 @@ impl PutObjectSync {
         self.callback = Some(callback);
         self
     }
+    /// Override the client's overall timeout for this operation.
+    ///
+    /// 为本次操作覆盖客户端的整体超时时间。
+    pub fn set_timeout(mut self, timeout: Duration) -> Self {
+        self.req.set_timeout(timeout);
+        self
+    }
+    /// Verify the upload against the CRC-64 OSS reports (on by default, see `OssClient::with_crc_check`).
+    ///
+    /// 使用 OSS 返回的 CRC-64 校验上传内容（默认开启，参见 `OssClient::with_crc_check`）。
+    pub fn set_crc_check(mut self, enable: bool) -> Self {
+        self.req.oss.crc_check = enable;
+        self
+    }
+    /// Sign the SHA-256 of the upload instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// Files are hashed in a streaming pre-pass before the upload starts.
+    ///
+    /// 对上传内容的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    ///
+    /// 文件会在上传前以流式方式预先计算哈希。
+    pub fn set_signed_payload(mut self, enable: bool) -> Self {
+        self.req.set_signed_payload(enable);
+        self
+    }
+    /// Send the MD5 of the upload as `Content-MD5` so OSS rejects content corrupted in transit.
+    ///
+    /// Files are hashed in a streaming pre-pass before the upload starts.
+    ///
+    /// 以 `Content-MD5` 发送上传内容的 MD5，使 OSS 拒绝传输中损坏的内容。
+    ///
+    /// 文件会在上传开始前以流式方式预先计算 MD5。
+    pub fn set_content_md5(mut self, enable: bool) -> Self {
+        self.content_md5 = enable;
+        self
+    }
     /// Upload a file from disk to OSS.
     ///
     /// 从磁盘上传文件到 OSS。
-    pub fn send_file(mut self, file: impl Into<String>) -> Result<(), Error> {
+    pub fn send_file(mut self, file: impl Into<String>) -> Result<ResponseMeta, Error> {
         let file = file.into();
         let file_type = match self.mime {
             Some(mime) => mime,
             None => match infer::get_from_path(&file)? {
                 Some(ext) => ext.mime_type().to_owned(),
-                None => mime_guess::from_path(
-                    &self.req.oss.object.clone().map(|v| v.to_string()).unwrap_or_else(|| String::new()),
-                )
-                .first()
-                .map(|v| v.to_string())
-                .unwrap_or_else(|| "application/octet-stream".to_owned())
-                .to_string(),
+                None => mime_guess::from_path(self.req.oss.object.clone().map(|v| v.to_string()).unwrap_or_default())
+                    .first()
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "application/octet-stream".to_owned())
+                    .to_string(),
             },
         };
         self.req.insert_header(header::CONTENT_TYPE.as_str(), file_type);
@@ -189,40 +235,54 @@ impl PutObjectSync {
         if !tags.is_empty() {
             self.req.insert_header("x-oss-tagging", tags);
         }
-        let file = File::open(&file)?;
+        let mut file = File::open(&file)?;
         let file_size = file.metadata()?.len();
         if file_size >= 5_368_709_120 {
             return Err(Error::InvalidFileSize);
         }
         self.req.insert_header(header::CONTENT_LENGTH.as_str(), file_size.to_string());
+        // Hash the file in one pre-pass for a signed payload and `Content-MD5`
+        let digests = digest_file_sync(&mut file, file_size, self.req.signs_payload(), self.content_md5)?;
+        if let Some(hash) = digests.sha256 {
+            self.req.set_payload_hash(hash);
+        }
+        if let Some(md5) = digests.md5 {
+            self.req.insert_header("Content-MD5", md5);
+        }
         let reader = BufReader::with_capacity(131072, file);
         let reader: Box<dyn Read> = match self.callback {
             Some(callback) => Box::new(ProgressReader::new(reader, file_size, Some(callback))),
             None => Box::new(reader),
         };
+        let crc_check = self.req.oss.crc_check;
+        let crc = SharedCrc::new(0);
+        let reader: Box<dyn Read> = if crc_check { Box::new(CrcReader::new(reader, crc.clone())) } else { reader };
         let body = SendBody::from_owned_reader(reader);
         let response = self.req.send_to_oss_with_body(body)?;
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => {
+                if crc_check {
+                    crc64::verify(response.headers(), crc.value())?;
+                }
+                Ok(ResponseMeta::from_headers(response.headers()))
+            }
             _ => Err(normal_error_sync(response)),
         }
     }
     /// Upload in-memory data to OSS.
     ///
     /// 上传内存数据到 OSS。
-    pub fn send_content(mut self, content: Vec<u8>) -> Result<(), Error> {
+    pub fn send_content(mut self, content: Vec<u8>) -> Result<ResponseMeta, Error> {
         let content_type = match self.mime {
             Some(mime) => mime,
             None => match infer::get(&content) {
                 Some(ext) => ext.mime_type().to_string(),
-                None => mime_guess::from_path(
-                    self.req.oss.object.clone().map(|v| v.to_string()).unwrap_or_else(|| String::new().into()),
-                )
-                .first()
-                .map(|v| v.to_string())
-                .unwrap_or_else(|| "application/octet-stream".to_owned())
-                .to_string(),
+                None => mime_guess::from_path(self.req.oss.object.clone().map(|v| v.to_string()).unwrap_or_default())
+                    .first()
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "application/octet-stream".to_owned())
+                    .to_string(),
             },
         };
         self.req.insert_header(header::CONTENT_TYPE.as_str(), content_type);
@@ -246,11 +306,20 @@ impl PutObjectSync {
             return Err(Error::InvalidFileSize);
         }
         self.req.insert_header(header::CONTENT_LENGTH.as_str(), content_size.to_string());
+        let crc = self.req.oss.crc_check.then(|| crc64::update(0, &content));
+        if self.content_md5 {
+            self.req.insert_header("Content-MD5", md5_base64(&content));
+        }
         self.req.set_body(content);
         let response = self.req.send_to_oss()?;
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => {
+                if let Some(crc) = crc {
+                    crc64::verify(response.headers(), crc)?;
+                }
+                Ok(ResponseMeta::from_headers(response.headers()))
+            }
             _ => Err(normal_error_sync(response)),
         }
     }
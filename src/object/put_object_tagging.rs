@@ -1,11 +1,10 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error,
     request::{Oss, OssRequest},
 };
-use bytes::Bytes;
 use http::Method;
-use http_body_util::Full;
 
 /// Set tags for an object.
 ///
@@ -20,7 +19,7 @@ pub struct PutObjectTagging {
 }
 impl PutObjectTagging {
     pub(super) fn new(oss: Oss, tags: Vec<(impl Into<String>, impl Into<String>)>) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "PutObjectTagging");
         req.insert_query("tagging", "");
         PutObjectTagging { req, tags: tags.into_iter().map(|(key, value)| (key.into(), value.into())).collect() }
     }
@@ -34,7 +33,7 @@ impl PutObjectTagging {
     /// Send the request.
     ///
     /// 发送请求。
-    pub async fn send(mut self) -> Result<(), Error> {
+    pub async fn send(mut self) -> Result<ResponseMeta, Error> {
         // Build body
         let tag_str = self
             .tags
@@ -50,13 +49,13 @@ impl PutObjectTagging {
             .join("");
         let body = format!("<Tagging><TagSet>{}</TagSet></Tagging>", tag_str);
         self.req.insert_header("Content-Length", body.len().to_string());
-        self.req.set_body(Full::new(Bytes::from(body)));
+        self.req.set_body(body);
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error(response).await),
         }
     }
@@ -1,5 +1,6 @@
 use crate::{
     Error,
+    common::ResponseMeta,
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
@@ -18,7 +19,7 @@ pub struct PutObjectTaggingSync {
 }
 impl PutObjectTaggingSync {
     pub(super) fn new(oss: Oss, tags: Vec<(impl Into<String>, impl Into<String>)>) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "PutObjectTagging");
         req.insert_query("tagging", "");
         PutObjectTaggingSync { req, tags: tags.into_iter().map(|(key, value)| (key.into(), value.into())).collect() }
     }
@@ -32,7 +33,7 @@ impl PutObjectTaggingSync {
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(mut self) -> Result<(), Error> {
+    pub fn send(mut self) -> Result<ResponseMeta, Error> {
         // Build body
         let tag_str = self
             .tags
@@ -54,7 +55,7 @@ impl PutObjectTaggingSync {
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
@@ -1,6 +1,6 @@
 use crate::{
     Error,
-    common::{Acl, StorageClass},
+    common::{Acl, ResponseMeta, StorageClass},
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
@@ -18,7 +18,7 @@ pub struct PutSymlinkSync {
 }
 impl PutSymlinkSync {
     pub(super) fn new(oss: Oss, symlink_target: impl Into<String>) -> Self {
-        let mut req = OssRequest::new(oss, Method::PUT);
+        let mut req = OssRequest::new(oss, Method::PUT, "PutSymlink");
         req.insert_query("symlink", "");
         req.insert_header("x-oss-symlink-target", symlink_target.into());
         PutSymlinkSync { req }
@@ -47,13 +47,13 @@ impl PutSymlinkSync {
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(self) -> Result<(), Error> {
+    pub fn send(self) -> Result<ResponseMeta, Error> {
         // Build the HTTP request
         let response = self.req.send_to_oss()?;
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
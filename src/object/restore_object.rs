@@ -1,12 +1,10 @@
 use crate::{
     Error,
-    common::RestoreTier,
+    common::{ResponseMeta, RestoreTier},
     error::normal_error,
     request::{Oss, OssRequest},
 };
-use bytes::Bytes;
 use http::Method;
-use http_body_util::Full;
 
 /// Restore an archived object.
 ///
@@ -22,7 +20,7 @@ pub struct RestoreObject {
 }
 impl RestoreObject {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "RestoreObject");
         req.insert_query("restore", "");
         RestoreObject { req, days: None, tier: None }
     }
@@ -43,23 +41,21 @@ impl RestoreObject {
     /// Send the request.
     ///
     /// 发送请求。
-    pub async fn send(mut self) -> Result<(), Error> {
+    pub async fn send(mut self) -> Result<ResponseMeta, Error> {
         // Build the body
-        let days_str = self.days.map(|v| format!("<Days>{}</Days>", v)).unwrap_or_else(|| String::new());
-        let tier_str = self
-            .tier
-            .map(|v| format!("<JobParameters><Tier>{}</Tier></JobParameters>", v))
-            .unwrap_or_else(|| String::new());
+        let days_str = self.days.map(|v| format!("<Days>{}</Days>", v)).unwrap_or_default();
+        let tier_str =
+            self.tier.map(|v| format!("<JobParameters><Tier>{}</Tier></JobParameters>", v)).unwrap_or_default();
         if !days_str.is_empty() || !tier_str.is_empty() {
             let body_str = format!("<RestoreRequest>{}{}</RestoreRequest>", days_str, tier_str);
-            self.req.set_body(Full::new(Bytes::from(body_str)));
+            self.req.set_body(body_str);
         }
         // Build the HTTP request
-        let response = self.req.send_to_oss()?.await?;
+        let response = self.req.send_to_oss().await?;
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error(response).await),
         }
     }
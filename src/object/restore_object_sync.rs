@@ -1,6 +1,6 @@
 use crate::{
     Error,
-    common::RestoreTier,
+    common::{ResponseMeta, RestoreTier},
     error::normal_error_sync,
     request_sync::{Oss, OssRequest},
 };
@@ -20,7 +20,7 @@ pub struct RestoreObjectSync {
 }
 impl RestoreObjectSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "RestoreObject");
         req.insert_query("restore", "");
         RestoreObjectSync { req, days: None, tier: None }
     }
@@ -41,13 +41,11 @@ impl RestoreObjectSync {
     /// Send the request.
     ///
     /// 发送请求。
-    pub fn send(mut self) -> Result<(), Error> {
+    pub fn send(mut self) -> Result<ResponseMeta, Error> {
         // Build the body
-        let days_str = self.days.map(|v| format!("<Days>{}</Days>", v)).unwrap_or_else(|| String::new());
-        let tier_str = self
-            .tier
-            .map(|v| format!("<JobParameters><Tier>{}</Tier></JobParameters>", v))
-            .unwrap_or_else(|| String::new());
+        let days_str = self.days.map(|v| format!("<Days>{}</Days>", v)).unwrap_or_default();
+        let tier_str =
+            self.tier.map(|v| format!("<JobParameters><Tier>{}</Tier></JobParameters>", v)).unwrap_or_default();
         if !days_str.is_empty() || !tier_str.is_empty() {
             let body_str = format!("<RestoreRequest>{}{}</RestoreRequest>", days_str, tier_str);
             self.req.set_body(body_str.into_bytes());
@@ -57,7 +55,7 @@ impl RestoreObjectSync {
         // Parse the response
         let status_code = response.status();
         match status_code {
-            code if code.is_success() => Ok(()),
+            code if code.is_success() => Ok(ResponseMeta::from_headers(response.headers())),
             _ => Err(normal_error_sync(response)),
         }
     }
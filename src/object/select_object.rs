@@ -3,12 +3,12 @@ use crate::{
     common::body_to_bytes,
     error::normal_error,
     request::{Oss, OssRequest},
+    transport::ResponseBody,
 };
 use bytes::Bytes;
-use futures_util::{Stream, StreamExt};
-use http::Method;
-use http_body_util::{BodyExt, Full};
-use hyper::{Response, body::Incoming};
+use futures_util::Stream;
+use http::{Method, Response};
+use http_body_util::BodyExt;
 use std::pin::Pin;
 
 /// Execute an SQL-like query against an object stored in OSS.
@@ -25,7 +25,7 @@ pub struct SelectObject {
 
 impl SelectObject {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "SelectObject");
         req.insert_query("select", "");
         req.insert_query("select-type", "2");
         SelectObject { req, request_xml: None }
@@ -58,7 +58,7 @@ impl SelectObject {
     /// 发送请求并将响应聚合到内存。
     pub async fn send(self) -> Result<Bytes, Error> {
         let response = self.send_internal().await?;
-        Ok(body_to_bytes(response.into_body()).await?)
+        body_to_bytes(response.into_body()).await
     }
 
     /// Send the request and return a response stream.
@@ -71,20 +71,17 @@ impl SelectObject {
         let status_code = response.status();
         match status_code {
             code if code.is_success() => {
-                let stream = response.into_body().into_data_stream().map(|item| match item {
-                    Ok(bytes) => Ok(bytes),
-                    Err(e) => Err(e.into()),
-                });
+                let stream = response.into_body().into_data_stream();
                 Ok(Box::pin(stream))
             }
             _ => Err(normal_error(response).await),
         }
     }
 
-    async fn send_internal(mut self) -> Result<Response<Incoming>, Error> {
+    async fn send_internal(mut self) -> Result<Response<ResponseBody>, Error> {
         let body = self.request_xml.ok_or(Error::MissingRequestBody)?;
-        self.req.set_body(Full::new(Bytes::from(body)));
-        let response = self.req.send_to_oss()?.await?;
+        self.req.set_body(body);
+        let response = self.req.send_to_oss().await?;
         let status_code = response.status();
         if status_code.is_success() { Ok(response) } else { Err(normal_error(response).await) }
     }
@@ -21,7 +21,7 @@ pub struct SelectObjectSync {
 
 impl SelectObjectSync {
     pub(super) fn new(oss: Oss) -> Self {
-        let mut req = OssRequest::new(oss, Method::POST);
+        let mut req = OssRequest::new(oss, Method::POST, "SelectObject");
         req.insert_query("select", "");
         req.insert_query("select-type", "2");
         SelectObjectSync { req, request_xml: None }
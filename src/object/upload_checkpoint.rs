@@ -0,0 +1,291 @@
+//! Planning and checkpoint state shared by the async and sync resumable uploaders.
+//!
+//! 异步与同步断点续传上传共用的分片规划与检查点状态。
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+use std::fs::Metadata;
+use std::time::UNIX_EPOCH;
+
+// Default part size, 8 MiB
+pub(crate) const DEFAULT_PART_SIZE: u64 = 8 * 1024 * 1024;
+// OSS rejects parts smaller than 100 KiB, except the last one
+const MIN_PART_SIZE: u64 = 102_400;
+const MAX_PART_SIZE: u64 = 5_368_709_120;
+const MAX_PARTS: u64 = 10_000;
+
+/// Part layout of a file: part `n` (1-based) covers `[(n - 1) * part_size, n * part_size)`.
+///
+/// 文件的分片布局：第 `n` 片（从 1 开始）覆盖 `[(n - 1) * part_size, n * part_size)`。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PartPlan {
+    pub file_size: u64,
+    pub part_size: u64,
+}
+
+impl PartPlan {
+    /// Use `part_size` when it keeps the upload within 10,000 parts, otherwise the smallest size that does.
+    ///
+    /// 若 `part_size` 能使分片数不超过 10,000 则采用之，否则取满足该限制的最小分片大小。
+    pub fn new(file_size: u64, part_size: u64) -> Result<Self, Error> {
+        let part_size = part_size.clamp(MIN_PART_SIZE, MAX_PART_SIZE).max(file_size.div_ceil(MAX_PARTS));
+        if part_size > MAX_PART_SIZE {
+            return Err(Error::InvalidFileSize);
+        }
+        Ok(PartPlan { file_size, part_size })
+    }
+
+    pub fn part_count(&self) -> u32 {
+        // an empty file is still uploaded as one (empty) part
+        self.file_size.div_ceil(self.part_size).max(1) as u32
+    }
+
+    /// Byte offset and length of a part.
+    ///
+    /// 分片的字节偏移与长度。
+    pub fn range(&self, part_number: u32) -> (u64, u64) {
+        let offset = (part_number as u64 - 1) * self.part_size;
+        (offset, self.part_size.min(self.file_size - offset))
+    }
+}
+
+/// A part already stored by OSS.
+///
+/// OSS 已保存的分片。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CheckpointPart {
+    pub part_number: u32,
+    pub etag: String,
+    pub crc64: Option<u64>,
+}
+
+impl CheckpointPart {
+    /// A part from a `ListParts` page, as `(part, size)` for `Checkpoint::reconcile`.
+    ///
+    /// `ListParts` 返回的分片，以 `(分片, 大小)` 形式供 `Checkpoint::reconcile` 使用。
+    pub fn listed(part_number: u32, etag: String, crc64: u64, size: u64) -> (Self, u64) {
+        // OSS reports 0 for parts uploaded without a CRC64
+        (CheckpointPart { part_number, etag, crc64: Some(crc64).filter(|crc| *crc != 0) }, size)
+    }
+}
+
+/// How a saved checkpoint is used for the next upload.
+///
+/// 已保存检查点在下一次上传中的用法。
+#[derive(Debug, PartialEq)]
+pub(crate) enum Resume {
+    /// Start a new multipart upload.
+    ///
+    /// 发起新的分片上传。
+    Fresh,
+    /// The file changed since the checkpoint was saved: abort this upload ID, then start a new one.
+    ///
+    /// 文件在保存检查点后发生变化：终止该上传 ID 后发起新的上传。
+    Stale(String),
+    /// Reconcile the checkpoint with `ListParts` and continue its upload.
+    ///
+    /// 通过 `ListParts` 核对检查点并继续上传。
+    Continue(Checkpoint),
+}
+
+/// Whether a failed upload is aborted; by default only when there is no checkpoint to resume from.
+///
+/// 上传失败时是否终止；默认仅在没有可供恢复的检查点时终止。
+pub(crate) fn abort_on_failure(setting: Option<bool>, checkpoint: Option<&str>) -> bool {
+    setting.unwrap_or(checkpoint.is_none())
+}
+
+/// Progress of a resumable upload, persisted as JSON after every completed part.
+///
+/// 断点续传的进度，每完成一个分片后以 JSON 形式保存。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Checkpoint {
+    pub upload_id: String,
+    pub bucket: String,
+    pub key: String,
+    pub file_size: u64,
+    /// Modification time of the file in nanoseconds since the Unix epoch.
+    pub modified: u64,
+    pub part_size: u64,
+    pub parts: Vec<CheckpointPart>,
+}
+
+impl Checkpoint {
+    pub fn new(upload_id: String, bucket: String, key: String, plan: PartPlan, modified: u64) -> Self {
+        Checkpoint {
+            upload_id,
+            bucket,
+            key,
+            file_size: plan.file_size,
+            modified,
+            part_size: plan.part_size,
+            parts: Vec::new(),
+        }
+    }
+
+    /// Parse a saved checkpoint; unreadable content is treated as no checkpoint.
+    ///
+    /// 解析已保存的检查点；无法解析的内容视为没有检查点。
+    pub fn from_json(json: &[u8]) -> Option<Self> {
+        serde_json::from_slice(json).ok()
+    }
+
+    /// Decide how to use the saved checkpoint content, if any, for an upload of this file version.
+    ///
+    /// A checkpoint for another target is left alone; one for an older version of the file is stale.
+    ///
+    /// 判断已保存的检查点内容（如有）如何用于该版本文件的上传。
+    ///
+    /// 其他目标的检查点不做处理；针对旧版本文件的检查点视为过期。
+    pub fn resume(saved: Option<&[u8]>, bucket: &str, key: &str, plan: PartPlan, modified: u64) -> Resume {
+        match saved.and_then(Checkpoint::from_json) {
+            Some(checkpoint) if checkpoint.matches(bucket, key, plan, modified) => Resume::Continue(checkpoint),
+            Some(checkpoint) if checkpoint.bucket == bucket && checkpoint.key == key => {
+                Resume::Stale(checkpoint.upload_id)
+            }
+            _ => Resume::Fresh,
+        }
+    }
+
+    pub fn to_json(&self) -> Vec<u8> {
+        serde_json::to_vec_pretty(self).expect("checkpoint serializes")
+    }
+
+    /// Whether the checkpoint was written for this target and this exact version of the file.
+    ///
+    /// 检查点是否针对同一目标及同一版本的文件。
+    pub fn matches(&self, bucket: &str, key: &str, plan: PartPlan, modified: u64) -> bool {
+        self.bucket == bucket
+            && self.key == key
+            && self.file_size == plan.file_size
+            && self.part_size == plan.part_size
+            && self.modified == modified
+    }
+
+    pub fn plan(&self) -> PartPlan {
+        PartPlan { file_size: self.file_size, part_size: self.part_size }
+    }
+
+    /// Record a finished part, keeping the list ordered by part number.
+    ///
+    /// 记录已完成的分片，并按分片编号排序。
+    pub fn insert(&mut self, part: CheckpointPart) {
+        match self.parts.binary_search_by_key(&part.part_number, |p| p.part_number) {
+            Ok(index) => self.parts[index] = part,
+            Err(index) => self.parts.insert(index, part),
+        }
+    }
+
+    /// Keep the parts OSS lists, as `(part, size)`, with the expected size, including those the checkpoint missed.
+    ///
+    /// Parts whose ETag differs from the recorded one are dropped and uploaded again.
+    ///
+    /// 保留 OSS 以预期大小列出的分片（`(分片, 大小)`），包括检查点遗漏的分片。
+    ///
+    /// ETag 与记录不一致的分片会被丢弃并重新上传。
+    pub fn reconcile(&mut self, listed: Vec<(CheckpointPart, u64)>) {
+        let plan = self.plan();
+        let recorded = std::mem::take(&mut self.parts);
+        for (part, size) in listed {
+            if part.part_number == 0 || part.part_number > plan.part_count() || size != plan.range(part.part_number).1 {
+                continue;
+            }
+            match recorded.iter().find(|p| p.part_number == part.part_number) {
+                Some(known) if known.etag != part.etag => continue,
+                Some(known) => self.insert(CheckpointPart { crc64: known.crc64.or(part.crc64), ..part }),
+                None => self.insert(part),
+            }
+        }
+    }
+
+    /// Part numbers that still have to be uploaded.
+    ///
+    /// 仍需上传的分片编号。
+    pub fn pending(&self) -> Vec<u32> {
+        (1..=self.plan().part_count())
+            .filter(|number| self.parts.binary_search_by_key(number, |p| p.part_number).is_err())
+            .collect()
+    }
+
+    /// Part numbers and ETags for `CompleteMultipartUpload`.
+    ///
+    /// 用于 `CompleteMultipartUpload` 的分片编号与 ETag。
+    pub fn completed_parts(&self) -> Vec<(String, String)> {
+        self.parts.iter().map(|part| (part.part_number.to_string(), part.etag.clone())).collect()
+    }
+
+    /// Bytes covered by the finished parts.
+    ///
+    /// 已完成分片覆盖的字节数。
+    pub fn uploaded(&self) -> u64 {
+        let plan = self.plan();
+        self.parts.iter().map(|part| plan.range(part.part_number).1).sum()
+    }
+}
+
+/// Modification time in nanoseconds since the Unix epoch, or 0 when the platform has none.
+///
+/// 以 Unix 纪元起的纳秒数表示的修改时间，平台不支持时为 0。
+pub(crate) fn modified_nanos(metadata: &Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_plan_and_checkpoint() {
+        let plan = PartPlan::new(250 * 1024, 100 * 1024).unwrap();
+        assert_eq!(plan.part_count(), 3);
+        assert_eq!(plan.range(3), (200 * 1024, 50 * 1024));
+        assert_eq!(PartPlan::new(10, 1).unwrap().part_size, MIN_PART_SIZE);
+        assert_eq!(PartPlan::new(0, DEFAULT_PART_SIZE).unwrap().part_count(), 1);
+        // large files grow the part size to stay within 10,000 parts
+        let large = PartPlan::new(200 * 1024 * 1024 * 1024, DEFAULT_PART_SIZE).unwrap();
+        assert!(large.part_count() <= 10_000 && large.part_size > DEFAULT_PART_SIZE);
+        assert!(PartPlan::new(MAX_PART_SIZE * MAX_PARTS + 1, DEFAULT_PART_SIZE).is_err());
+
+        let mut checkpoint = Checkpoint::new("id".into(), "bucket".into(), "key".into(), plan, 7);
+        let part = |number: u32, etag: &str| CheckpointPart { part_number: number, etag: etag.into(), crc64: None };
+        checkpoint.insert(CheckpointPart { crc64: Some(1), ..part(2, "b") });
+        checkpoint.insert(part(1, "a"));
+        assert_eq!(checkpoint.pending(), [3]);
+        let parsed = Checkpoint::from_json(&checkpoint.to_json()).unwrap();
+        assert_eq!(parsed, checkpoint);
+        assert!(parsed.matches("bucket", "key", plan, 7));
+        assert!(!parsed.matches("bucket", "key", plan, 8));
+        assert!(Checkpoint::from_json(b"{").is_none());
+
+        // part 1 changed on the server, part 2 is kept, part 3 was uploaded but not recorded
+        checkpoint.reconcile(vec![(part(1, "x"), 100 * 1024), (part(2, "b"), 100 * 1024), (part(3, "c"), 50 * 1024)]);
+        assert_eq!(checkpoint.pending(), [1]);
+        assert_eq!(checkpoint.parts[0].crc64, Some(1));
+        assert_eq!(checkpoint.uploaded(), 150 * 1024);
+    }
+
+    #[test]
+    fn test_resume_decision() {
+        let plan = PartPlan::new(250 * 1024, 100 * 1024).unwrap();
+        let mut checkpoint = Checkpoint::new("id".into(), "bucket".into(), "key".into(), plan, 7);
+        let (part, size) = CheckpointPart::listed(2, "b".into(), 0, 100 * 1024);
+        assert_eq!((part.crc64, size), (None, 100 * 1024));
+        checkpoint.insert(part);
+        assert_eq!(checkpoint.completed_parts(), [("2".to_string(), "b".to_string())]);
+
+        let json = checkpoint.to_json();
+        assert_eq!(Checkpoint::resume(Some(&json), "bucket", "key", plan, 7), Resume::Continue(checkpoint));
+        assert_eq!(Checkpoint::resume(Some(&json), "bucket", "key", plan, 8), Resume::Stale("id".into()));
+        assert_eq!(Checkpoint::resume(Some(&json), "bucket", "other", plan, 7), Resume::Fresh);
+        assert_eq!(Checkpoint::resume(Some(b"{"), "bucket", "key", plan, 7), Resume::Fresh);
+        assert_eq!(Checkpoint::resume(None, "bucket", "key", plan, 7), Resume::Fresh);
+
+        assert!(abort_on_failure(None, None));
+        assert!(!abort_on_failure(None, Some("upload.json")));
+        assert!(abort_on_failure(Some(true), Some("upload.json")));
+    }
+}
@@ -0,0 +1,268 @@
+use super::upload_checkpoint::{
+    Checkpoint, CheckpointPart, DEFAULT_PART_SIZE, PartPlan, Resume, abort_on_failure, modified_nanos,
+};
+use crate::{OssObject, RetryPolicy, common::CompleteUploadOutput, error::Error, oss::Oss};
+use tokio::{fs, task::JoinSet};
+
+/// Upload a local file with multipart upload, resuming from a checkpoint file after a failure or crash.
+///
+/// Parts are uploaded concurrently. When a checkpoint file is set, progress is saved there as JSON after every
+/// part, and a later run for the same unchanged file picks up the upload again, using `ListParts` to find the parts
+/// OSS already holds. The checkpoint is removed once the upload completes.
+///
+/// 以分片上传方式上传本地文件，失败或崩溃后可从检查点文件恢复。
+///
+/// 分片并发上传。设置检查点文件后，每完成一个分片就以 JSON 形式保存进度；对同一个未修改的文件再次上传时会继续该上传，
+/// 并通过 `ListParts` 确认 OSS 已保存的分片。上传完成后检查点文件会被删除。
+pub struct UploadFile {
+    object: OssObject,
+    bucket: String,
+    key: String,
+    file: String,
+    part_size: u64,
+    concurrency: usize,
+    checkpoint: Option<String>,
+    abort_on_failure: Option<bool>,
+    mime: Option<String>,
+    callback: Option<Box<dyn Fn(u64, u64) + Send + Sync + 'static>>,
+    retry_policy: RetryPolicy,
+}
+
+impl UploadFile {
+    pub(super) fn new(oss: Oss, file: impl Into<String>) -> Self {
+        let bucket = oss.bucket.clone().unwrap_or_default().to_string();
+        let key = oss.object.clone().unwrap_or_default().to_string();
+        let retry_policy = oss.retry_policy.clone();
+        UploadFile {
+            object: OssObject::new(oss, key.clone()),
+            bucket,
+            key,
+            file: file.into(),
+            part_size: DEFAULT_PART_SIZE,
+            concurrency: 4,
+            checkpoint: None,
+            abort_on_failure: None,
+            mime: None,
+            callback: None,
+            retry_policy,
+        }
+    }
+    /// Set the part size; defaults to 8 MiB.
+    ///
+    /// Sizes below 100 KiB are raised to 100 KiB, and the size grows as needed to stay within 10,000 parts.
+    ///
+    /// 设置分片大小，默认 8 MiB。
+    ///
+    /// 小于 100 KiB 时按 100 KiB 处理；为保证分片数不超过 10,000，会按需增大分片。
+    pub fn set_part_size(mut self, part_size: u64) -> Self {
+        self.part_size = part_size;
+        self
+    }
+    /// Set how many parts are uploaded at the same time; defaults to 4.
+    ///
+    /// 设置同时上传的分片数量，默认 4。
+    pub fn set_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+    /// Save progress to this JSON file so an interrupted upload can be resumed.
+    ///
+    /// 将进度保存到该 JSON 文件，以便恢复中断的上传。
+    pub fn set_checkpoint(mut self, path: impl Into<String>) -> Self {
+        self.checkpoint = Some(path.into());
+        self
+    }
+    /// Abort the multipart upload when it fails, or keep it for a later resume.
+    ///
+    /// By default the upload is kept when a checkpoint file is set and aborted otherwise.
+    ///
+    /// 上传失败时终止分片上传，或保留以便之后恢复。
+    ///
+    /// 默认在设置了检查点文件时保留，否则终止。
+    pub fn set_abort_on_failure(mut self, abort: bool) -> Self {
+        self.abort_on_failure = Some(abort);
+        self
+    }
+    /// Set the object's MIME type.
+    ///
+    /// 设置对象的 MIME 类型。
+    pub fn set_mime(mut self, mime: impl Into<String>) -> Self {
+        self.mime = Some(mime.into());
+        self
+    }
+    /// Set a progress callback receiving the uploaded and total bytes after every part.
+    ///
+    /// Parts restored from the checkpoint count as uploaded.
+    ///
+    /// 设置进度回调，每完成一个分片回调一次已上传字节数与总字节数。
+    ///
+    /// 从检查点恢复的分片计为已上传。
+    pub fn set_callback(mut self, callback: Box<dyn Fn(u64, u64) + Send + Sync + 'static>) -> Self {
+        self.callback = Some(callback);
+        self
+    }
+    /// Upload the file and complete the multipart upload.
+    ///
+    /// 上传文件并完成分片上传。
+    pub async fn send(self) -> Result<CompleteUploadOutput, Error> {
+        let object = &self.object;
+        let metadata = fs::metadata(&self.file).await?;
+        let modified = modified_nanos(&metadata);
+        let plan = PartPlan::new(metadata.len(), self.part_size)?;
+        let mut checkpoint = match self.resume(plan, modified).await? {
+            Some(checkpoint) => checkpoint,
+            None => {
+                let mut init = object.multipart_init_upload();
+                if let Some(mime) = &self.mime {
+                    init = init.set_mime(mime);
+                }
+                let upload_id = init.send().await?.upload_id;
+                Checkpoint::new(upload_id, self.bucket.clone(), self.key.clone(), plan, modified)
+            }
+        };
+        self.save(&checkpoint).await?;
+        let result = match self.upload_parts(&mut checkpoint).await {
+            Ok(()) => {
+                let parts = checkpoint.completed_parts();
+                object
+                    .multipart_complete_upload(&checkpoint.upload_id)
+                    .add_parts(parts.iter().map(|(number, etag)| (number.as_str(), etag.as_str())).collect())
+                    .send()
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(output) => {
+                self.remove_checkpoint().await;
+                Ok(output)
+            }
+            Err(e) => {
+                if abort_on_failure(self.abort_on_failure, self.checkpoint.as_deref()) {
+                    // the upload error matters more than a failed cleanup
+                    let _ = object.multipart_abort_upload(&checkpoint.upload_id).send().await;
+                    self.remove_checkpoint().await;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    // Load a checkpoint for this file and reconcile it with the parts OSS holds
+    async fn resume(&self, plan: PartPlan, modified: u64) -> Result<Option<Checkpoint>, Error> {
+        let object = &self.object;
+        let saved = match &self.checkpoint {
+            Some(path) => fs::read(path).await.ok(),
+            None => None,
+        };
+        let mut checkpoint = match Checkpoint::resume(saved.as_deref(), &self.bucket, &self.key, plan, modified) {
+            Resume::Continue(checkpoint) => checkpoint,
+            Resume::Stale(upload_id) => {
+                // the file changed, so its parts are useless
+                let _ = object.multipart_abort_upload(&upload_id).send().await;
+                return Ok(None);
+            }
+            Resume::Fresh => return Ok(None),
+        };
+        let mut listed = Vec::new();
+        let mut marker = 0;
+        loop {
+            let page =
+                match object.multipart_list_parts(&checkpoint.upload_id).set_part_number_marker(marker).send().await {
+                    Ok(page) => page,
+                    Err(e) if e.is_not_found() => return Ok(None),
+                    Err(e) => return Err(e),
+                };
+            for part in page.part.unwrap_or_default() {
+                listed.push(CheckpointPart::listed(part.part_number, part.e_tag, part.hash_crc64ecma, part.size));
+            }
+            if !page.is_truncated {
+                break;
+            }
+            marker = page.next_part_number_marker;
+        }
+        checkpoint.reconcile(listed);
+        Ok(Some(checkpoint))
+    }
+
+    async fn upload_parts(&self, checkpoint: &mut Checkpoint) -> Result<(), Error> {
+        let plan = checkpoint.plan();
+        let mut uploaded = checkpoint.uploaded();
+        let mut pending = checkpoint.pending().into_iter();
+        let mut tasks = JoinSet::new();
+        let mut failure = None;
+        loop {
+            while failure.is_none() && tasks.len() < self.concurrency {
+                let Some(part_number) = pending.next() else { break };
+                let object = self.object.clone();
+                let upload_id = checkpoint.upload_id.clone();
+                let file = self.file.clone();
+                let policy = self.retry_policy.clone();
+                tasks.spawn(async move { upload_part(object, upload_id, file, plan, part_number, policy).await });
+            }
+            let Some(joined) = tasks.join_next().await else { break };
+            match joined {
+                Ok(Ok(part)) => {
+                    uploaded += plan.range(part.part_number).1;
+                    checkpoint.insert(part);
+                    self.save(checkpoint).await?;
+                    if let Some(callback) = &self.callback {
+                        callback(uploaded, plan.file_size);
+                    }
+                }
+                Ok(Err(e)) => {
+                    failure.get_or_insert(e);
+                }
+                Err(e) => {
+                    failure.get_or_insert(Error::IoError(std::io::Error::other(e)));
+                }
+            }
+        }
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    // Write to a temporary file first so a crash never leaves a truncated checkpoint
+    async fn save(&self, checkpoint: &Checkpoint) -> Result<(), Error> {
+        if let Some(path) = &self.checkpoint {
+            let temp = format!("{}.tmp", path);
+            fs::write(&temp, checkpoint.to_json()).await?;
+            fs::rename(&temp, path).await?;
+        }
+        Ok(())
+    }
+
+    // Best-effort: the object is already complete, and a stale checkpoint only costs the next run a ListParts
+    // call that reports the upload as gone
+    async fn remove_checkpoint(&self) {
+        if let Some(path) = &self.checkpoint {
+            let _ = fs::remove_file(path).await;
+        }
+    }
+}
+
+// Parts are streamed from the file and cannot be replayed by the request, so a failed part is sent again here
+// following the client's retry policy
+async fn upload_part(
+    object: OssObject,
+    upload_id: String,
+    file: String,
+    plan: PartPlan,
+    part_number: u32,
+    policy: RetryPolicy,
+) -> Result<CheckpointPart, Error> {
+    let (offset, len) = plan.range(part_number);
+    let mut attempt = 1;
+    loop {
+        match object.multipart_upload_part(part_number, &upload_id).send_file_range(&file, offset, len).await {
+            Ok(output) => return Ok(CheckpointPart { part_number, etag: output.etag, crc64: output.meta.crc64 }),
+            Err(e) if attempt < policy.max_attempts() && policy.should_retry(&e) => {
+                tokio::time::sleep(policy.backoff(attempt)).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
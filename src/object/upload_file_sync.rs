@@ -0,0 +1,265 @@
+use super::upload_checkpoint::{
+    Checkpoint, CheckpointPart, DEFAULT_PART_SIZE, PartPlan, Resume, abort_on_failure, modified_nanos,
+};
+use crate::{OssObject, RetryPolicy, common::CompleteUploadOutput, error::Error, oss::Oss};
+use std::fs;
+use std::sync::Mutex;
+use std::thread;
+
+/// Upload a local file with multipart upload, resuming from a checkpoint file after a failure or crash (sync).
+///
+/// Parts are uploaded concurrently on worker threads. When a checkpoint file is set, progress is saved there as JSON after every
+/// part, and a later run for the same unchanged file picks up the upload again, using `ListParts` to find the parts
+/// OSS already holds. The checkpoint is removed once the upload completes.
+///
+/// 以分片上传方式上传本地文件，失败或崩溃后可从检查点文件恢复（同步）。
+///
+/// 分片在工作线程上并发上传。设置检查点文件后，每完成一个分片就以 JSON 形式保存进度；对同一个未修改的文件再次上传时会继续该上传，
+/// 并通过 `ListParts` 确认 OSS 已保存的分片。上传完成后检查点文件会被删除。
+pub struct UploadFileSync {
+    object: OssObject,
+    bucket: String,
+    key: String,
+    file: String,
+    part_size: u64,
+    concurrency: usize,
+    checkpoint: Option<String>,
+    abort_on_failure: Option<bool>,
+    mime: Option<String>,
+    callback: Option<Box<dyn Fn(u64, u64) + Send + Sync + 'static>>,
+    retry_policy: RetryPolicy,
+}
+
+impl UploadFileSync {
+    pub(super) fn new(oss: Oss, file: impl Into<String>) -> Self {
+        let bucket = oss.bucket.clone().unwrap_or_default().to_string();
+        let key = oss.object.clone().unwrap_or_default().to_string();
+        let retry_policy = oss.retry_policy.clone();
+        UploadFileSync {
+            object: OssObject::new(oss, key.clone()),
+            bucket,
+            key,
+            file: file.into(),
+            part_size: DEFAULT_PART_SIZE,
+            concurrency: 4,
+            checkpoint: None,
+            abort_on_failure: None,
+            mime: None,
+            callback: None,
+            retry_policy,
+        }
+    }
+    /// Set the part size; defaults to 8 MiB.
+    ///
+    /// Sizes below 100 KiB are raised to 100 KiB, and the size grows as needed to stay within 10,000 parts.
+    ///
+    /// 设置分片大小，默认 8 MiB。
+    ///
+    /// 小于 100 KiB 时按 100 KiB 处理；为保证分片数不超过 10,000，会按需增大分片。
+    pub fn set_part_size(mut self, part_size: u64) -> Self {
+        self.part_size = part_size;
+        self
+    }
+    /// Set how many parts are uploaded at the same time, one thread each; defaults to 4.
+    ///
+    /// 设置同时上传的分片数量（每个分片一个线程），默认 4。
+    pub fn set_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+    /// Save progress to this JSON file so an interrupted upload can be resumed.
+    ///
+    /// 将进度保存到该 JSON 文件，以便恢复中断的上传。
+    pub fn set_checkpoint(mut self, path: impl Into<String>) -> Self {
+        self.checkpoint = Some(path.into());
+        self
+    }
+    /// Abort the multipart upload when it fails, or keep it for a later resume.
+    ///
+    /// By default the upload is kept when a checkpoint file is set and aborted otherwise.
+    ///
+    /// 上传失败时终止分片上传，或保留以便之后恢复。
+    ///
+    /// 默认在设置了检查点文件时保留，否则终止。
+    pub fn set_abort_on_failure(mut self, abort: bool) -> Self {
+        self.abort_on_failure = Some(abort);
+        self
+    }
+    /// Set the object's MIME type.
+    ///
+    /// 设置对象的 MIME 类型。
+    pub fn set_mime(mut self, mime: impl Into<String>) -> Self {
+        self.mime = Some(mime.into());
+        self
+    }
+    /// Set a progress callback receiving the uploaded and total bytes after every part.
+    ///
+    /// Parts restored from the checkpoint count as uploaded.
+    ///
+    /// 设置进度回调，每完成一个分片回调一次已上传字节数与总字节数。
+    ///
+    /// 从检查点恢复的分片计为已上传。
+    pub fn set_callback(mut self, callback: Box<dyn Fn(u64, u64) + Send + Sync + 'static>) -> Self {
+        self.callback = Some(callback);
+        self
+    }
+    /// Upload the file and complete the multipart upload.
+    ///
+    /// 上传文件并完成分片上传。
+    pub fn send(self) -> Result<CompleteUploadOutput, Error> {
+        let object = &self.object;
+        let metadata = fs::metadata(&self.file)?;
+        let modified = modified_nanos(&metadata);
+        let plan = PartPlan::new(metadata.len(), self.part_size)?;
+        let mut checkpoint = match self.resume(plan, modified)? {
+            Some(checkpoint) => checkpoint,
+            None => {
+                let mut init = object.multipart_init_upload_sync();
+                if let Some(mime) = &self.mime {
+                    init = init.set_mime(mime);
+                }
+                let upload_id = init.send()?.upload_id;
+                Checkpoint::new(upload_id, self.bucket.clone(), self.key.clone(), plan, modified)
+            }
+        };
+        self.save(&checkpoint)?;
+        let result = match self.upload_parts(&mut checkpoint) {
+            Ok(()) => {
+                let parts = checkpoint.completed_parts();
+                object
+                    .multipart_complete_upload_sync(&checkpoint.upload_id)
+                    .add_parts(parts.iter().map(|(number, etag)| (number.as_str(), etag.as_str())).collect())
+                    .send()
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(output) => {
+                self.remove_checkpoint();
+                Ok(output)
+            }
+            Err(e) => {
+                if abort_on_failure(self.abort_on_failure, self.checkpoint.as_deref()) {
+                    // the upload error matters more than a failed cleanup
+                    let _ = object.multipart_abort_upload_sync(&checkpoint.upload_id).send();
+                    self.remove_checkpoint();
+                }
+                Err(e)
+            }
+        }
+    }
+
+    // Load a checkpoint for this file and reconcile it with the parts OSS holds
+    fn resume(&self, plan: PartPlan, modified: u64) -> Result<Option<Checkpoint>, Error> {
+        let object = &self.object;
+        let saved = self.checkpoint.as_ref().and_then(|path| fs::read(path).ok());
+        let mut checkpoint = match Checkpoint::resume(saved.as_deref(), &self.bucket, &self.key, plan, modified) {
+            Resume::Continue(checkpoint) => checkpoint,
+            Resume::Stale(upload_id) => {
+                // the file changed, so its parts are useless
+                let _ = object.multipart_abort_upload_sync(&upload_id).send();
+                return Ok(None);
+            }
+            Resume::Fresh => return Ok(None),
+        };
+        let mut listed = Vec::new();
+        let mut marker = 0;
+        loop {
+            let page =
+                match object.multipart_list_parts_sync(&checkpoint.upload_id).set_part_number_marker(marker).send() {
+                    Ok(page) => page,
+                    Err(e) if e.is_not_found() => return Ok(None),
+                    Err(e) => return Err(e),
+                };
+            for part in page.part.unwrap_or_default() {
+                listed.push(CheckpointPart::listed(part.part_number, part.e_tag, part.hash_crc64ecma, part.size));
+            }
+            if !page.is_truncated {
+                break;
+            }
+            marker = page.next_part_number_marker;
+        }
+        checkpoint.reconcile(listed);
+        Ok(Some(checkpoint))
+    }
+
+    fn upload_parts(&self, checkpoint: &mut Checkpoint) -> Result<(), Error> {
+        let plan = checkpoint.plan();
+        let upload_id = checkpoint.upload_id.clone();
+        let pending = Mutex::new(checkpoint.pending().into_iter());
+        let progress = Mutex::new((checkpoint, None::<Error>));
+        thread::scope(|scope| {
+            for _ in 0..self.concurrency {
+                scope.spawn(|| {
+                    loop {
+                        // stop taking new parts once any part has failed
+                        if progress.lock().unwrap().1.is_some() {
+                            break;
+                        }
+                        let Some(part_number) = pending.lock().unwrap().next() else { break };
+                        let result = self.upload_part(&upload_id, plan, part_number);
+                        let mut progress = progress.lock().unwrap();
+                        let (checkpoint, failure) = &mut *progress;
+                        let result = result.and_then(|part| {
+                            checkpoint.insert(part);
+                            self.save(checkpoint)
+                        });
+                        match result {
+                            Ok(()) => {
+                                if let Some(callback) = &self.callback {
+                                    callback(checkpoint.uploaded(), plan.file_size);
+                                }
+                            }
+                            Err(e) => {
+                                failure.get_or_insert(e);
+                            }
+                        }
+                    }
+                });
+            }
+        });
+        match progress.into_inner().unwrap().1 {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    // Parts are streamed from the file, so a failed part is sent again here, see `upload_file::upload_part`
+    fn upload_part(&self, upload_id: &str, plan: PartPlan, part_number: u32) -> Result<CheckpointPart, Error> {
+        let (offset, len) = plan.range(part_number);
+        let mut attempt = 1;
+        loop {
+            match self
+                .object
+                .multipart_upload_part_sync(part_number, upload_id)
+                .send_file_range(&self.file, offset, len)
+            {
+                Ok(output) => {
+                    return Ok(CheckpointPart { part_number, etag: output.etag, crc64: output.meta.crc64 });
+                }
+                Err(e) if attempt < self.retry_policy.max_attempts() && self.retry_policy.should_retry(&e) => {
+                    thread::sleep(self.retry_policy.backoff(attempt));
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    // Write to a temporary file first so a crash never leaves a truncated checkpoint
+    fn save(&self, checkpoint: &Checkpoint) -> Result<(), Error> {
+        if let Some(path) = &self.checkpoint {
+            let temp = format!("{}.tmp", path);
+            fs::write(&temp, checkpoint.to_json())?;
+            fs::rename(&temp, path)?;
+        }
+        Ok(())
+    }
+
+    // Best-effort, like `UploadFile::remove_checkpoint`
+    fn remove_checkpoint(&self) {
+        if let Some(path) = &self.checkpoint {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
@@ -1,4 +1,17 @@
+use crate::Error;
+use crate::clock::ClockSkew;
+use crate::common::{AddressingStyle, EndpointKind, SignatureVersion};
+use crate::credentials::{Credentials, CredentialsCache, CredentialsProvider};
+use crate::interceptor::{Interceptor, Interceptors};
+use crate::proxy::Proxy;
+use crate::retry::RetryPolicy;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use crate::tls::TlsConfig;
+use crate::transport::{Transport, TransportConfig};
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// Shared OSS configuration used by async and sync clients.
 ///
@@ -8,33 +21,64 @@ pub(crate) struct Oss {
     pub ak_id: Cow<'static, str>,
     pub ak_secret: Cow<'static, str>,
     pub security_token: Option<Cow<'static, str>>,
+    pub credentials_provider: Option<Arc<CredentialsCache>>,
     pub region: Cow<'static, str>,
     pub endpoint: Cow<'static, str>,
+    /// Kind the endpoint was derived from; `None` once an explicit endpoint is set.
+    pub endpoint_kind: Option<EndpointKind>,
+    /// Endpoints reported by DescribeRegions, keyed by region and kind, shared by clones.
+    pub region_endpoints: Arc<Mutex<HashMap<(String, EndpointKind), String>>>,
+    /// Whether cross-region rejections trigger a region lookup and a re-send.
+    pub region_discovery: bool,
+    /// Regions discovered per bucket, shared by clones.
+    pub bucket_regions: Arc<Mutex<HashMap<String, String>>>,
     pub custom_domain: Option<Cow<'static, str>>,
     pub bucket: Option<Cow<'static, str>>,
     pub object: Option<Cow<'static, str>>,
     pub enable_https: bool,
+    pub addressing_style: AddressingStyle,
+    pub signed_payload: bool,
+    /// Whether uploads and downloads are verified against `x-oss-hash-crc64ecma`.
+    pub crc_check: bool,
+    pub signature_version: SignatureVersion,
+    pub transport: Arc<Transport>,
+    pub retry_policy: RetryPolicy,
+    pub clock: Arc<ClockSkew>,
+    pub interceptors: Interceptors,
 }
 
 impl Oss {
     pub fn new(ak_id: impl Into<String>, ak_secret: impl Into<String>, region: impl Into<String>) -> Self {
         let region = region.into();
         let region = region.trim().to_string();
-        let endpoint = if region.is_empty() {
-            Cow::Borrowed("oss.aliyuncs.com")
+        let (endpoint, endpoint_kind) = if region.is_empty() {
+            (Cow::Borrowed("oss.aliyuncs.com"), None)
         } else {
-            Cow::Owned(format!("oss-{}.aliyuncs.com", region))
+            (Cow::Owned(EndpointKind::Public.endpoint(&region)), Some(EndpointKind::Public))
         };
         Oss {
             ak_id: Cow::Owned(ak_id.into()),
             ak_secret: Cow::Owned(ak_secret.into()),
             security_token: None,
+            credentials_provider: None,
             region: Cow::Owned(region),
             endpoint,
+            endpoint_kind,
+            region_endpoints: Arc::default(),
+            region_discovery: false,
+            bucket_regions: Arc::default(),
             custom_domain: None,
             bucket: None,
             object: None,
             enable_https: true,
+            addressing_style: AddressingStyle::VirtualHosted,
+            signed_payload: false,
+            crc_check: true,
+            signature_version: SignatureVersion::V4,
+            transport: Arc::new(Transport::default()),
+            retry_policy: RetryPolicy::default(),
+            clock: Arc::new(ClockSkew::default()),
+            interceptors: Interceptors::default(),
         }
     }
 
@@ -48,12 +92,149 @@ impl Oss {
 
     pub fn set_endpoint(&mut self, endpoint: impl Into<String>) {
         self.endpoint = Cow::Owned(endpoint.into());
+        self.endpoint_kind = None;
+    }
+
+    /// Use an endpoint of this kind for the region, preferring the one reported by DescribeRegions.
+    ///
+    /// 使用该地域此类型的 Endpoint，优先采用 DescribeRegions 返回的地址。
+    pub fn set_endpoint_kind(&mut self, kind: EndpointKind) {
+        let endpoint = self.region_endpoint(kind).unwrap_or_else(|| kind.endpoint(&self.region));
+        self.endpoint = Cow::Owned(endpoint);
+        self.endpoint_kind = Some(kind);
+    }
+
+    /// Whether DescribeRegions should be consulted before using this kind of endpoint: it reports
+    /// public, internal and acceleration endpoints, and each region is looked up once.
+    ///
+    /// 使用此类型 Endpoint 前是否需要查询 DescribeRegions：它返回公网、内网与传输加速 Endpoint，
+    /// 每个地域只查询一次。
+    pub fn needs_region_lookup(&self, kind: EndpointKind) -> bool {
+        matches!(kind, EndpointKind::Public | EndpointKind::Internal | EndpointKind::Accelerate)
+            && self.region_endpoint(kind).is_none()
+    }
+
+    /// Cache the endpoints DescribeRegions reported for a region (`oss-` prefix optional).
+    ///
+    /// 缓存 DescribeRegions 返回的地域 Endpoint（地域可带 `oss-` 前缀）。
+    pub fn cache_region_endpoints(&self, region: &str, internet: &str, internal: &str, accelerate: &str) {
+        let region = region.strip_prefix("oss-").unwrap_or(region);
+        let mut endpoints = self.region_endpoints.lock().unwrap_or_else(|e| e.into_inner());
+        for (kind, endpoint) in [
+            (EndpointKind::Public, internet),
+            (EndpointKind::Internal, internal),
+            (EndpointKind::Accelerate, accelerate),
+        ] {
+            if !endpoint.is_empty() {
+                endpoints.insert((region.to_string(), kind), endpoint.to_string());
+            }
+        }
+    }
+
+    fn region_endpoint(&self, kind: EndpointKind) -> Option<String> {
+        let endpoints = self.region_endpoints.lock().unwrap_or_else(|e| e.into_inner());
+        endpoints.get(&(self.region.to_string(), kind)).cloned()
+    }
+
+    /// Whether a failed request may be retried in the bucket's discovered region.
+    ///
+    /// 失败的请求是否可以在 Bucket 查询到的地域重试。
+    pub fn follows_bucket_region(&self) -> bool {
+        self.region_discovery && self.bucket.is_some() && self.custom_domain.is_none()
+    }
+
+    /// Switch to the region previously discovered for the bucket, if any.
+    ///
+    /// 切换到此前为该 Bucket 查询到的地域（如有）。
+    pub fn apply_bucket_region(&mut self) {
+        if !self.follows_bucket_region() {
+            return;
+        }
+        let region = {
+            let regions = self.bucket_regions.lock().unwrap_or_else(|e| e.into_inner());
+            self.bucket.as_deref().and_then(|bucket| regions.get(bucket).cloned())
+        };
+        if let Some(region) = region {
+            self.rebind_region(&region);
+        }
+    }
+
+    /// Whether the bucket's region has already been discovered, even if it is the signing region.
+    ///
+    /// 是否已查询到 Bucket 所在地域（即使与当前签名地域相同）。
+    pub fn knows_bucket_region(&self) -> bool {
+        let regions = self.bucket_regions.lock().unwrap_or_else(|e| e.into_inner());
+        self.bucket.as_deref().is_some_and(|bucket| regions.contains_key(bucket))
+    }
+
+    /// Remember the bucket's region (`oss-` prefix optional) and rebind to it. Returns `false`
+    /// when it already is the signing region.
+    ///
+    /// 记录 Bucket 所在地域（地域可带 `oss-` 前缀）并重新绑定。若已是当前签名地域则返回 `false`。
+    pub fn bind_bucket_region(&mut self, region: &str) -> bool {
+        let region = region.strip_prefix("oss-").unwrap_or(region);
+        if region.is_empty() {
+            return false;
+        }
+        // remembered even when unchanged, so a genuine rejection does not trigger another lookup
+        if let Some(bucket) = self.bucket.as_deref() {
+            let mut regions = self.bucket_regions.lock().unwrap_or_else(|e| e.into_inner());
+            regions.insert(bucket.to_string(), region.to_string());
+        }
+        if region == self.region {
+            return false;
+        }
+        self.rebind_region(region);
+        true
+    }
+
+    /// Sign for `region`; an endpoint derived from the old region follows it, an explicit one is kept
+    /// so only the signing region changes.
+    fn rebind_region(&mut self, region: &str) {
+        if region == self.region {
+            return;
+        }
+        self.region = Cow::Owned(region.to_string());
+        if let Some(kind) = self.endpoint_kind {
+            self.set_endpoint_kind(kind);
+        }
     }
 
     pub fn set_https(&mut self, enable: bool) {
         self.enable_https = enable;
     }
 
+    pub fn set_addressing_style(&mut self, style: AddressingStyle) {
+        self.addressing_style = style;
+    }
+
+    /// Scheme, host and bucket path prefix of request URLs, ending with `/`.
+    ///
+    /// 请求 URL 的协议、主机与 Bucket 路径前缀，以 `/` 结尾。
+    pub fn base_url(&self) -> String {
+        let scheme = if self.enable_https { "https" } else { "http" };
+        if let Some(custom_domain) = self.custom_domain.as_deref() {
+            return format!("{}://{}/", scheme, custom_domain);
+        }
+        match (self.bucket.as_deref(), self.addressing_style) {
+            (Some(bucket), AddressingStyle::VirtualHosted) => format!("{}://{}.{}/", scheme, bucket, self.endpoint),
+            (Some(bucket), AddressingStyle::Path) => format!("{}://{}/{}/", scheme, self.endpoint, bucket),
+            (None, _) => format!("{}://{}/", scheme, self.endpoint),
+        }
+    }
+
+    pub fn set_signature_version(&mut self, version: SignatureVersion) {
+        self.signature_version = version;
+    }
+
+    pub fn set_signed_payload(&mut self, enable: bool) {
+        self.signed_payload = enable;
+    }
+
+    pub fn set_crc_check(&mut self, enable: bool) {
+        self.crc_check = enable;
+    }
+
     pub fn set_custom_domain(&mut self, domain: impl Into<String>) {
         self.custom_domain = Some(Cow::Owned(domain.into()));
     }
@@ -61,6 +242,82 @@ impl Oss {
     pub fn set_security_token(&mut self, token: impl Into<String>) {
         self.security_token = Some(Cow::Owned(token.into()));
     }
+
+    pub fn set_credentials_provider(&mut self, provider: Arc<dyn CredentialsProvider>) {
+        self.credentials_provider = Some(Arc::new(CredentialsCache::new(provider)));
+    }
+
+    /// Credentials to sign with: the provider's when one is set, otherwise the static AccessKey pair.
+    ///
+    /// 用于签名的凭证：设置了提供者时使用提供者的凭证，否则使用静态 AccessKey。
+    pub fn credentials(&self) -> Result<Credentials, Error> {
+        match &self.credentials_provider {
+            Some(provider) => provider.get(),
+            None => Ok(self.static_credentials()),
+        }
+    }
+
+    #[cfg(feature = "_async-base")]
+    pub async fn credentials_async(&self) -> Result<Credentials, Error> {
+        match &self.credentials_provider {
+            Some(provider) => provider.get_async().await,
+            None => Ok(self.static_credentials()),
+        }
+    }
+
+    fn static_credentials(&self) -> Credentials {
+        let credentials = Credentials::new(self.ak_id.as_ref(), self.ak_secret.as_ref());
+        match &self.security_token {
+            Some(token) => credentials.with_security_token(token.as_ref()),
+            None => credentials,
+        }
+    }
+
+    pub fn add_interceptor(&mut self, interceptor: Arc<dyn Interceptor>) {
+        self.interceptors.push(interceptor);
+    }
+
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
+    pub fn set_pool_idle_timeout(&mut self, timeout: Duration) {
+        self.update_transport(|config| config.pool_idle_timeout = Some(timeout));
+    }
+
+    pub fn set_pool_max_idle_per_host(&mut self, max_idle: usize) {
+        self.update_transport(|config| config.pool_max_idle_per_host = Some(max_idle));
+    }
+
+    pub fn set_connect_timeout(&mut self, timeout: Duration) {
+        self.update_transport(|config| config.connect_timeout = Some(timeout));
+    }
+
+    pub fn set_read_timeout(&mut self, timeout: Duration) {
+        self.update_transport(|config| config.read_timeout = Some(timeout));
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.update_transport(|config| config.timeout = Some(timeout));
+    }
+
+    pub fn set_proxy(&mut self, proxy: Proxy) {
+        self.update_transport(|config| config.proxy = Some(proxy));
+    }
+
+    #[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+    pub fn set_tls_config(&mut self, tls: &TlsConfig) -> Result<(), Error> {
+        let tls = Arc::new(tls.build()?);
+        self.update_transport(|config| config.tls = Some(tls));
+        Ok(())
+    }
+
+    // Transport settings are baked into the pooled clients, so changing them starts a new pool.
+    fn update_transport(&mut self, update: impl FnOnce(&mut TransportConfig)) {
+        let mut config = self.transport.config().clone();
+        update(&mut config);
+        self.transport = Arc::new(Transport::new(config));
+    }
 }
 
 #[cfg(test)]
@@ -87,4 +344,85 @@ mod tests {
         assert_eq!(oss.custom_domain.as_deref(), Some("example.com"));
         assert_eq!(oss.security_token.as_deref(), Some("token"));
     }
+
+    #[test]
+    fn test_base_url_addressing_styles() {
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        assert_eq!(oss.base_url(), "https://oss-cn-hangzhou.aliyuncs.com/");
+        oss.set_bucket("bucket");
+        assert_eq!(oss.base_url(), "https://bucket.oss-cn-hangzhou.aliyuncs.com/");
+        oss.set_addressing_style(AddressingStyle::Path);
+        oss.set_endpoint("127.0.0.1:9000");
+        oss.set_https(false);
+        assert_eq!(oss.base_url(), "http://127.0.0.1:9000/bucket/");
+        oss.set_custom_domain("cdn.example.com");
+        assert_eq!(oss.base_url(), "http://cdn.example.com/");
+    }
+
+    #[test]
+    fn test_endpoint_kind_and_region_cache() {
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        assert_eq!(oss.endpoint_kind, Some(EndpointKind::Public));
+        oss.set_endpoint_kind(EndpointKind::Internal);
+        assert_eq!(oss.endpoint.as_ref(), "oss-cn-hangzhou-internal.aliyuncs.com");
+        assert!(oss.needs_region_lookup(EndpointKind::Internal));
+        assert!(!oss.needs_region_lookup(EndpointKind::Dualstack));
+
+        oss.clone().cache_region_endpoints("oss-cn-hangzhou", "public.example.com", "internal.example.com", "");
+        assert!(!oss.needs_region_lookup(EndpointKind::Internal));
+        assert!(oss.needs_region_lookup(EndpointKind::Accelerate));
+        oss.set_endpoint_kind(EndpointKind::Internal);
+        assert_eq!(oss.endpoint.as_ref(), "internal.example.com");
+        oss.set_endpoint("127.0.0.1:9000");
+        assert_eq!(oss.endpoint_kind, None);
+    }
+
+    #[test]
+    fn test_bucket_region_binding() {
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_bucket("bucket");
+        oss.region_discovery = true;
+        assert!(!oss.knows_bucket_region());
+        assert!(!oss.bind_bucket_region("oss-cn-hangzhou"));
+        assert!(oss.knows_bucket_region());
+        assert!(oss.bind_bucket_region("oss-cn-beijing"));
+        assert_eq!(oss.region.as_ref(), "cn-beijing");
+        assert_eq!(oss.endpoint.as_ref(), "oss-cn-beijing.aliyuncs.com");
+
+        let mut other = Oss::new("id", "secret", "cn-hangzhou");
+        other.bucket_regions = oss.bucket_regions.clone();
+        other.set_bucket("bucket");
+        other.set_endpoint("127.0.0.1:9000");
+        other.apply_bucket_region();
+        assert_eq!(other.region.as_ref(), "cn-hangzhou");
+        other.region_discovery = true;
+        other.apply_bucket_region();
+        assert_eq!(other.region.as_ref(), "cn-beijing");
+        assert_eq!(other.endpoint.as_ref(), "127.0.0.1:9000");
+    }
+
+    #[test]
+    fn test_transport_shared_between_clones() {
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        let cloned = oss.clone();
+        assert!(Arc::ptr_eq(&oss.transport, &cloned.transport));
+        oss.set_pool_idle_timeout(Duration::from_secs(15));
+        oss.set_pool_max_idle_per_host(4);
+        assert!(!Arc::ptr_eq(&oss.transport, &cloned.transport));
+        assert_eq!(oss.transport.config().pool_idle_timeout, Some(Duration::from_secs(15)));
+        assert_eq!(oss.transport.config().pool_max_idle_per_host, Some(4));
+        let bucket = oss.clone();
+        assert!(Arc::ptr_eq(&oss.transport, &bucket.transport));
+    }
+
+    #[test]
+    fn test_credentials_resolution() {
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_security_token("token");
+        assert_eq!(oss.credentials().unwrap(), Credentials::new("id", "secret").with_security_token("token"));
+        oss.set_credentials_provider(Arc::new(crate::credentials::StaticCredentialsProvider::new(Credentials::new(
+            "provided", "secret2",
+        ))));
+        assert_eq!(oss.credentials().unwrap().access_key_id(), "provided");
+    }
 }
@@ -0,0 +1,68 @@
+//! Detection of requests sent to or signed for a region other than the bucket's own.
+//!
+//! 识别发往或签名于 Bucket 所在地域之外的请求。
+use crate::{Error, OssErrorCode};
+use http::StatusCode;
+
+/// Statuses OSS answers with when a bucket is addressed through the wrong region.
+///
+/// OSS 在通过错误地域访问 Bucket 时返回的状态码。
+pub(crate) fn is_region_status(status: StatusCode) -> bool {
+    matches!(status, StatusCode::MOVED_PERMANENTLY | StatusCode::BAD_REQUEST | StatusCode::FORBIDDEN)
+}
+
+/// Whether OSS may have rejected the request because of the region it was sent to or signed for.
+///
+/// OSS 是否可能因请求的目标地域或签名地域不正确而拒绝了请求。
+pub(crate) fn is_wrong_region(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::OssError(status, oss_error) if is_region_status(*status)
+            && matches!(
+                oss_error.error_code(),
+                OssErrorCode::PermanentRedirect | OssErrorCode::AccessDenied | OssErrorCode::SignatureDoesNotMatch
+            )
+    )
+}
+
+/// Region named by the `Endpoint` of a redirect error, e.g. `cn-beijing` for
+/// `oss-cn-beijing.aliyuncs.com` or its internal variant.
+///
+/// 重定向错误中 `Endpoint` 所指的地域，例如 `oss-cn-beijing.aliyuncs.com` 或其内网地址对应
+/// `cn-beijing`。
+pub(crate) fn region_hint(error: &Error) -> Option<String> {
+    let Error::OssError(_, oss_error) = error else {
+        return None;
+    };
+    let host = oss_error.endpoint.as_deref()?.split('.').next()?;
+    let region = host.strip_prefix("oss-")?;
+    let region = region.strip_suffix("-internal").unwrap_or(region);
+    (!region.is_empty() && !region.starts_with("accelerate")).then(|| region.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oss_error(status: StatusCode, body: &str) -> Error {
+        Error::OssError(status, serde_xml_rs::from_reader(body.as_bytes()).unwrap())
+    }
+
+    #[test]
+    fn test_wrong_region_errors() {
+        let redirect = oss_error(
+            StatusCode::MOVED_PERMANENTLY,
+            "<Error><Code>PermanentRedirect</Code><Message>m</Message><RequestId>r</RequestId><EC>0003-00000001</EC>\
+             <Endpoint>oss-cn-beijing-internal.aliyuncs.com</Endpoint></Error>",
+        );
+        assert!(is_wrong_region(&redirect));
+        assert_eq!(region_hint(&redirect).as_deref(), Some("cn-beijing"));
+
+        let denied = oss_error(StatusCode::FORBIDDEN, &crate::test_util::oss_error_body("AccessDenied"));
+        assert!(is_wrong_region(&denied));
+        assert_eq!(region_hint(&denied), None);
+
+        let missing = oss_error(StatusCode::NOT_FOUND, &crate::test_util::oss_error_body("NoSuchBucket"));
+        assert!(!is_wrong_region(&missing));
+    }
+}
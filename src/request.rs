@@ -1,35 +1,35 @@
 use crate::{
-    Error,
-    common::{url_encode, url_encode_path},
+    Credentials, Error,
+    common::{SignatureVersion, format_gmt, sha256_hex, signature_v4, url_encode, url_encode_path},
+    signature_v1,
+    signer::{self, ALGORITHM, UNSIGNED_PAYLOAD},
 };
-use aws_lc_rs::{digest, hmac};
 use bytes::Bytes;
-use http::{Method, header};
+use http::{Method, StatusCode, header};
 use http_body::Body as HttpBody;
-use http_body_util::{BodyExt, Empty, combinators::BoxBody};
-use hyper::Request;
-#[cfg(feature = "_async-rustls")]
-use hyper_rustls::HttpsConnectorBuilder;
-#[cfg(feature = "async-native-tls")]
-use hyper_tls::HttpsConnector;
-use hyper_util::client::legacy::{Client, ResponseFuture};
-use hyper_util::rt::TokioExecutor;
+use http_body_util::{BodyExt, Full};
+use hyper::{Request, Response};
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::error::Error as StdError;
+use std::time::Duration;
 use time::OffsetDateTime;
+use tokio::time::Instant;
 
 pub(crate) use crate::oss::Oss;
+use crate::{
+    clock::is_time_skewed,
+    error::normal_error,
+    region::{is_region_status, is_wrong_region, region_hint},
+    trace::OperationTrace,
+    transport::{AsyncBody, ResponseBody},
+};
 
 #[cfg(all(feature = "_async-rustls", feature = "async-native-tls"))]
-compile_error!(
-    "Async TLS backend conflict: enable exactly one of `async` (default, rustls) or `async-native-tls`."
-);
+compile_error!("Async TLS backend conflict: enable exactly one of `async` (default, rustls) or `async-native-tls`.");
 
 #[cfg(all(feature = "_async-base", not(any(feature = "_async-rustls", feature = "async-native-tls"))))]
-compile_error!(
-    "Async TLS backend missing: enable `async` (default, rustls) or `async-native-tls`."
-);
+compile_error!("Async TLS backend missing: enable `async` (default, rustls) or `async-native-tls`.");
 
 /// Builder for requests sent to OSS.
 ///
@@ -40,20 +40,34 @@ pub(crate) struct OssRequest {
     method: Method,
     headers: HashMap<String, String>,
     queries: HashMap<String, String>,
-    body: BoxBody<Bytes, Box<dyn StdError + Send + Sync>>,
+    body: RequestBody,
+    timeout: Option<Duration>,
+    operation: &'static str,
+}
+
+/// Request payload; in-memory bodies can be replayed when a request is retried.
+///
+/// 请求体；内存中的请求体可在重试时重放。
+#[derive(Debug)]
+enum RequestBody {
+    Bytes(Bytes),
+    Stream(Option<AsyncBody>),
 }
 
 impl OssRequest {
     /// Create a new request builder with default empty headers, queries and body.
     ///
     /// 创建请求构建器，默认头、查询参数和请求体为空。
-    pub fn new(oss: Oss, method: Method) -> Self {
+    pub fn new(mut oss: Oss, method: Method, operation: &'static str) -> Self {
+        oss.apply_bucket_region();
         OssRequest {
             oss,
             method,
             headers: HashMap::with_capacity(10),
             queries: HashMap::with_capacity(10),
-            body: Empty::<Bytes>::new().map_err(|never| -> Box<dyn StdError + Send + Sync> { match never {} }).boxed(),
+            body: RequestBody::Bytes(Bytes::new()),
+            timeout: None,
+            operation,
         }
     }
 
@@ -73,6 +87,33 @@ impl OssRequest {
         self
     }
 
+    /// Sign the SHA-256 of the body instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// In-memory bodies are hashed when the request is sent; streaming bodies need
+    /// `set_payload_hash` to be called beforehand.
+    ///
+    /// 对请求体的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    ///
+    /// 内存请求体在发送时计算哈希；流式请求体需事先调用 `set_payload_hash`。
+    pub fn set_signed_payload(&mut self, enable: bool) -> &mut Self {
+        self.oss.signed_payload = enable;
+        self
+    }
+
+    /// Whether the body's SHA-256 is signed; V1 signatures never cover the payload.
+    ///
+    /// 是否对请求体的 SHA-256 签名；V1 签名从不覆盖请求体。
+    pub(crate) fn signs_payload(&self) -> bool {
+        self.oss.signed_payload && self.oss.signature_version == SignatureVersion::V4
+    }
+
+    /// Set the hex-encoded SHA-256 of a streaming body, computed ahead of time.
+    ///
+    /// 设置预先计算的流式请求体 SHA-256（十六进制）。
+    pub fn set_payload_hash(&mut self, hash: impl Into<String>) -> &mut Self {
+        self.insert_header("x-oss-content-sha256", hash)
+    }
+
     /// Insert a header key/value pair.
     ///
     /// 插入请求头键值对。
@@ -89,31 +130,35 @@ impl OssRequest {
         self
     }
 
-    /// Set the request body.
+    /// Override the overall deadline configured on the client for this request.
     ///
-    /// 设置请求体。
-    pub fn set_body<B>(&mut self, body: B) -> &mut Self
+    /// 为本次请求覆盖客户端配置的整体截止时间。
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set an in-memory request body.
+    ///
+    /// 设置内存请求体。
+    pub fn set_body(&mut self, body: impl Into<Bytes>) -> &mut Self {
+        self.body = RequestBody::Bytes(body.into());
+        self
+    }
+
+    /// Set a streaming request body; streaming requests are never retried.
+    ///
+    /// 设置流式请求体；流式请求不会重试。
+    pub fn set_stream_body<B>(&mut self, body: B) -> &mut Self
     where
         B: HttpBody<Data = Bytes> + Send + Sync + 'static,
         B::Error: Into<Box<dyn StdError + Send + Sync>>,
     {
-        self.body = body.map_err(Into::into).boxed();
+        self.body = RequestBody::Stream(Some(body.map_err(Into::into).boxed()));
         self
     }
 
     pub fn uri(&self) -> String {
-        // protocol
-        let protocol = if self.oss.enable_https { "https://" } else { "http://" };
-        // host
-        let host = if let Some(custom_domain) = self.oss.custom_domain.clone() {
-            custom_domain.to_string()
-        } else {
-            format!(
-                "{}{}",
-                self.oss.bucket.clone().map(|v| format!("{}.", v)).unwrap_or_else(|| String::new()),
-                self.oss.endpoint
-            )
-        };
         // query string
         let query = self
             .queries
@@ -128,54 +173,65 @@ impl OssRequest {
         let query_str = if query.is_empty() { String::new() } else { format!("?{}", query) };
         // build final url
         format!(
-            "{}{}/{}{}",
-            protocol,
-            host,
+            "{}{}{}",
+            self.oss.base_url(),
             url_encode_path(&self.oss.object.clone().unwrap_or_else(|| String::new().into())),
             query_str
         )
     }
-    pub fn query_sign(&mut self, expires: OffsetDateTime) {
-        let now = OffsetDateTime::now_utc();
-        let expires = (expires - now).whole_seconds().max(1);
-        let date = format_oss_date(now);
-        let date_short = format_oss_date_short(now);
+    /// Sign the request into query parameters, resolving credentials first.
+    ///
+    /// 将签名写入查询参数，签名前先解析凭证。
+    pub fn query_sign(&mut self, expires: OffsetDateTime) -> Result<(), Error> {
+        let credentials = self.oss.credentials()?;
+        let credentials = &credentials;
+        // expiry is measured on the local clock, the signing time follows the server clock
+        let expires = (expires - OffsetDateTime::now_utc()).whole_seconds().max(1);
+        if self.oss.signature_version == SignatureVersion::V1 {
+            self.query_sign_v1(credentials, expires);
+            return Ok(());
+        }
+        let now = self.oss.clock.now();
+        let date = signer::format_date(now);
+        let date_short = signer::format_date_short(now);
         let region = self.oss.region.to_string();
 
-        self.insert_query("x-oss-signature-version", "OSS4-HMAC-SHA256");
-        self.insert_query(
-            "x-oss-credential",
-            format!("{}/{}/{}/oss/aliyun_v4_request", self.oss.ak_id, date_short, region),
-        );
+        self.insert_query("x-oss-signature-version", ALGORITHM);
+        self.insert_query("x-oss-credential", signer::credential(credentials, &date_short, &region));
         self.insert_query("x-oss-date", &date);
         self.insert_query("x-oss-expires", expires.to_string());
-        if let Some(token) = self.oss.security_token.clone() {
+        if let Some(token) = credentials.security_token() {
             self.insert_query("x-oss-security-token", token);
         }
 
-        let additional_headers = additional_headers_v4(&self.headers);
+        let additional_headers = signer::additional_headers(self.headers.keys().map(String::as_str));
         if !additional_headers.is_empty() {
             self.insert_query("x-oss-additional-headers", additional_headers.join(";"));
         }
 
-        let canonical_request = self.canonical_request_v4(&additional_headers);
-        let signature = self.signature_v4(&canonical_request, &date, &date_short, &region);
+        let signature = self.signature_v4(credentials, &additional_headers, &date, &date_short, &region);
         self.insert_query("x-oss-signature", signature);
+        Ok(())
     }
-    pub fn header_sign(&mut self) {
-        let now = OffsetDateTime::now_utc();
-        let date = format_oss_date(now);
-        let date_short = format_oss_date_short(now);
+    pub fn header_sign(&mut self, credentials: &Credentials) {
+        if self.oss.signature_version == SignatureVersion::V1 {
+            self.header_sign_v1(credentials);
+            return;
+        }
+        let now = self.oss.clock.now();
+        let date = signer::format_date(now);
+        let date_short = signer::format_date_short(now);
         let region = self.oss.region.to_string();
 
         self.insert_header("x-oss-date", &date);
-        self.insert_header("x-oss-content-sha256", "UNSIGNED-PAYLOAD");
+        if !self.headers.contains_key("x-oss-content-sha256") {
+            self.insert_header("x-oss-content-sha256", UNSIGNED_PAYLOAD);
+        }
 
-        let additional_headers = additional_headers_v4(&self.headers);
-        let canonical_request = self.canonical_request_v4(&additional_headers);
-        let signature = self.signature_v4(&canonical_request, &date, &date_short, &region);
+        let additional_headers = signer::additional_headers(self.headers.keys().map(String::as_str));
+        let signature = self.signature_v4(credentials, &additional_headers, &date, &date_short, &region);
 
-        let credential = format!("{}/{}/{}/oss/aliyun_v4_request", self.oss.ak_id, date_short, region);
+        let credential = signer::credential(credentials, &date_short, &region);
         let authorization = if additional_headers.is_empty() {
             format!("OSS4-HMAC-SHA256 Credential={},Signature={}", credential, signature)
         } else {
@@ -189,160 +245,278 @@ impl OssRequest {
         self.insert_header(header::AUTHORIZATION.as_str(), authorization);
     }
 
-    fn apply_security_token(&mut self) {
-        if let Some(security_token) = self.oss.security_token.clone() {
+    // V1 URL signing: `Expires` is an absolute timestamp on the server clock
+    fn query_sign_v1(&mut self, credentials: &Credentials, expires_in: i64) {
+        let expires = (self.oss.clock.now().unix_timestamp() + expires_in).to_string();
+        if let Some(token) = credentials.security_token() {
+            self.insert_query("security-token", token);
+        }
+        let string_to_sign = signature_v1::string_to_sign(
+            &self.method,
+            &self.headers,
+            &self.queries,
+            self.oss.bucket.as_deref(),
+            self.oss.object.as_deref(),
+            &expires,
+        );
+        self.insert_query("OSSAccessKeyId", credentials.access_key_id());
+        self.insert_query("Expires", expires);
+        self.insert_query("Signature", signature_v1::signature(credentials.access_key_secret(), &string_to_sign));
+    }
+
+    fn header_sign_v1(&mut self, credentials: &Credentials) {
+        let date = format_gmt(self.oss.clock.now());
+        self.insert_header(header::DATE.as_str(), &date);
+        let string_to_sign = signature_v1::string_to_sign(
+            &self.method,
+            &self.headers,
+            &self.queries,
+            self.oss.bucket.as_deref(),
+            self.oss.object.as_deref(),
+            &date,
+        );
+        let signature = signature_v1::signature(credentials.access_key_secret(), &string_to_sign);
+        self.insert_header(
+            header::AUTHORIZATION.as_str(),
+            format!("OSS {}:{}", credentials.access_key_id(), signature),
+        );
+    }
+
+    fn apply_security_token(&mut self, credentials: &Credentials) {
+        if let Some(security_token) = credentials.security_token() {
             self.insert_header("x-oss-security-token", security_token);
         }
     }
 
-    pub fn send_to_oss(mut self) -> Result<ResponseFuture, Error> {
-        // insert temporary security token if provided
-        self.apply_security_token();
-        // ensure required V4 headers exist before signing
-        if !self.headers.contains_key("x-oss-content-sha256") {
-            self.insert_header("x-oss-content-sha256", "UNSIGNED-PAYLOAD");
+    fn take_body(&mut self) -> AsyncBody {
+        match &mut self.body {
+            RequestBody::Bytes(bytes) => Full::new(bytes.clone()).map_err(|never| match never {}).boxed(),
+            RequestBody::Stream(stream) => {
+                stream.take().unwrap_or_else(|| Full::new(Bytes::new()).map_err(|never| match never {}).boxed())
+            }
         }
+    }
+
+    fn build_request(&mut self, credentials: &Credentials) -> Result<Request<AsyncBody>, Error> {
+        // insert temporary security token if provided
+        self.apply_security_token(credentials);
         // sign headers
-        self.header_sign();
+        self.header_sign(credentials);
         // build http request
-        let mut req = Request::builder().method(&self.method).uri(&self.uri());
+        let mut req = Request::builder().method(&self.method).uri(self.uri());
         for (key, value) in self.headers.iter() {
             req = req.header(key, value);
         }
-        let request = req.body(self.body)?;
-        if self.oss.enable_https {
-            #[cfg(feature = "async-native-tls")]
-            let https = HttpsConnector::new();
-            #[cfg(feature = "_async-rustls")]
-            let https = HttpsConnectorBuilder::new().with_webpki_roots().https_or_http().enable_http1().build();
-            let client: Client<_, BoxBody<Bytes, Box<dyn StdError + Send + Sync>>> =
-                Client::builder(TokioExecutor::new()).build(https);
-            Ok(client.request(request))
-        } else {
-            let client: Client<_, BoxBody<Bytes, Box<dyn StdError + Send + Sync>>> =
-                Client::builder(TokioExecutor::new()).build_http();
-            Ok(client.request(request))
-        }
+        Ok(req.body(self.take_body())?)
     }
-}
-
-fn format_oss_date(datetime: OffsetDateTime) -> String {
-    datetime
-        .format(&time::format_description::parse("[year][month][day]T[hour][minute][second]Z").expect("valid format"))
-        .expect("formatting")
-}
-
-fn format_oss_date_short(datetime: OffsetDateTime) -> String {
-    datetime.format(&time::format_description::parse("[year][month][day]").expect("valid format")).expect("formatting")
-}
 
-fn additional_headers_v4(headers: &HashMap<String, String>) -> Vec<String> {
-    let mut list = Vec::new();
-    for key in headers.keys() {
-        let lower = key.to_ascii_lowercase();
-        if lower == "content-type" || lower == "content-md5" || lower.starts_with("x-oss-") || lower == "authorization"
-        {
-            continue;
+    /// Sign and send the request, retrying according to the client's `RetryPolicy`.
+    ///
+    /// Non-success responses are returned as-is on the last attempt; when a retry is still possible
+    /// they are decoded so the policy can classify them, and the decoded error is returned if the
+    /// policy declines to retry.
+    ///
+    /// The overall deadline covers every attempt and the response body. The read timeout bounds the
+    /// wait between response body chunks, and also the wait for response headers when the request
+    /// body is held in memory.
+    ///
+    /// Every response `Date` header updates the client's clock offset, and a `RequestTimeTooSkewed`
+    /// rejection of an in-memory request is re-signed and sent once more outside the retry budget.
+    ///
+    /// 签名并发送请求，按客户端的 `RetryPolicy` 进行重试。
+    ///
+    /// 最后一次尝试的非成功响应原样返回；仍可重试时会先解析错误以供策略判断，
+    /// 若策略不重试则直接返回解析后的错误。
+    ///
+    /// 整体截止时间覆盖所有尝试与响应体读取。读取超时限制响应体数据块之间的等待时间，
+    /// 请求体在内存中时也限制等待响应头的时间。
+    ///
+    /// 每个响应的 `Date` 头都会更新客户端的时钟偏差；内存请求体的请求若因 `RequestTimeTooSkewed`
+    /// 被拒绝，会重新签名并额外发送一次，不计入重试次数。
+    pub async fn send_to_oss(mut self) -> Result<Response<ResponseBody>, Error> {
+        // ensure required V4 headers exist before signing
+        if self.oss.signature_version == SignatureVersion::V4 && !self.headers.contains_key("x-oss-content-sha256") {
+            let payload_hash = match &self.body {
+                RequestBody::Bytes(bytes) if self.signs_payload() => sha256_hex(bytes),
+                _ => "UNSIGNED-PAYLOAD".to_string(),
+            };
+            self.insert_header("x-oss-content-sha256", payload_hash);
         }
-        list.push(lower);
+        let trace = self.trace();
+        let mut sends = 0;
+        let result = trace.instrument(self.send_attempts(&trace, &mut sends)).await;
+        trace.finish(sends, &result);
+        result
     }
-    list.sort();
-    list
-}
 
-fn hex_encode(bytes: &[u8]) -> String {
-    bytes.iter().map(|b| format!("{:02x}", b)).collect()
-}
+    fn trace(&self) -> OperationTrace {
+        let bytes_sent = match &self.body {
+            RequestBody::Bytes(bytes) => Some(bytes.len() as u64),
+            RequestBody::Stream(_) => self
+                .headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+                .and_then(|(_, value)| value.parse().ok()),
+        };
+        OperationTrace::new(
+            self.operation,
+            &self.method,
+            self.oss.bucket.as_deref(),
+            self.oss.object.as_deref(),
+            bytes_sent,
+        )
+    }
 
-impl OssRequest {
-    fn canonical_query_v4(&self) -> String {
-        let mut items: Vec<(String, String)> =
-            self.queries.iter().map(|(k, v)| (url_encode(k), url_encode(v))).collect();
-        items.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
-        let mut out = String::new();
-        for (key, value) in items {
-            if !out.is_empty() {
-                out.push('&');
+    async fn send_attempts(
+        &mut self,
+        trace: &OperationTrace,
+        sends: &mut u32,
+    ) -> Result<Response<ResponseBody>, Error> {
+        let config = self.oss.transport.config();
+        let read_timeout = config.read_timeout;
+        let deadline = self.timeout.or(config.timeout).map(|timeout| Instant::now() + timeout);
+        let policy = self.oss.retry_policy.clone();
+        let replayable = matches!(self.body, RequestBody::Bytes(_));
+        let max_attempts = policy.attempts_for(&self.method, &self.headers, replayable);
+        let mut skew_retry = replayable;
+        let mut region_retry = replayable && self.oss.follows_bucket_region();
+        let mut attempt = 1;
+        loop {
+            *sends += 1;
+            let credentials = self.oss.credentials_async().await?;
+            let request = self.build_request(&credentials)?;
+            let (request, head) = self.oss.interceptors.request(request);
+            let header_deadline = match (replayable.then_some(read_timeout).flatten(), deadline) {
+                (Some(read_timeout), Some(deadline)) => Some(deadline.min(Instant::now() + read_timeout)),
+                (Some(read_timeout), None) => Some(Instant::now() + read_timeout),
+                (None, deadline) => deadline,
+            };
+            let pending = self.oss.transport.async_client().request(request);
+            let result = match header_deadline {
+                Some(at) => match tokio::time::timeout_at(at, pending).await {
+                    Ok(result) => result.map_err(client_error),
+                    Err(_) => Err(Error::Timeout),
+                },
+                None => pending.await.map_err(client_error),
+            };
+            let result = match result {
+                Ok(response) => Ok(self
+                    .oss
+                    .interceptors
+                    .response(head.as_ref(), response.map(|body| ResponseBody::new(body, read_timeout, deadline)))),
+                Err(e) => {
+                    self.oss.interceptors.error(head.as_ref(), &e);
+                    Err(e)
+                }
+            };
+            if let Ok(response) = &result {
+                self.oss.clock.observe(response.headers());
             }
-            out.push_str(&key);
-            if !value.is_empty() {
-                out.push('=');
-                out.push_str(&value);
+            let error = match result {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response)
+                    if attempt >= max_attempts
+                        && !(skew_retry && response.status() == StatusCode::FORBIDDEN)
+                        && !(region_retry && is_region_status(response.status())) =>
+                {
+                    return Ok(response);
+                }
+                Ok(response) => normal_error(response).await,
+                Err(e) if attempt >= max_attempts => return Err(e),
+                Err(e) => e,
+            };
+            // the clock offset has just been corrected, so re-sign and resend right away
+            if skew_retry && is_time_skewed(&error) {
+                skew_retry = false;
+                trace.retry(*sends, &error);
+                continue;
             }
-        }
-        out
-    }
-
-    fn canonical_uri_v4(&self) -> String {
-        let mut path = String::from("/");
-        if let Some(bucket) = self.oss.bucket.as_deref() {
-            if !bucket.is_empty() {
-                path.push_str(bucket);
-                path.push('/');
+            if region_retry && is_wrong_region(&error) {
+                region_retry = false;
+                if self.discover_bucket_region(&error).await {
+                    trace.retry(*sends, &error);
+                    continue;
+                }
+                return Err(error);
             }
-        }
-        if let Some(object) = self.oss.object.as_deref() {
-            if !object.is_empty() {
-                path.push_str(&url_encode_path(object));
+            if attempt >= max_attempts || !policy.should_retry(&error) {
+                return Err(error);
+            }
+            let delay = policy.backoff(attempt);
+            if deadline.is_some_and(|deadline| Instant::now() + delay >= deadline) {
+                return Err(error);
             }
+            trace.retry(*sends, &error);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
         }
-        if path.is_empty() { "/".to_string() } else { path }
     }
+}
 
-    fn canonical_headers_v4(&self, additional_headers: &[String]) -> String {
-        let mut pairs: Vec<(String, String)> = Vec::new();
-        for (key, value) in self.headers.iter() {
-            let lower = key.to_ascii_lowercase();
-            let include = lower == "content-type"
-                || lower == "content-md5"
-                || lower.starts_with("x-oss-")
-                || additional_headers.contains(&lower);
-            if include {
-                pairs.push((lower, value.trim().to_string()));
+impl OssRequest {
+    /// Find the bucket's region, from the redirect endpoint or else GetBucketLocation, and rebind to
+    /// it. Returns whether the request should be re-sent.
+    async fn discover_bucket_region(&mut self, error: &Error) -> bool {
+        let region = match region_hint(error) {
+            Some(region) => region,
+            // already looked up, so the rejection is not about the region
+            None if self.oss.knows_bucket_region() => return false,
+            None => {
+                let mut oss = self.oss.clone();
+                oss.region_discovery = false;
+                oss.object = None;
+                let bucket = crate::OssBucket { oss };
+                match Box::pin(bucket.get_bucket_location().send()).await {
+                    Ok(region) => region,
+                    Err(_) => return false,
+                }
             }
-        }
-        pairs.sort_by(|a, b| a.0.cmp(&b.0));
-        let mut out = String::new();
-        for (key, value) in pairs {
-            out.push_str(&key);
-            out.push(':');
-            out.push_str(&value);
-            out.push('\n');
-        }
-        out
+        };
+        self.oss.bind_bucket_region(&region)
     }
+}
 
-    fn canonical_request_v4(&self, additional_headers: &[String]) -> String {
-        let hashed_payload = self.headers.get("x-oss-content-sha256").map(|v| v.as_str()).unwrap_or("UNSIGNED-PAYLOAD");
-        format!(
-            "{}\n{}\n{}\n{}\n{}\n{}",
-            self.method,
-            self.canonical_uri_v4(),
-            self.canonical_query_v4(),
-            self.canonical_headers_v4(additional_headers),
-            additional_headers.join(";"),
-            hashed_payload
-        )
+// Connect timeouts surface from hyper as an I/O `TimedOut` error somewhere in the source chain.
+fn client_error(error: hyper_util::client::legacy::Error) -> Error {
+    let mut source = error.source();
+    while let Some(inner) = source {
+        if inner.downcast_ref::<std::io::Error>().is_some_and(|e| e.kind() == std::io::ErrorKind::TimedOut) {
+            return Error::Timeout;
+        }
+        source = inner.source();
     }
+    Error::HyperClientError(error)
+}
 
-    fn signature_v4(&self, canonical_request: &str, date: &str, date_short: &str, region: &str) -> String {
-        let hashed_request = hex_encode(digest::digest(&digest::SHA256, canonical_request.as_bytes()).as_ref());
-        let scope = format!("{}/{}/oss/aliyun_v4_request", date_short, region);
-        let string_to_sign = format!("OSS4-HMAC-SHA256\n{}\n{}\n{}", date, scope, hashed_request);
-
-        let key = format!("aliyun_v4{}", self.oss.ak_secret);
-        let k_date = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key.as_bytes()), date_short.as_bytes());
-        let k_region = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, k_date.as_ref()), region.as_bytes());
-        let k_service = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, k_region.as_ref()), b"oss");
-        let k_sign = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, k_service.as_ref()), b"aliyun_v4_request");
-
-        hex_encode(hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, k_sign.as_ref()), string_to_sign.as_bytes()).as_ref())
+impl OssRequest {
+    fn signature_v4(
+        &self,
+        credentials: &Credentials,
+        additional_headers: &[String],
+        date: &str,
+        date_short: &str,
+        region: &str,
+    ) -> String {
+        let hashed_payload = self.headers.get("x-oss-content-sha256").map(String::as_str).unwrap_or(UNSIGNED_PAYLOAD);
+        let canonical_request = signer::canonical_request(
+            self.method.as_str(),
+            self.oss.bucket.as_deref(),
+            self.oss.object.as_deref(),
+            self.queries.iter().map(|(key, value)| (key.as_str(), value.as_str())),
+            self.headers.iter().map(|(key, value)| (key.as_str(), value.as_str())),
+            additional_headers,
+            hashed_payload,
+        );
+        let string_to_sign = signer::string_to_sign(date, date_short, region, &canonical_request);
+        signature_v4(credentials.access_key_secret(), date_short, region, &string_to_sign)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::RetryPolicy;
+    use crate::test_util::{MockResponse, MockServer, oss_error_body};
     use time::OffsetDateTime;
 
     #[test]
@@ -350,16 +524,16 @@ mod tests {
         let mut oss = Oss::new("id", "secret", "cn-hangzhou");
         oss.set_bucket("bucket");
         oss.set_object("file.txt");
-        let req = OssRequest::new(oss, Method::GET);
+        let req = OssRequest::new(oss, Method::GET, "Test");
         assert_eq!(req.uri(), "https://bucket.oss-cn-hangzhou.aliyuncs.com/file.txt");
     }
 
     #[test]
     fn test_query_sign_inserts_signature() {
         let oss = Oss::new("id", "secret", "cn-hangzhou");
-        let mut req = OssRequest::new(oss, Method::GET);
+        let mut req = OssRequest::new(oss, Method::GET, "Test");
         let expires = OffsetDateTime::from_unix_timestamp(0).unwrap();
-        req.query_sign(expires);
+        req.query_sign(expires).unwrap();
         let uri = req.uri();
         assert!(uri.contains("x-oss-signature="));
         assert!(uri.contains("x-oss-credential=id%2F"));
@@ -370,8 +544,360 @@ mod tests {
     fn test_security_token_header_injected() {
         let mut oss = Oss::new("id", "secret", "cn-hangzhou");
         oss.set_security_token("token");
-        let mut req = OssRequest::new(oss, Method::GET);
-        req.apply_security_token();
+        let mut req = OssRequest::new(oss, Method::GET, "Test");
+        req.apply_security_token(&req.oss.credentials().unwrap());
         assert_eq!(req.headers.get("x-oss-security-token").map(|s| s.as_str()), Some("token"));
     }
+
+    fn mock_oss(server: &MockServer, policy: RetryPolicy) -> Oss {
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_custom_domain(server.host());
+        oss.set_https(false);
+        oss.set_retry_policy(policy.set_base_delay(std::time::Duration::from_millis(1)));
+        oss
+    }
+
+    #[test]
+    fn test_path_style_addressing() {
+        let server = MockServer::start(vec![MockResponse::new(200, "ok")]);
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_endpoint(server.host());
+        oss.set_https(false);
+        oss.set_addressing_style(crate::common::AddressingStyle::Path);
+        oss.set_bucket("bucket");
+        oss.set_object("dir/file name.txt");
+        let mut req = OssRequest::new(oss, Method::GET, "Test");
+        req.insert_query("versionId", "v1");
+        assert_eq!(req.uri(), format!("http://{}/bucket/dir/file%20name.txt?versionId=v1", server.host()));
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let response = runtime.block_on(req.send_to_oss()).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let request = server.requests().pop().unwrap();
+        assert_eq!(request.target, "/bucket/dir/file%20name.txt?versionId=v1");
+        assert!(request.header("authorization").unwrap().starts_with("OSS4-HMAC-SHA256 "));
+    }
+
+    #[test]
+    fn test_region_discovery_rebinds_and_caches() {
+        let server = MockServer::start(vec![
+            MockResponse::new(403, oss_error_body("AccessDenied")),
+            MockResponse::new(200, "<LocationConstraint>oss-cn-beijing</LocationConstraint>"),
+            MockResponse::new(200, "ok"),
+            MockResponse::new(200, "ok"),
+        ]);
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_endpoint(server.host());
+        oss.set_https(false);
+        oss.set_addressing_style(crate::common::AddressingStyle::Path);
+        oss.set_bucket("bucket");
+        oss.region_discovery = true;
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let response = runtime.block_on(OssRequest::new(oss.clone(), Method::GET, "Test").send_to_oss()).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        runtime.block_on(OssRequest::new(oss, Method::GET, "Test").send_to_oss()).unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[1].target, "/bucket/?location");
+        assert!(requests[1].header("authorization").unwrap().contains("/cn-hangzhou/oss/"));
+        assert!(requests[2].header("authorization").unwrap().contains("/cn-beijing/oss/"));
+        assert!(requests[3].header("authorization").unwrap().contains("/cn-beijing/oss/"));
+    }
+
+    #[test]
+    fn test_region_discovery_remembers_same_region() {
+        let server = MockServer::start(vec![
+            MockResponse::new(403, oss_error_body("AccessDenied")),
+            MockResponse::new(200, "<LocationConstraint>oss-cn-hangzhou</LocationConstraint>"),
+            MockResponse::new(403, oss_error_body("AccessDenied")),
+        ]);
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_endpoint(server.host());
+        oss.set_https(false);
+        oss.set_addressing_style(crate::common::AddressingStyle::Path);
+        oss.set_bucket("bucket");
+        oss.region_discovery = true;
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        for _ in 0..2 {
+            let result = runtime.block_on(OssRequest::new(oss.clone(), Method::GET, "Test").send_to_oss());
+            assert!(result.unwrap_err().is_access_denied());
+        }
+
+        // the bucket is in the signing region, so the second rejection is returned without a lookup
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[1].target, "/bucket/?location");
+        assert_eq!(requests[2].target, "/bucket/");
+    }
+
+    #[test]
+    fn test_signature_v1_header_and_query() {
+        let server = MockServer::start(vec![MockResponse::new(200, "ok")]);
+        let mut oss = mock_oss(&server, RetryPolicy::none());
+        oss.set_signature_version(SignatureVersion::V1);
+        oss.set_bucket("bucket");
+        oss.set_object("dir/a.txt");
+        let mut req = OssRequest::new(oss.clone(), Method::PUT, "Test");
+        req.insert_header("x-oss-meta-author", "alice");
+        req.insert_query("acl", "");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(req.send_to_oss()).unwrap();
+        let request = server.requests().pop().unwrap();
+        assert!(request.header("x-oss-content-sha256").is_none());
+        let date = request.header("date").unwrap();
+        let expected = format!("PUT\n\n\n{}\nx-oss-meta-author:alice\n/bucket/dir/a.txt?acl", date);
+        assert_eq!(
+            request.header("authorization").unwrap(),
+            format!("OSS id:{}", signature_v1::signature("secret", &expected))
+        );
+
+        oss.set_security_token("token");
+        let mut req = OssRequest::new(oss, Method::GET, "Test");
+        req.query_sign(OffsetDateTime::now_utc() + time::Duration::minutes(5)).unwrap();
+        assert!(req.queries.contains_key("Signature"));
+        assert_eq!(req.queries.get("OSSAccessKeyId").map(String::as_str), Some("id"));
+        assert_eq!(req.queries.get("security-token").map(String::as_str), Some("token"));
+        let expires: i64 = req.queries["Expires"].parse().unwrap();
+        assert!(expires > OffsetDateTime::now_utc().unix_timestamp());
+        assert!(!req.queries.contains_key("x-oss-signature"));
+    }
+
+    #[test]
+    fn test_retry_replays_in_memory_body() {
+        let server = MockServer::start(vec![
+            MockResponse::new(503, oss_error_body("ServiceUnavailable")),
+            MockResponse::new(200, "ok"),
+        ]);
+        let mut req = OssRequest::new(mock_oss(&server, RetryPolicy::new(3)), Method::PUT, "Test");
+        req.set_body("payload");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let response = runtime.block_on(req.send_to_oss()).unwrap();
+        assert!(response.status().is_success());
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].body, b"payload");
+    }
+
+    #[test]
+    fn test_retry_skips_non_idempotent_and_non_retryable() {
+        let server = MockServer::start(vec![
+            MockResponse::new(503, oss_error_body("ServiceUnavailable")),
+            MockResponse::new(404, oss_error_body("NoSuchKey")),
+        ]);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let req = OssRequest::new(mock_oss(&server, RetryPolicy::new(3)), Method::POST, "Test");
+        let response = runtime.block_on(req.send_to_oss()).unwrap();
+        assert_eq!(response.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+        let req = OssRequest::new(mock_oss(&server, RetryPolicy::new(3)), Method::GET, "Test");
+        let error = runtime.block_on(req.send_to_oss()).unwrap_err();
+        assert!(matches!(error, Error::OssError(status, _) if status == http::StatusCode::NOT_FOUND));
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_forbid_overwrite_put_sent_once() {
+        let server = MockServer::start(vec![
+            MockResponse::new(503, oss_error_body("ServiceUnavailable")),
+            MockResponse::new(200, "ok"),
+        ]);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let mut req = OssRequest::new(mock_oss(&server, RetryPolicy::new(3)), Method::PUT, "PutObject");
+        req.insert_header("x-oss-forbid-overwrite", "true");
+        req.set_body(Bytes::from_static(b"data"));
+        let response = runtime.block_on(req.send_to_oss()).unwrap();
+        assert_eq!(response.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_read_and_overall_timeouts() {
+        let server = MockServer::start(vec![
+            MockResponse::new(200, "slow").delay(std::time::Duration::from_millis(500)),
+            MockResponse::new(200, "slow").delay(std::time::Duration::from_millis(500)),
+        ]);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let mut oss = mock_oss(&server, RetryPolicy::none());
+        oss.set_read_timeout(std::time::Duration::from_millis(100));
+        let error = runtime.block_on(OssRequest::new(oss, Method::GET, "Test").send_to_oss()).unwrap_err();
+        assert!(matches!(error, Error::Timeout));
+        let mut req = OssRequest::new(mock_oss(&server, RetryPolicy::none()), Method::GET, "Test");
+        req.set_timeout(std::time::Duration::from_millis(100));
+        let error = runtime.block_on(req.send_to_oss()).unwrap_err();
+        assert!(matches!(error, Error::Timeout));
+    }
+
+    #[test]
+    fn test_stalled_body_times_out_once() {
+        use futures_util::StreamExt;
+        let server =
+            MockServer::start(vec![MockResponse::new(200, "0123456789").stall(std::time::Duration::from_millis(500))]);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let mut oss = mock_oss(&server, RetryPolicy::none());
+        oss.set_read_timeout(std::time::Duration::from_millis(100));
+        let items: Vec<_> = runtime.block_on(async {
+            let response = OssRequest::new(oss, Method::GET, "Test").send_to_oss().await.unwrap();
+            response.into_body().into_data_stream().collect().await
+        });
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().unwrap().as_ref(), b"01234");
+        assert!(matches!(items[1], Err(Error::Timeout)));
+    }
+
+    #[test]
+    fn test_clock_skew_corrected_and_retried_once() {
+        let server_time = OffsetDateTime::now_utc() + time::Duration::hours(1);
+        let server = MockServer::start(vec![
+            MockResponse::new(403, oss_error_body("RequestTimeTooSkewed"))
+                .header("Date", crate::common::format_gmt(server_time)),
+            MockResponse::new(200, "ok"),
+        ]);
+        let oss = mock_oss(&server, RetryPolicy::none());
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let response = runtime.block_on(OssRequest::new(oss, Method::GET, "Test").send_to_oss()).unwrap();
+        assert!(response.status().is_success());
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        let signed = requests[1].header("x-oss-date").unwrap();
+        assert!(signed >= signer::format_date(server_time - time::Duration::seconds(5)).as_str());
+    }
+
+    #[test]
+    fn test_signed_payload_hashes_body() {
+        let server = MockServer::start(vec![MockResponse::new(200, "ok"), MockResponse::new(200, "ok")]);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let mut req = OssRequest::new(mock_oss(&server, RetryPolicy::none()), Method::PUT, "Test");
+        req.set_signed_payload(true).set_body("payload");
+        runtime.block_on(req.send_to_oss()).unwrap();
+        let mut req = OssRequest::new(mock_oss(&server, RetryPolicy::none()), Method::PUT, "Test");
+        req.set_body("payload");
+        runtime.block_on(req.send_to_oss()).unwrap();
+        let requests = server.requests();
+        assert_eq!(requests[0].header("x-oss-content-sha256"), Some(sha256_hex(b"payload").as_str()));
+        assert_eq!(requests[1].header("x-oss-content-sha256"), Some("UNSIGNED-PAYLOAD"));
+    }
+
+    #[test]
+    fn test_interceptors_see_each_attempt() {
+        let server = MockServer::start(vec![
+            MockResponse::new(503, oss_error_body("ServiceUnavailable")),
+            MockResponse::new(200, "ok"),
+        ]);
+        let interceptor = std::sync::Arc::new(crate::test_util::RecordingInterceptor::default());
+        let mut oss = mock_oss(&server, RetryPolicy::new(2));
+        oss.add_interceptor(interceptor.clone());
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(OssRequest::new(oss, Method::GET, "Test").send_to_oss()).unwrap();
+        assert_eq!(*interceptor.statuses.lock().unwrap(), vec![503, 200]);
+        assert!(server.requests().iter().all(|r| r.header("x-intercepted") == Some("1")));
+    }
+
+    #[test]
+    fn test_http_proxy() {
+        let server = MockServer::start(vec![MockResponse::new(200, "ok"), MockResponse::new(200, "ok")]);
+        let proxy = crate::test_util::ConnectProxy::start();
+        let mut oss = mock_oss(&server, RetryPolicy::none());
+        oss.set_proxy(crate::Proxy::http(&proxy.url()).unwrap().set_auth("user", "pass"));
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let response = runtime.block_on(OssRequest::new(oss.clone(), Method::GET, "Test").send_to_oss()).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let tunnels = proxy.requests();
+        assert_eq!(tunnels[0].method, "CONNECT");
+        assert_eq!(tunnels[0].target, server.host());
+        assert_eq!(tunnels[0].header("proxy-authorization"), Some("Basic dXNlcjpwYXNz"));
+
+        oss.set_proxy(crate::Proxy::http(&proxy.url()).unwrap().set_no_proxy("127.0.0.1"));
+        runtime.block_on(OssRequest::new(oss, Method::GET, "Test").send_to_oss()).unwrap();
+        assert_eq!(proxy.requests().len(), 1);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_custom_tls_roots_and_client_certificate() {
+        use crate::test_util::{TEST_CA_PEM, TEST_CLIENT_CERT_PEM, TEST_CLIENT_KEY_PEM, tls_server_config};
+        use crate::{TlsConfig, TlsVersion};
+        let server = MockServer::start_tls(
+            vec![MockResponse::new(200, "ok"), MockResponse::new(200, "ok")],
+            tls_server_config(true, rustls::ALL_VERSIONS),
+        );
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_custom_domain(server.host());
+        oss.set_retry_policy(RetryPolicy::none());
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        // the test CA is not a public root
+        assert!(runtime.block_on(OssRequest::new(oss.clone(), Method::GET, "Test").send_to_oss()).is_err());
+
+        let tls = TlsConfig::new()
+            .set_root_certificates_pem(TEST_CA_PEM)
+            .unwrap()
+            .set_client_identity_pem(TEST_CLIENT_CERT_PEM, TEST_CLIENT_KEY_PEM)
+            .unwrap();
+        // native-tls cannot require TLS 1.3, and the sync native-tls backend no minimum at all
+        let min_version = match (cfg!(feature = "async-native-tls"), cfg!(feature = "sync-native-tls")) {
+            (_, true) => None,
+            (true, false) => Some(TlsVersion::Tls12),
+            (false, false) => Some(TlsVersion::Tls13),
+        };
+        let tls = match min_version {
+            Some(version) => tls.set_min_version(version),
+            None => tls,
+        };
+        oss.set_tls_config(&tls).unwrap();
+        let response = runtime.block_on(OssRequest::new(oss, Method::GET, "Test").send_to_oss()).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let request = server.requests().pop().unwrap();
+        assert!(request.client_certificate);
+        if min_version == Some(TlsVersion::Tls13) {
+            assert_eq!(request.tls_version.as_deref(), Some("TLSv1_3"));
+        }
+    }
+
+    #[cfg(all(feature = "_async-rustls", not(feature = "sync-native-tls")))]
+    #[test]
+    fn test_min_tls_version_enforced() {
+        use crate::{TlsConfig, TlsVersion};
+        let server = MockServer::start_tls(
+            vec![MockResponse::new(200, "ok")],
+            crate::test_util::tls_server_config(false, &[&rustls::version::TLS12]),
+        );
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_custom_domain(server.host());
+        oss.set_retry_policy(RetryPolicy::none());
+        let tls = TlsConfig::new().set_root_certificates_pem(crate::test_util::TEST_CA_PEM).unwrap();
+        oss.set_tls_config(&tls.set_min_version(TlsVersion::Tls13)).unwrap();
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        assert!(runtime.block_on(OssRequest::new(oss, Method::GET, "Test").send_to_oss()).is_err());
+        assert!(server.requests().is_empty());
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_span_records_operation() {
+        let server = MockServer::start(vec![
+            MockResponse::new(503, oss_error_body("ServiceUnavailable")),
+            MockResponse::new(200, "ok").header("x-oss-request-id", "rid-2"),
+        ]);
+        let mut oss = mock_oss(&server, RetryPolicy::new(2));
+        oss.set_bucket("bucket");
+        oss.set_object("dir/key");
+        let recorder = crate::test_util::SpanRecorder::default();
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        let mut req = OssRequest::new(oss, Method::PUT, "PutObject");
+        req.set_body(Bytes::from_static(b"hello"));
+        runtime.block_on(req.send_to_oss()).unwrap();
+
+        let field = |name| recorder.field("oss.request", name);
+        assert_eq!(field("operation").as_deref(), Some("PutObject"));
+        assert_eq!(field("bucket").as_deref(), Some("bucket"));
+        assert_eq!(field("key").as_deref(), Some("dir/key"));
+        assert_eq!(field("method").as_deref(), Some("PUT"));
+        assert_eq!(field("status").as_deref(), Some("200"));
+        assert_eq!(field("request_id").as_deref(), Some("rid-2"));
+        assert_eq!(field("bytes_sent").as_deref(), Some("5"));
+        assert_eq!(field("bytes_received").as_deref(), Some("2"));
+        assert_eq!(field("attempts").as_deref(), Some("2"));
+        assert!(field("latency_ms").is_some());
+        let recorded = format!("{:?}", recorder.spans.lock().unwrap());
+        assert!(!recorded.contains("secret") && !recorded.contains("OSS4-HMAC-SHA256"));
+    }
 }
@@ -1,24 +1,26 @@
 use crate::{
-    Error,
-    common::{url_encode, url_encode_path},
+    Credentials, Error,
+    clock::is_time_skewed,
+    common::{SignatureVersion, format_gmt, sha256_hex, signature_v4, url_encode, url_encode_path},
+    error::normal_error_sync,
+    region::{is_region_status, is_wrong_region, region_hint},
+    signature_v1,
+    signer::{self, ALGORITHM, UNSIGNED_PAYLOAD},
+    trace::OperationTrace,
 };
-use aws_lc_rs::{digest, hmac};
-use http::{Method, header};
+use http::{Method, StatusCode, header};
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use time::OffsetDateTime;
 use ureq::{self, AsSendBody, Body};
 
 pub(crate) use crate::oss::Oss;
 
 #[cfg(all(feature = "_sync-rustls", feature = "sync-native-tls"))]
-compile_error!(
-    "Sync TLS backend conflict: enable exactly one of `sync` (default, rustls) or `sync-native-tls`."
-);
+compile_error!("Sync TLS backend conflict: enable exactly one of `sync` (default, rustls) or `sync-native-tls`.");
 
 #[cfg(all(feature = "_sync-base", not(any(feature = "_sync-rustls", feature = "sync-native-tls"))))]
-compile_error!(
-    "Sync TLS backend missing: enable `sync` (default, rustls) or `sync-native-tls`."
-);
+compile_error!("Sync TLS backend missing: enable `sync` (default, rustls) or `sync-native-tls`.");
 
 /// Builder for synchronous OSS requests.
 ///
@@ -30,14 +32,25 @@ pub(crate) struct OssRequest {
     headers: HashMap<String, String>,
     queries: HashMap<String, String>,
     body: Vec<u8>,
+    timeout: Option<Duration>,
+    operation: &'static str,
 }
 
 impl OssRequest {
     /// Create a new request builder.
     ///
     /// 创建请求构建器。
-    pub fn new(oss: Oss, method: Method) -> Self {
-        OssRequest { oss, method, headers: HashMap::new(), queries: HashMap::new(), body: Vec::new() }
+    pub fn new(mut oss: Oss, method: Method, operation: &'static str) -> Self {
+        oss.apply_bucket_region();
+        OssRequest {
+            oss,
+            method,
+            headers: HashMap::new(),
+            queries: HashMap::new(),
+            body: Vec::new(),
+            timeout: None,
+            operation,
+        }
     }
 
     /// Override the endpoint used for the request.
@@ -56,6 +69,33 @@ impl OssRequest {
         self
     }
 
+    /// Sign the SHA-256 of the body instead of sending `UNSIGNED-PAYLOAD`.
+    ///
+    /// In-memory bodies are hashed when the request is sent; streaming bodies need
+    /// `set_payload_hash` to be called beforehand.
+    ///
+    /// 对请求体的 SHA-256 进行签名，而不是发送 `UNSIGNED-PAYLOAD`。
+    ///
+    /// 内存请求体在发送时计算哈希；流式请求体需事先调用 `set_payload_hash`。
+    pub fn set_signed_payload(&mut self, enable: bool) -> &mut Self {
+        self.oss.signed_payload = enable;
+        self
+    }
+
+    /// Whether the body's SHA-256 is signed; V1 signatures never cover the payload.
+    ///
+    /// 是否对请求体的 SHA-256 签名；V1 签名从不覆盖请求体。
+    pub(crate) fn signs_payload(&self) -> bool {
+        self.oss.signed_payload && self.oss.signature_version == SignatureVersion::V4
+    }
+
+    /// Set the hex-encoded SHA-256 of a streaming body, computed ahead of time.
+    ///
+    /// 设置预先计算的流式请求体 SHA-256（十六进制）。
+    pub fn set_payload_hash(&mut self, hash: impl Into<String>) -> &mut Self {
+        self.insert_header("x-oss-content-sha256", hash)
+    }
+
     /// Insert a header key/value pair.
     ///
     /// 插入请求头键值对。
@@ -72,6 +112,14 @@ impl OssRequest {
         self
     }
 
+    /// Override the overall deadline configured on the client for this request.
+    ///
+    /// 为本次请求覆盖客户端配置的整体截止时间。
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
     /// Set request body.
     ///
     /// 设置请求体。
@@ -80,22 +128,10 @@ impl OssRequest {
         self
     }
     pub fn uri(&self) -> String {
-        let scheme = if self.oss.enable_https { "https" } else { "http" };
-        let domain =
-            self.oss.custom_domain.as_deref().map_or_else(|| format!("{}", self.oss.endpoint), |v| v.to_string());
-        let mut path = String::new();
-        if self.oss.custom_domain.is_none() {
-            if let Some(bucket) = self.oss.bucket.as_deref() {
-                path.push_str(&format!("{}.", bucket));
-            }
-            path.push_str(&domain);
-            if let Some(object) = self.oss.object.as_deref() {
-                path.push_str(&format!("/{}", url_encode_path(object)));
-            }
-        } else if let Some(object) = self.oss.object.as_deref() {
-            path.push_str(&format!("/{}", url_encode_path(object)));
+        let mut uri = self.oss.base_url();
+        if let Some(object) = self.oss.object.as_deref() {
+            uri.push_str(&url_encode_path(object));
         }
-        let mut uri = format!("{}://{}", scheme, path);
         if !self.queries.is_empty() {
             let query_string = self
                 .queries
@@ -110,51 +146,63 @@ impl OssRequest {
         }
         uri
     }
-    pub fn url_sign(&mut self, expires: &OffsetDateTime) {
-        let now = OffsetDateTime::now_utc();
-        let expires = (*expires - now).whole_seconds().max(1);
-        let date = format_oss_date(now);
-        let date_short = format_oss_date_short(now);
+    /// Sign the request into query parameters, resolving credentials first.
+    ///
+    /// 将签名写入查询参数，签名前先解析凭证。
+    pub fn url_sign(&mut self, expires: &OffsetDateTime) -> Result<(), Error> {
+        let credentials = self.oss.credentials()?;
+        let credentials = &credentials;
+        // expiry is measured on the local clock, the signing time follows the server clock
+        let expires = (*expires - OffsetDateTime::now_utc()).whole_seconds().max(1);
+        if self.oss.signature_version == SignatureVersion::V1 {
+            self.query_sign_v1(credentials, expires);
+            return Ok(());
+        }
+        let now = self.oss.clock.now();
+        let date = signer::format_date(now);
+        let date_short = signer::format_date_short(now);
         let region = self.oss.region.to_string();
 
-        self.insert_query("x-oss-signature-version", "OSS4-HMAC-SHA256");
-        self.insert_query(
-            "x-oss-credential",
-            format!("{}/{}/{}/oss/aliyun_v4_request", self.oss.ak_id, date_short, region),
-        );
+        self.insert_query("x-oss-signature-version", ALGORITHM);
+        self.insert_query("x-oss-credential", signer::credential(credentials, &date_short, &region));
         self.insert_query("x-oss-date", &date);
         self.insert_query("x-oss-expires", expires.to_string());
-        if let Some(token) = self.oss.security_token.clone() {
+        if let Some(token) = credentials.security_token() {
             self.insert_query("x-oss-security-token", token);
         }
 
-        let additional_headers = additional_headers_v4(&self.headers);
+        let additional_headers = signer::additional_headers(self.headers.keys().map(String::as_str));
         if !additional_headers.is_empty() {
             self.insert_query("x-oss-additional-headers", additional_headers.join(";"));
         }
 
-        let canonical_request = self.canonical_request_v4(&additional_headers);
-        let signature = self.signature_v4(&canonical_request, &date, &date_short, &region);
+        let signature = self.signature_v4(credentials, &additional_headers, &date, &date_short, &region);
         self.insert_query("x-oss-signature", signature);
+        Ok(())
     }
 
-    pub fn query_sign(&mut self, expires: OffsetDateTime) {
-        self.url_sign(&expires);
+    pub fn query_sign(&mut self, expires: OffsetDateTime) -> Result<(), Error> {
+        self.url_sign(&expires)
     }
-    pub fn header_sign(&mut self) {
-        let now = OffsetDateTime::now_utc();
-        let date = format_oss_date(now);
-        let date_short = format_oss_date_short(now);
+    pub fn header_sign(&mut self, credentials: &Credentials) {
+        if self.oss.signature_version == SignatureVersion::V1 {
+            self.header_sign_v1(credentials);
+            return;
+        }
+        let now = self.oss.clock.now();
+        let date = signer::format_date(now);
+        let date_short = signer::format_date_short(now);
         let region = self.oss.region.to_string();
 
         self.insert_header("x-oss-date", &date);
-        self.insert_header("x-oss-content-sha256", "UNSIGNED-PAYLOAD");
+        if !self.headers.contains_key("x-oss-content-sha256") {
+            self.insert_header("x-oss-content-sha256", UNSIGNED_PAYLOAD);
+        }
 
-        let additional_headers = additional_headers_v4(&self.headers);
-        let canonical_request = self.canonical_request_v4(&additional_headers);
-        let signature = self.signature_v4(&canonical_request, &date, &date_short, &region);
+        let additional_headers = signer::additional_headers(self.headers.keys().map(String::as_str));
+        let signature = self.signature_v4(credentials, &additional_headers, &date, &date_short, &region);
 
-        let credential = format!("{}/{}/{}/oss/aliyun_v4_request", self.oss.ak_id, date_short, region);
+        let credential = signer::credential(credentials, &date_short, &region);
         let authorization = if additional_headers.is_empty() {
             format!("OSS4-HMAC-SHA256 Credential={},Signature={}", credential, signature)
         } else {
@@ -167,159 +215,532 @@ impl OssRequest {
         };
         self.insert_header(header::AUTHORIZATION.as_str(), authorization);
     }
-    fn apply_security_token(&mut self) {
-        if let Some(security_token) = self.oss.security_token.clone() {
+    // V1 URL signing: `Expires` is an absolute timestamp on the server clock
+    fn query_sign_v1(&mut self, credentials: &Credentials, expires_in: i64) {
+        let expires = (self.oss.clock.now().unix_timestamp() + expires_in).to_string();
+        if let Some(token) = credentials.security_token() {
+            self.insert_query("security-token", token);
+        }
+        let string_to_sign = signature_v1::string_to_sign(
+            &self.method,
+            &self.headers,
+            &self.queries,
+            self.oss.bucket.as_deref(),
+            self.oss.object.as_deref(),
+            &expires,
+        );
+        self.insert_query("OSSAccessKeyId", credentials.access_key_id());
+        self.insert_query("Expires", expires);
+        self.insert_query("Signature", signature_v1::signature(credentials.access_key_secret(), &string_to_sign));
+    }
+
+    fn header_sign_v1(&mut self, credentials: &Credentials) {
+        let date = format_gmt(self.oss.clock.now());
+        self.insert_header(header::DATE.as_str(), &date);
+        let string_to_sign = signature_v1::string_to_sign(
+            &self.method,
+            &self.headers,
+            &self.queries,
+            self.oss.bucket.as_deref(),
+            self.oss.object.as_deref(),
+            &date,
+        );
+        let signature = signature_v1::signature(credentials.access_key_secret(), &string_to_sign);
+        self.insert_header(
+            header::AUTHORIZATION.as_str(),
+            format!("OSS {}:{}", credentials.access_key_id(), signature),
+        );
+    }
+
+    fn apply_security_token(&mut self, credentials: &Credentials) {
+        if let Some(security_token) = credentials.security_token() {
             self.insert_header("x-oss-security-token", security_token);
         }
     }
 
+    /// Sign and send the request with the in-memory body, retrying according to the client's `RetryPolicy`.
+    ///
+    /// A `RequestTimeTooSkewed` rejection is re-signed with the corrected clock and sent once more
+    /// outside the retry budget.
+    ///
+    /// 使用内存请求体签名并发送请求，按客户端的 `RetryPolicy` 进行重试。
+    ///
+    /// 若因 `RequestTimeTooSkewed` 被拒绝，会按校正后的时钟重新签名并额外发送一次，不计入重试次数。
     pub fn send_to_oss(mut self) -> Result<http::Response<Body>, Error> {
         let body = std::mem::take(&mut self.body);
-        self.send_to_oss_with_body(body)
+        self.prepare(Some(&body));
+        let trace = self.trace(Some(body.len() as u64));
+        let mut sends = 0;
+        let result = trace.in_scope(|| self.send_attempts(&body, &trace, &mut sends));
+        trace.finish(sends, &result);
+        result
+    }
+
+    fn send_attempts(
+        &mut self,
+        body: &[u8],
+        trace: &OperationTrace,
+        sends: &mut u32,
+    ) -> Result<http::Response<Body>, Error> {
+        let deadline = self.deadline();
+        let policy = self.oss.retry_policy.clone();
+        let max_attempts = policy.attempts_for(&self.method, &self.headers, true);
+        let mut skew_retry = true;
+        let mut region_retry = self.oss.follows_bucket_region();
+        let mut attempt = 1;
+        loop {
+            *sends += 1;
+            let error = match self.run(body, deadline) {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response)
+                    if attempt >= max_attempts
+                        && !(skew_retry && response.status() == StatusCode::FORBIDDEN)
+                        && !(region_retry && is_region_status(response.status())) =>
+                {
+                    return Ok(response);
+                }
+                Ok(response) => normal_error_sync(response),
+                Err(e) if attempt >= max_attempts => return Err(e),
+                Err(e) => e,
+            };
+            // the clock offset has just been corrected, so re-sign and resend right away
+            if skew_retry && is_time_skewed(&error) {
+                skew_retry = false;
+                trace.retry(*sends, &error);
+                continue;
+            }
+            if region_retry && is_wrong_region(&error) {
+                region_retry = false;
+                if self.discover_bucket_region(&error) {
+                    trace.retry(*sends, &error);
+                    continue;
+                }
+                return Err(error);
+            }
+            if attempt >= max_attempts || !policy.should_retry(&error) {
+                return Err(error);
+            }
+            let delay = policy.backoff(attempt);
+            if deadline.is_some_and(|deadline| Instant::now() + delay >= deadline) {
+                return Err(error);
+            }
+            trace.retry(*sends, &error);
+            std::thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+
+    /// Find the bucket's region, from the redirect endpoint or else GetBucketLocation, and rebind to
+    /// it. Returns whether the request should be re-sent.
+    fn discover_bucket_region(&mut self, error: &Error) -> bool {
+        let region = match region_hint(error) {
+            Some(region) => region,
+            // already looked up, so the rejection is not about the region
+            None if self.oss.knows_bucket_region() => return false,
+            None => {
+                let mut oss = self.oss.clone();
+                oss.region_discovery = false;
+                oss.object = None;
+                match (crate::OssBucket { oss }).get_bucket_location_sync().send() {
+                    Ok(region) => region,
+                    Err(_) => return false,
+                }
+            }
+        };
+        self.oss.bind_bucket_region(&region)
     }
 
+    /// Sign and send the request with a streaming body; streaming requests are never retried.
+    ///
+    /// 使用流式请求体签名并发送请求；流式请求不会重试。
     pub fn send_to_oss_with_body<B: AsSendBody>(mut self, body: B) -> Result<http::Response<Body>, Error> {
-        self.apply_security_token();
-        if !self.headers.contains_key("x-oss-content-sha256") {
-            self.insert_header("x-oss-content-sha256", "UNSIGNED-PAYLOAD");
+        self.prepare(None);
+        let content_length = self
+            .headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.parse().ok());
+        let trace = self.trace(content_length);
+        let deadline = self.deadline();
+        let result = trace.in_scope(|| self.run(body, deadline));
+        trace.finish(1, &result);
+        result
+    }
+
+    fn trace(&self, bytes_sent: Option<u64>) -> OperationTrace {
+        OperationTrace::new(
+            self.operation,
+            &self.method,
+            self.oss.bucket.as_deref(),
+            self.oss.object.as_deref(),
+            bytes_sent,
+        )
+    }
+
+    fn prepare(&mut self, body: Option<&[u8]>) {
+        if self.oss.signature_version == SignatureVersion::V4 && !self.headers.contains_key("x-oss-content-sha256") {
+            let payload_hash = match body {
+                Some(body) if self.signs_payload() => sha256_hex(body),
+                _ => "UNSIGNED-PAYLOAD".to_string(),
+            };
+            self.insert_header("x-oss-content-sha256", payload_hash);
         }
-        self.header_sign();
+    }
+
+    // The overall timeout covers every attempt and retry delay of the operation, not each attempt
+    fn deadline(&self) -> Option<Instant> {
+        self.timeout.or(self.oss.transport.config().timeout).map(|timeout| Instant::now() + timeout)
+    }
+
+    fn run<B: AsSendBody>(&mut self, body: B, deadline: Option<Instant>) -> Result<http::Response<Body>, Error> {
+        let remaining = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        if remaining.is_some_and(|remaining| remaining.is_zero()) {
+            return Err(Error::Timeout);
+        }
+        let credentials = self.oss.credentials()?;
+        self.apply_security_token(&credentials);
+        self.header_sign(&credentials);
         let url = self.uri();
 
         let mut builder = http::Request::builder().method(self.method.clone()).uri(&url);
         for (k, v) in self.headers.iter() {
             builder = builder.header(k, v);
         }
-        let request = builder.body(body)?;
-        let response = ureq::run(request)?;
+        let (request, head) = self.oss.interceptors.request(builder.body(body)?);
+        let agent = self.oss.transport.sync_agent();
+        let request = match remaining {
+            Some(remaining) => agent.configure_request(request).timeout_global(Some(remaining)).build(),
+            None => request,
+        };
+        let response = match agent.run(request) {
+            Ok(response) => self.oss.interceptors.response(head.as_ref(), response),
+            Err(e) => {
+                let error = match e {
+                    ureq::Error::Timeout(_) => Error::Timeout,
+                    e => Error::RequestError(e),
+                };
+                self.oss.interceptors.error(head.as_ref(), &error);
+                return Err(error);
+            }
+        };
+        self.oss.clock.observe(response.headers());
         Ok(response)
     }
 }
 
-fn format_oss_date(datetime: OffsetDateTime) -> String {
-    datetime
-        .format(&time::format_description::parse("[year][month][day]T[hour][minute][second]Z").expect("valid format"))
-        .expect("formatting")
+impl OssRequest {
+    fn signature_v4(
+        &self,
+        credentials: &Credentials,
+        additional_headers: &[String],
+        date: &str,
+        date_short: &str,
+        region: &str,
+    ) -> String {
+        let hashed_payload = self.headers.get("x-oss-content-sha256").map(String::as_str).unwrap_or(UNSIGNED_PAYLOAD);
+        let canonical_request = signer::canonical_request(
+            self.method.as_str(),
+            self.oss.bucket.as_deref(),
+            self.oss.object.as_deref(),
+            self.queries.iter().map(|(key, value)| (key.as_str(), value.as_str())),
+            self.headers.iter().map(|(key, value)| (key.as_str(), value.as_str())),
+            additional_headers,
+            hashed_payload,
+        );
+        let string_to_sign = signer::string_to_sign(date, date_short, region, &canonical_request);
+        signature_v4(credentials.access_key_secret(), date_short, region, &string_to_sign)
+    }
 }
 
-fn format_oss_date_short(datetime: OffsetDateTime) -> String {
-    datetime.format(&time::format_description::parse("[year][month][day]").expect("valid format")).expect("formatting")
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RetryPolicy;
+    use crate::test_util::{MockResponse, MockServer, oss_error_body};
 
-fn additional_headers_v4(headers: &HashMap<String, String>) -> Vec<String> {
-    let mut list = Vec::new();
-    for key in headers.keys() {
-        let lower = key.to_ascii_lowercase();
-        if lower == "content-type" || lower == "content-md5" || lower.starts_with("x-oss-") || lower == "authorization"
-        {
-            continue;
-        }
-        list.push(lower);
+    #[test]
+    fn test_security_token_header_injected_sync() {
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_security_token("token");
+        let mut req = OssRequest::new(oss, Method::GET, "Test");
+        req.apply_security_token(&req.oss.credentials().unwrap());
+        assert_eq!(req.headers.get("x-oss-security-token").map(|s| s.as_str()), Some("token"));
     }
-    list.sort();
-    list
-}
 
-fn hex_encode(bytes: &[u8]) -> String {
-    bytes.iter().map(|b| format!("{:02x}", b)).collect()
-}
+    #[test]
+    fn test_retry_replays_body_sync() {
+        let server = MockServer::start(vec![
+            MockResponse::new(500, oss_error_body("InternalError")),
+            MockResponse::new(200, "ok"),
+        ]);
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_custom_domain(server.host());
+        oss.set_https(false);
+        oss.set_retry_policy(RetryPolicy::new(2).set_base_delay(std::time::Duration::from_millis(1)));
+        let mut req = OssRequest::new(oss, Method::PUT, "Test");
+        req.set_body(b"payload".to_vec());
+        let response = req.send_to_oss().unwrap();
+        assert!(response.status().is_success());
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].body, b"payload");
+    }
 
-impl OssRequest {
-    fn canonical_query_v4(&self) -> String {
-        let mut items: Vec<(String, String)> =
-            self.queries.iter().map(|(k, v)| (url_encode(k), url_encode(v))).collect();
-        items.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
-        let mut out = String::new();
-        for (key, value) in items {
-            if !out.is_empty() {
-                out.push('&');
-            }
-            out.push_str(&key);
-            if !value.is_empty() {
-                out.push('=');
-                out.push_str(&value);
-            }
-        }
-        out
+    #[test]
+    fn test_region_redirect_followed_sync() {
+        let redirect = "<Error><Code>PermanentRedirect</Code><Message>m</Message><RequestId>r</RequestId>\
+                        <EC>0003-00000001</EC><Endpoint>oss-cn-beijing.aliyuncs.com</Endpoint></Error>";
+        let server = MockServer::start(vec![MockResponse::new(301, redirect), MockResponse::new(200, "ok")]);
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_endpoint(server.host());
+        oss.set_https(false);
+        oss.set_addressing_style(crate::common::AddressingStyle::Path);
+        oss.set_retry_policy(RetryPolicy::none());
+        oss.set_bucket("bucket");
+        oss.region_discovery = true;
+        let response = OssRequest::new(oss, Method::GET, "Test").send_to_oss().unwrap();
+        assert!(response.status().is_success());
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].header("authorization").unwrap().contains("/cn-beijing/oss/"));
     }
 
-    fn canonical_uri_v4(&self) -> String {
-        let mut path = String::from("/");
-        if let Some(bucket) = self.oss.bucket.as_deref() {
-            if !bucket.is_empty() {
-                path.push_str(bucket);
-                path.push('/');
-            }
-        }
-        if let Some(object) = self.oss.object.as_deref() {
-            if !object.is_empty() {
-                path.push_str(&url_encode_path(object));
-            }
-        }
-        if path.is_empty() { "/".to_string() } else { path }
-    }
-
-    fn canonical_headers_v4(&self, additional_headers: &[String]) -> String {
-        let mut pairs: Vec<(String, String)> = Vec::new();
-        for (key, value) in self.headers.iter() {
-            let lower = key.to_ascii_lowercase();
-            let include = lower == "content-type"
-                || lower == "content-md5"
-                || lower.starts_with("x-oss-")
-                || additional_headers.contains(&lower);
-            if include {
-                pairs.push((lower, value.trim().to_string()));
-            }
-        }
-        pairs.sort_by(|a, b| a.0.cmp(&b.0));
-        let mut out = String::new();
-        for (key, value) in pairs {
-            out.push_str(&key);
-            out.push(':');
-            out.push_str(&value);
-            out.push('\n');
-        }
-        out
-    }
-
-    fn canonical_request_v4(&self, additional_headers: &[String]) -> String {
-        let hashed_payload = self.headers.get("x-oss-content-sha256").map(|v| v.as_str()).unwrap_or("UNSIGNED-PAYLOAD");
-        format!(
-            "{}\n{}\n{}\n{}\n{}\n{}",
-            self.method,
-            self.canonical_uri_v4(),
-            self.canonical_query_v4(),
-            self.canonical_headers_v4(additional_headers),
-            additional_headers.join(";"),
-            hashed_payload
-        )
+    #[test]
+    fn test_v1_file_upload_skips_payload_hash_sync() {
+        use crate::{OssClient, common::AddressingStyle};
+        let server = MockServer::start(vec![MockResponse::new(200, "")]);
+        let mut client = OssClient::new("id", "secret", "cn-hangzhou")
+            .disable_https()
+            .with_addressing_style(AddressingStyle::Path)
+            .with_signature_version(SignatureVersion::V1)
+            .with_signed_payload(true)
+            .with_crc_check(false);
+        client.set_endpoint(server.host());
+        let path = std::env::temp_dir().join(format!("oss-v1-upload-{}", std::process::id()));
+        std::fs::write(&path, b"payload").unwrap();
+        let result = client.bucket("bucket").object("a.txt").put_object_sync().send_file(path.to_str().unwrap());
+        std::fs::remove_file(&path).ok();
+        result.unwrap();
+        let requests = server.requests();
+        assert!(requests[0].header("x-oss-content-sha256").is_none());
+        assert!(requests[0].header("authorization").unwrap().starts_with("OSS "));
     }
 
-    fn signature_v4(&self, canonical_request: &str, date: &str, date_short: &str, region: &str) -> String {
-        let hashed_request = hex_encode(digest::digest(&digest::SHA256, canonical_request.as_bytes()).as_ref());
-        let scope = format!("{}/{}/oss/aliyun_v4_request", date_short, region);
-        let string_to_sign = format!("OSS4-HMAC-SHA256\n{}\n{}\n{}", date, scope, hashed_request);
+    #[test]
+    fn test_timeout_sync() {
+        let server =
+            MockServer::start(vec![MockResponse::new(200, "slow").delay(std::time::Duration::from_millis(500))]);
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_custom_domain(server.host());
+        oss.set_https(false);
+        oss.set_retry_policy(RetryPolicy::none());
+        let mut req = OssRequest::new(oss, Method::GET, "Test");
+        req.set_timeout(std::time::Duration::from_millis(100));
+        assert!(matches!(req.send_to_oss(), Err(Error::Timeout)));
+    }
 
-        let key = format!("aliyun_v4{}", self.oss.ak_secret);
-        let k_date = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key.as_bytes()), date_short.as_bytes());
-        let k_region = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, k_date.as_ref()), region.as_bytes());
-        let k_service = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, k_region.as_ref()), b"oss");
-        let k_sign = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, k_service.as_ref()), b"aliyun_v4_request");
+    #[test]
+    fn test_stalled_body_times_out_sync() {
+        let mock_oss = || {
+            let server = MockServer::start(vec![
+                MockResponse::new(200, "0123456789").stall(std::time::Duration::from_millis(500)),
+            ]);
+            let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+            oss.set_custom_domain(server.host());
+            oss.set_https(false);
+            oss.set_retry_policy(RetryPolicy::none());
+            oss
+        };
 
-        hex_encode(hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, k_sign.as_ref()), string_to_sign.as_bytes()).as_ref())
+        // idle read timeout alone
+        let mut oss = mock_oss();
+        oss.set_read_timeout(std::time::Duration::from_millis(100));
+        let response = OssRequest::new(oss, Method::GET, "Test").send_to_oss().unwrap();
+        let error = crate::common::body_to_bytes_sync(response.into_body()).map_err(Error::from).unwrap_err();
+        assert!(matches!(error, Error::Timeout));
+
+        // overall deadline firing during the body read
+        let mut req = OssRequest::new(mock_oss(), Method::GET, "Test");
+        req.set_timeout(std::time::Duration::from_millis(200));
+        let response = req.send_to_oss().unwrap();
+        let error = crate::common::body_to_bytes_sync(response.into_body()).map_err(Error::from).unwrap_err();
+        assert!(matches!(error, Error::Timeout));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_timeout_covers_retries_sync() {
+        let server = MockServer::start(vec![
+            MockResponse::new(503, oss_error_body("ServiceUnavailable")),
+            MockResponse::new(503, oss_error_body("ServiceUnavailable")),
+            MockResponse::new(200, "ok"),
+        ]);
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_custom_domain(server.host());
+        oss.set_https(false);
+        oss.set_retry_policy(
+            RetryPolicy::new(3).set_base_delay(std::time::Duration::from_millis(300)).set_jitter(false),
+        );
+        let mut req = OssRequest::new(oss, Method::GET, "Test");
+        req.set_timeout(std::time::Duration::from_millis(200));
+        let started = Instant::now();
+        assert!(matches!(req.send_to_oss(), Err(Error::OssError(..))));
+        // the first backoff would pass the deadline, so there is no retry
+        assert!(started.elapsed() < std::time::Duration::from_millis(300));
+        assert_eq!(server.requests().len(), 1);
+    }
 
     #[test]
-    fn test_security_token_header_injected_sync() {
+    fn test_clock_skew_corrected_sync() {
+        let server_time = OffsetDateTime::now_utc() + time::Duration::hours(1);
+        let server = MockServer::start(vec![
+            MockResponse::new(403, oss_error_body("RequestTimeTooSkewed"))
+                .header("Date", crate::common::format_gmt(server_time)),
+            MockResponse::new(200, "ok"),
+        ]);
         let mut oss = Oss::new("id", "secret", "cn-hangzhou");
-        oss.set_security_token("token");
-        let mut req = OssRequest::new(oss, Method::GET);
-        req.apply_security_token();
-        assert_eq!(req.headers.get("x-oss-security-token").map(|s| s.as_str()), Some("token"));
+        oss.set_custom_domain(server.host());
+        oss.set_https(false);
+        oss.set_retry_policy(RetryPolicy::none());
+        let response = OssRequest::new(oss, Method::GET, "Test").send_to_oss().unwrap();
+        assert!(response.status().is_success());
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        let signed = requests[1].header("x-oss-date").unwrap();
+        assert!(signed >= signer::format_date(server_time - time::Duration::seconds(5)).as_str());
+    }
+
+    #[test]
+    fn test_signed_payload_sync() {
+        let server = MockServer::start(vec![MockResponse::new(200, "ok"), MockResponse::new(200, "ok")]);
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_custom_domain(server.host());
+        oss.set_https(false);
+        oss.set_signed_payload(true);
+        let mut req = OssRequest::new(oss.clone(), Method::PUT, "Test");
+        req.set_body(b"payload".to_vec());
+        req.send_to_oss().unwrap();
+        let mut req = OssRequest::new(oss, Method::PUT, "Test");
+        req.set_payload_hash(sha256_hex(b"streamed"));
+        req.send_to_oss_with_body(&b"streamed"[..]).unwrap();
+        let requests = server.requests();
+        assert_eq!(requests[0].header("x-oss-content-sha256"), Some(sha256_hex(b"payload").as_str()));
+        assert_eq!(requests[1].header("x-oss-content-sha256"), Some(sha256_hex(b"streamed").as_str()));
+    }
+
+    #[test]
+    fn test_interceptors_sync() {
+        let server = MockServer::start(vec![MockResponse::new(404, oss_error_body("NoSuchKey"))]);
+        let interceptor = std::sync::Arc::new(crate::test_util::RecordingInterceptor::default());
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_custom_domain(server.host());
+        oss.set_https(false);
+        oss.add_interceptor(interceptor.clone());
+        assert!(OssRequest::new(oss, Method::GET, "Test").send_to_oss().is_err());
+        assert_eq!(*interceptor.statuses.lock().unwrap(), vec![404]);
+        assert_eq!(server.requests()[0].header("x-intercepted"), Some("1"));
+    }
+
+    #[test]
+    fn test_http_proxy_sync() {
+        let server = MockServer::start(vec![MockResponse::new(200, "ok")]);
+        let proxy = crate::test_util::ConnectProxy::start();
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_custom_domain(server.host());
+        oss.set_https(false);
+        oss.set_proxy(crate::Proxy::http(&proxy.url()).unwrap().set_auth("user", "pass"));
+        let response = OssRequest::new(oss, Method::GET, "Test").send_to_oss().unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let tunnels = proxy.requests();
+        assert_eq!(tunnels[0].method, "CONNECT");
+        assert_eq!(tunnels[0].target, server.host());
+        // ureq spells the scheme in lowercase
+        let (scheme, token) = tunnels[0].header("proxy-authorization").unwrap().split_once(' ').unwrap();
+        assert!(scheme.eq_ignore_ascii_case("basic"));
+        assert_eq!(token, "dXNlcjpwYXNz");
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_custom_tls_sync() {
+        use crate::TlsConfig;
+        use crate::test_util::{TEST_CA_PEM, TEST_CLIENT_CERT_PEM, TEST_CLIENT_KEY_PEM, tls_server_config};
+        let server = MockServer::start_tls(
+            vec![MockResponse::new(200, "ok"), MockResponse::new(200, "ok")],
+            tls_server_config(true, rustls::ALL_VERSIONS),
+        );
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_custom_domain(server.host());
+        oss.set_retry_policy(RetryPolicy::none());
+        // the test CA is not a public root
+        assert!(OssRequest::new(oss.clone(), Method::GET, "Test").send_to_oss().is_err());
+
+        let tls = TlsConfig::new()
+            .set_root_certificates_pem(TEST_CA_PEM)
+            .unwrap()
+            .set_client_identity_pem(TEST_CLIENT_CERT_PEM, TEST_CLIENT_KEY_PEM)
+            .unwrap();
+        #[cfg(all(feature = "_sync-rustls", not(feature = "async-native-tls")))]
+        let tls = tls.set_min_version(crate::TlsVersion::Tls13);
+        oss.set_tls_config(&tls).unwrap();
+        let response = OssRequest::new(oss, Method::GET, "Test").send_to_oss().unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let request = server.requests().pop().unwrap();
+        assert!(request.client_certificate);
+        #[cfg(all(feature = "_sync-rustls", not(feature = "async-native-tls")))]
+        assert_eq!(request.tls_version.as_deref(), Some("TLSv1_3"));
+    }
+
+    #[cfg(all(feature = "_sync-rustls", not(feature = "async-native-tls")))]
+    #[test]
+    fn test_min_tls_version_enforced_sync() {
+        let server = MockServer::start_tls(
+            vec![MockResponse::new(200, "ok")],
+            crate::test_util::tls_server_config(false, &[&rustls::version::TLS12]),
+        );
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_custom_domain(server.host());
+        oss.set_retry_policy(RetryPolicy::none());
+        let tls = crate::TlsConfig::new().set_root_certificates_pem(crate::test_util::TEST_CA_PEM).unwrap();
+        oss.set_tls_config(&tls.set_min_version(crate::TlsVersion::Tls13)).unwrap();
+        assert!(OssRequest::new(oss, Method::GET, "Test").send_to_oss().is_err());
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn test_path_style_addressing_sync() {
+        let server = MockServer::start(vec![MockResponse::new(200, "ok")]);
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_endpoint(server.host());
+        oss.set_https(false);
+        oss.set_addressing_style(crate::common::AddressingStyle::Path);
+        oss.set_bucket("bucket");
+        oss.set_object("dir/file.txt");
+        let req = OssRequest::new(oss.clone(), Method::GET, "Test");
+        assert!(req.send_to_oss().unwrap().status().is_success());
+        assert_eq!(server.requests()[0].target, "/bucket/dir/file.txt");
+
+        let mut req = OssRequest::new(oss, Method::GET, "Test");
+        req.url_sign(&(OffsetDateTime::now_utc() + time::Duration::minutes(5))).unwrap();
+        assert!(req.uri().starts_with(&format!("http://{}/bucket/dir/file.txt?", server.host())));
+    }
+
+    #[test]
+    fn test_signature_v1_sync() {
+        let server = MockServer::start(vec![MockResponse::new(200, "ok")]);
+        let mut oss = Oss::new("id", "secret", "cn-hangzhou");
+        oss.set_custom_domain(server.host());
+        oss.set_https(false);
+        oss.set_signature_version(SignatureVersion::V1);
+        oss.set_bucket("bucket");
+        let mut req = OssRequest::new(oss, Method::GET, "Test");
+        req.insert_query("uploads", "");
+        req.insert_query("prefix", "a");
+        req.send_to_oss().unwrap();
+        let request = server.requests().pop().unwrap();
+        let expected = format!("GET\n\n\n{}\n/bucket/?uploads", request.header("date").unwrap());
+        assert_eq!(
+            request.header("authorization").unwrap(),
+            format!("OSS id:{}", signature_v1::signature("secret", &expected))
+        );
     }
 }
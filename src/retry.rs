@@ -0,0 +1,290 @@
+//! Automatic retry with exponential backoff.
+//!
+//! 带指数退避的自动重试。
+use crate::{Error, OssErrorCode};
+use http::Method;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Retry policy applied to every request sent from an `OssClient`.
+///
+/// Only requests that are safe to resend are retried: the method must be idempotent
+/// (`GET`, `HEAD`, `PUT`, `DELETE`, `OPTIONS`) and the body must be held in memory.
+/// Streaming uploads such as `send_file()` are sent exactly once.
+///
+/// The delay before attempt `n + 1` is `base_delay * 2^(n - 1)`, capped at `max_delay`.
+/// With jitter enabled the actual delay is picked uniformly from the upper half of that value.
+///
+/// The default policy makes up to 3 attempts with a 200 ms base delay, a 20 s cap and jitter enabled.
+///
+/// 应用于 `OssClient` 所有请求的重试策略。
+///
+/// 仅重试可安全重发的请求：方法必须是幂等的（`GET`、`HEAD`、`PUT`、`DELETE`、`OPTIONS`），
+/// 且请求体保存在内存中。`send_file()` 等流式上传只发送一次。
+///
+/// 第 `n + 1` 次尝试前的等待时间为 `base_delay * 2^(n - 1)`，上限为 `max_delay`。
+/// 启用抖动时，实际等待时间在该值的后半区间内均匀随机选取。
+///
+/// 默认策略最多尝试 3 次，基础等待 200 毫秒，上限 20 秒，并启用抖动。
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    classifier: Arc<dyn Fn(&Error) -> bool + Send + Sync>,
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .field("jitter", &self.jitter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new(3)
+    }
+}
+
+impl RetryPolicy {
+    /// Create a policy allowing up to `max_attempts` attempts (including the first one).
+    ///
+    /// 创建最多尝试 `max_attempts` 次（含首次）的策略。
+    pub fn new(max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts: max_attempts.max(1),
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(20),
+            jitter: true,
+            classifier: Arc::new(RetryPolicy::is_retryable_error),
+        }
+    }
+    /// Create a policy that never retries.
+    ///
+    /// 创建不进行重试的策略。
+    pub fn none() -> Self {
+        RetryPolicy::new(1)
+    }
+    /// Set the delay before the first retry.
+    ///
+    /// 设置首次重试前的等待时间。
+    pub fn set_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+    /// Set the upper bound for a single backoff delay.
+    ///
+    /// 设置单次退避等待时间的上限。
+    pub fn set_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+    /// Enable or disable jitter.
+    ///
+    /// 启用或禁用随机抖动。
+    pub fn set_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+    /// Replace the classifier deciding whether an error is worth retrying.
+    ///
+    /// The default classifier is [`RetryPolicy::is_retryable_error`].
+    ///
+    /// 替换用于判断错误是否值得重试的分类器。
+    ///
+    /// 默认分类器为 [`RetryPolicy::is_retryable_error`]。
+    pub fn set_classifier(mut self, classifier: impl Fn(&Error) -> bool + Send + Sync + 'static) -> Self {
+        self.classifier = Arc::new(classifier);
+        self
+    }
+    /// Maximum number of attempts, including the first one.
+    ///
+    /// 最大尝试次数（含首次）。
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+    /// Default classifier: connection and I/O failures, timeouts, HTTP 5xx/429 and the OSS codes
+    /// `ServiceUnavailable`, `InternalError`, `RequestTimeout`, `RequestTimeTooSkewed` and `Throttling`.
+    ///
+    /// 默认分类器：连接与 I/O 失败、超时、HTTP 5xx/429，以及 OSS 错误码
+    /// `ServiceUnavailable`、`InternalError`、`RequestTimeout`、`RequestTimeTooSkewed`、`Throttling`。
+    pub fn is_retryable_error(error: &Error) -> bool {
+        match error {
+            Error::OssError(status, oss_error) => {
+                is_retryable_status(status.as_u16())
+                    || matches!(
+                        oss_error.error_code(),
+                        OssErrorCode::ServiceUnavailable
+                            | OssErrorCode::InternalError
+                            | OssErrorCode::RequestTimeout
+                            | OssErrorCode::RequestTimeTooSkewed
+                            | OssErrorCode::Throttling
+                    )
+            }
+            Error::OssInvalidError(status, _) => is_retryable_status(status.as_u16()),
+            Error::IoError(_) | Error::Timeout => true,
+            #[cfg(feature = "_async-base")]
+            Error::HyperClientError(error) => is_transient_client_error(error),
+            #[cfg(feature = "_sync-base")]
+            Error::RequestError(error) => matches!(
+                error,
+                ureq::Error::Io(_)
+                    | ureq::Error::ConnectionFailed
+                    | ureq::Error::HostNotFound
+                    | ureq::Error::Protocol(_)
+            ),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn should_retry(&self, error: &Error) -> bool {
+        (self.classifier)(error)
+    }
+
+    /// Attempts allowed for a request with the given method, headers and replayability.
+    ///
+    /// A write with `x-oss-forbid-overwrite: true` is not idempotent: when a lost response hides a
+    /// successful first attempt, the retry fails with `FileAlreadyExists`.
+    ///
+    /// 给定方法、请求头与可重放性的请求允许的尝试次数。
+    ///
+    /// 带 `x-oss-forbid-overwrite: true` 的写入不是幂等的：若首次尝试已成功但响应丢失，
+    /// 重试会因 `FileAlreadyExists` 失败。
+    pub(crate) fn attempts_for(&self, method: &Method, headers: &HashMap<String, String>, replayable: bool) -> u32 {
+        let idempotent = matches!(*method, Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS);
+        let forbid_overwrite = headers.iter().any(|(key, value)| {
+            key.eq_ignore_ascii_case("x-oss-forbid-overwrite") && value.eq_ignore_ascii_case("true")
+        });
+        if idempotent && !forbid_overwrite && replayable { self.max_attempts } else { 1 }
+    }
+
+    /// Delay to wait after the given (1-based) failed attempt.
+    ///
+    /// 第 `attempt` 次（从 1 开始）失败后的等待时间。
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        if !self.jitter {
+            return delay;
+        }
+        let mut random = [0u8; 8];
+        if aws_lc_rs::rand::fill(&mut random).is_err() {
+            return delay;
+        }
+        let fraction = u64::from_le_bytes(random) as f64 / u64::MAX as f64;
+        delay / 2 + delay.div_f64(2.0).mul_f64(fraction)
+    }
+}
+
+fn is_retryable_status(status: u16) -> bool {
+    status == 429 || (500..=599).contains(&status)
+}
+
+// Like the sync transport, only connection failures and I/O errors are worth another attempt; other client
+// errors, such as a failing request body, would fail the same way again
+#[cfg(feature = "_async-base")]
+fn is_transient_client_error(error: &hyper_util::client::legacy::Error) -> bool {
+    if error.is_connect() {
+        return true;
+    }
+    let mut source = std::error::Error::source(error);
+    while let Some(inner) = source {
+        if inner.is::<std::io::Error>() {
+            return true;
+        }
+        source = inner.source();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use http::StatusCode;
+
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        let policy = RetryPolicy::new(5)
+            .set_base_delay(Duration::from_millis(100))
+            .set_max_delay(Duration::from_millis(350))
+            .set_jitter(false);
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff(40), Duration::from_millis(350));
+        let jittered = policy.set_jitter(true).backoff(2);
+        assert!(jittered >= Duration::from_millis(100) && jittered <= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_attempts_only_for_safe_requests() {
+        let policy = RetryPolicy::new(4);
+        let headers = HashMap::new();
+        assert_eq!(policy.attempts_for(&Method::GET, &headers, true), 4);
+        assert_eq!(policy.attempts_for(&Method::PUT, &headers, false), 1);
+        assert_eq!(policy.attempts_for(&Method::POST, &headers, true), 1);
+        assert_eq!(RetryPolicy::none().attempts_for(&Method::GET, &headers, true), 1);
+    }
+
+    #[test]
+    fn test_forbid_overwrite_not_retried() {
+        let policy = RetryPolicy::new(4);
+        let mut headers = HashMap::new();
+        headers.insert("x-oss-forbid-overwrite".to_string(), "false".to_string());
+        assert_eq!(policy.attempts_for(&Method::PUT, &headers, true), 4);
+        headers.insert("x-oss-forbid-overwrite".to_string(), "true".to_string());
+        assert_eq!(policy.attempts_for(&Method::PUT, &headers, true), 1);
+    }
+
+    #[test]
+    fn test_default_classifier() {
+        assert!(RetryPolicy::is_retryable_error(&Error::OssInvalidError(
+            StatusCode::SERVICE_UNAVAILABLE,
+            Bytes::new()
+        )));
+        assert!(RetryPolicy::is_retryable_error(&Error::OssInvalidError(StatusCode::TOO_MANY_REQUESTS, Bytes::new())));
+        assert!(!RetryPolicy::is_retryable_error(&Error::OssInvalidError(StatusCode::NOT_FOUND, Bytes::new())));
+        assert!(!RetryPolicy::is_retryable_error(&Error::InvalidFileSize));
+        let policy = RetryPolicy::default().set_classifier(|_| false);
+        assert!(!policy.should_retry(&Error::OssInvalidError(StatusCode::BAD_GATEWAY, Bytes::new())));
+    }
+
+    #[cfg(feature = "_async-base")]
+    #[test]
+    fn test_client_errors_retried_only_for_connect_and_io() {
+        use crate::test_util::{MockResponse, MockServer};
+        use crate::transport::{Transport, TransportConfig};
+        use http_body_util::{BodyExt, Empty, StreamBody};
+
+        let transport = Transport::new(TransportConfig::default());
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        // nothing listens on a port that was just released
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let request = http::Request::get(format!("http://127.0.0.1:{}/", port))
+            .body(Empty::new().map_err(|never| match never {}).boxed())
+            .unwrap();
+        let error = runtime.block_on(transport.async_client().request(request)).unwrap_err();
+        assert!(RetryPolicy::is_retryable_error(&Error::HyperClientError(error)));
+
+        // a body that fails on its own is not retried
+        let server = MockServer::start(vec![MockResponse::new(200, "ok")]);
+        let body = StreamBody::new(futures_util::stream::iter([Err::<http_body::Frame<Bytes>, _>(Box::<
+            dyn std::error::Error + Send + Sync,
+        >::from(
+            "broken body"
+        ))]));
+        let request = http::Request::put(format!("http://{}/", server.host())).body(body.boxed()).unwrap();
+        let error = runtime.block_on(transport.async_client().request(request)).unwrap_err();
+        assert!(!error.is_connect());
+        assert!(!RetryPolicy::is_retryable_error(&Error::HyperClientError(error)));
+    }
+}
@@ -0,0 +1,338 @@
+//! Minimal scripted HTTP server used by unit tests.
+//!
+//! 单元测试使用的极简脚本化 HTTP 服务。
+#![allow(dead_code)]
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// A canned response; each accepted connection serves exactly one of them, in order.
+#[derive(Debug, Clone)]
+pub(crate) struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub delay: Option<Duration>,
+    /// Pause after the head and the first half of the body.
+    pub stall: Option<Duration>,
+}
+
+impl MockResponse {
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        MockResponse { status, headers: Vec::new(), body: body.into(), delay: None, stall: None }
+    }
+
+    pub fn header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    pub fn stall(mut self, stall: Duration) -> Self {
+        self.stall = Some(stall);
+        self
+    }
+}
+
+/// A request captured by the server.
+#[derive(Debug, Clone)]
+pub(crate) struct RecordedRequest {
+    pub method: String,
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub tls_version: Option<String>,
+    pub client_certificate: bool,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, v)| v.as_str())
+    }
+}
+
+pub(crate) struct MockServer {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockServer {
+    pub fn start(responses: Vec<MockResponse>) -> Self {
+        MockServer::spawn(responses, None)
+    }
+
+    /// Serve the responses over TLS with the given rustls settings, recording the negotiated
+    /// protocol version and whether the client presented a certificate.
+    pub fn start_tls(responses: Vec<MockResponse>, config: Arc<rustls::ServerConfig>) -> Self {
+        MockServer::spawn(responses, Some(config))
+    }
+
+    fn spawn(responses: Vec<MockResponse>, tls: Option<Arc<rustls::ServerConfig>>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let addr = listener.local_addr().expect("local addr");
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for response in responses {
+                let Ok((stream, _)) = listener.accept() else { return };
+                match &tls {
+                    None => {
+                        let Some((request, stream)) = read_request(stream) else { continue };
+                        recorded.lock().unwrap().push(request);
+                        write_response(stream, &response);
+                    }
+                    Some(config) => {
+                        let connection = rustls::ServerConnection::new(config.clone()).expect("server connection");
+                        let Some((mut request, mut stream)) =
+                            read_request(rustls::StreamOwned::new(connection, stream))
+                        else {
+                            continue;
+                        };
+                        request.tls_version = stream.conn.protocol_version().map(|version| format!("{:?}", version));
+                        request.client_certificate = stream.conn.peer_certificates().is_some();
+                        recorded.lock().unwrap().push(request);
+                        write_response(&mut stream, &response);
+                        stream.conn.send_close_notify();
+                        let _ = stream.flush();
+                    }
+                }
+            }
+        });
+        MockServer { addr, requests }
+    }
+
+    /// `host:port` of the server, suitable for `set_custom_domain`.
+    pub fn host(&self) -> String {
+        self.addr.to_string()
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Read one request, including its body.
+fn read_request<S: Read + Write>(stream: S) -> Option<(RecordedRequest, S)> {
+    let mut reader = BufReader::new(stream);
+    let mut request = read_head(&mut reader)?;
+    let length = request.header("content-length").and_then(|v| v.parse::<usize>().ok()).unwrap_or(0);
+    request.body = vec![0; length];
+    let _ = reader.read_exact(&mut request.body);
+    Some((request, reader.into_inner()))
+}
+
+fn write_response(mut stream: impl Write, response: &MockResponse) {
+    if let Some(delay) = response.delay {
+        thread::sleep(delay);
+    }
+    let mut head = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.body.len()
+    );
+    for (key, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", key, value));
+    }
+    head.push_str("\r\n");
+    let _ = stream.write_all(head.as_bytes());
+    let (first, rest) = response.body.split_at(response.body.len() / 2);
+    let _ = stream.write_all(first);
+    let _ = stream.flush();
+    if let Some(stall) = response.stall {
+        thread::sleep(stall);
+    }
+    let _ = stream.write_all(rest);
+    let _ = stream.flush();
+}
+
+/// rustls server settings presenting `testdata/tls/server.pem` (valid for `127.0.0.1`), optionally
+/// requiring a client certificate issued by the test CA.
+pub(crate) fn tls_server_config(
+    require_client_certificate: bool,
+    versions: &[&'static rustls::SupportedProtocolVersion],
+) -> Arc<rustls::ServerConfig> {
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject};
+    let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let builder =
+        rustls::ServerConfig::builder_with_provider(provider.clone()).with_protocol_versions(versions).unwrap();
+    let builder = if require_client_certificate {
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add_parsable_certificates(CertificateDer::pem_slice_iter(TEST_CA_PEM).map(Result::unwrap));
+        let verifier =
+            rustls::server::WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider).build().unwrap();
+        builder.with_client_cert_verifier(verifier)
+    } else {
+        builder.with_no_client_auth()
+    };
+    let certs = CertificateDer::pem_slice_iter(include_bytes!("../testdata/tls/server.pem")).map(Result::unwrap);
+    let key = PrivateKeyDer::from_pem_slice(include_bytes!("../testdata/tls/server.key")).unwrap();
+    Arc::new(builder.with_single_cert(certs.collect(), key).unwrap())
+}
+
+pub(crate) const TEST_CA_PEM: &[u8] = include_bytes!("../testdata/tls/ca.pem");
+pub(crate) const TEST_CLIENT_CERT_PEM: &[u8] = include_bytes!("../testdata/tls/client.pem");
+pub(crate) const TEST_CLIENT_KEY_PEM: &[u8] = include_bytes!("../testdata/tls/client.key");
+
+/// Read a request line and headers; the body is left unread.
+fn read_head(reader: &mut impl BufRead) -> Option<RecordedRequest> {
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default().to_string();
+    let mut headers = Vec::new();
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).is_err() || header.trim().is_empty() {
+            break;
+        }
+        if let Some((key, value)) = header.trim_end().split_once(':') {
+            headers.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    Some(RecordedRequest { method, target, headers, body: Vec::new(), tls_version: None, client_certificate: false })
+}
+
+/// HTTP `CONNECT` proxy recording each tunnel request and piping bytes to the requested target.
+pub(crate) struct ConnectProxy {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl ConnectProxy {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind proxy");
+        let addr = listener.local_addr().expect("local addr");
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { return };
+                let recorded = recorded.clone();
+                thread::spawn(move || {
+                    let mut reader = BufReader::new(stream);
+                    let Some(request) = read_head(&mut reader) else { return };
+                    let target = request.target.clone();
+                    recorded.lock().unwrap().push(request);
+                    let mut client = reader.into_inner();
+                    let Ok(mut upstream) = std::net::TcpStream::connect(&target) else { return };
+                    let _ = client.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n");
+                    let (Ok(mut client_read), Ok(mut upstream_write)) = (client.try_clone(), upstream.try_clone())
+                    else {
+                        return;
+                    };
+                    thread::spawn(move || {
+                        let _ = std::io::copy(&mut client_read, &mut upstream_write);
+                        let _ = upstream_write.shutdown(std::net::Shutdown::Write);
+                    });
+                    let _ = std::io::copy(&mut upstream, &mut client);
+                    let _ = client.shutdown(std::net::Shutdown::Write);
+                });
+            }
+        });
+        ConnectProxy { addr, requests }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// OSS-style XML error body.
+pub(crate) fn oss_error_body(code: &str) -> String {
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?><Error><Code>{}</Code><Message>mock</Message><RequestId>rid</RequestId><EC>0000-00000000</EC></Error>",
+        code
+    )
+}
+
+/// Interceptor adding an `x-intercepted` header and recording the response statuses it saw.
+#[derive(Default)]
+pub(crate) struct RecordingInterceptor {
+    pub statuses: Mutex<Vec<u16>>,
+}
+
+impl crate::Interceptor for RecordingInterceptor {
+    fn on_request(&self, request: &mut http::Request<()>) {
+        request.headers_mut().insert("x-intercepted", http::HeaderValue::from_static("1"));
+    }
+
+    fn on_response(&self, _request: &http::Request<()>, response: &mut http::Response<()>) {
+        self.statuses.lock().unwrap().push(response.status().as_u16());
+    }
+}
+
+/// Span name with its recorded fields, formatted with `Debug`.
+#[cfg(feature = "tracing")]
+pub(crate) type RecordedSpan = (&'static str, Vec<(String, String)>);
+
+/// Subscriber recording the fields of every span.
+#[cfg(feature = "tracing")]
+#[derive(Default, Clone)]
+pub(crate) struct SpanRecorder {
+    pub spans: Arc<Mutex<Vec<RecordedSpan>>>,
+}
+
+#[cfg(feature = "tracing")]
+impl SpanRecorder {
+    /// Recorded value of `field` on the first span named `name`.
+    pub fn field(&self, name: &str, field: &str) -> Option<String> {
+        let spans = self.spans.lock().unwrap();
+        let (_, fields) = spans.iter().find(|(span, _)| *span == name)?;
+        fields.iter().rev().find(|(key, _)| key == field).map(|(_, value)| value.clone())
+    }
+}
+
+#[cfg(feature = "tracing")]
+struct FieldVisitor<'a>(&'a mut Vec<(String, String)>);
+
+#[cfg(feature = "tracing")]
+impl tracing::field::Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.0.push((field.name().to_string(), value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name().to_string(), format!("{:?}", value)));
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl tracing::Subscriber for SpanRecorder {
+    fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        let mut fields = Vec::new();
+        span.record(&mut FieldVisitor(&mut fields));
+        let mut spans = self.spans.lock().unwrap();
+        spans.push((span.metadata().name(), fields));
+        tracing::span::Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &tracing::span::Id, values: &tracing::span::Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        if let Some((_, fields)) = spans.get_mut(span.into_u64() as usize - 1) {
+            values.record(&mut FieldVisitor(fields));
+        }
+    }
+
+    fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+    fn event(&self, _: &tracing::Event<'_>) {}
+
+    fn enter(&self, _: &tracing::span::Id) {}
+
+    fn exit(&self, _: &tracing::span::Id) {}
+}
@@ -0,0 +1,338 @@
+//! TLS settings for connections to OSS.
+//!
+//! 连接 OSS 时使用的 TLS 配置。
+use crate::Error;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject};
+use std::fmt;
+use std::sync::Arc;
+
+/// Lowest TLS protocol version a client will negotiate.
+///
+/// 客户端可协商的最低 TLS 协议版本。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsVersion {
+    /// TLS 1.2.
+    Tls12,
+    /// TLS 1.3.
+    Tls13,
+}
+
+/// TLS settings applied by `OssClient::with_tls_config` to both the async and sync transports.
+///
+/// By default the rustls backends trust the Mozilla (webpki) roots and the native-tls backends
+/// trust the system store. Replacing the roots with a PEM bundle makes the client trust only
+/// those certificates, which suits OSS-compatible gateways behind a private CA.
+///
+/// The native-tls backends cannot enforce TLS 1.3, the sync native-tls backend cannot enforce a
+/// minimum version at all, and both require client keys in PKCS#8 form; unsupported combinations
+/// are rejected by `with_tls_config`.
+///
+/// 由 `OssClient::with_tls_config` 同时应用于异步与同步传输层的 TLS 配置。
+///
+/// 默认情况下，rustls 后端信任 Mozilla（webpki）根证书，native-tls 后端信任系统证书库。将根证书
+/// 替换为 PEM 证书包后，客户端只信任这些证书，适用于私有 CA 签发证书的 OSS 兼容网关。
+///
+/// native-tls 后端无法强制 TLS 1.3，同步 native-tls 后端完全无法限制最低版本，且二者都要求客户端
+/// 私钥为 PKCS#8 格式；不支持的组合会被 `with_tls_config` 拒绝。
+#[derive(Clone, Default)]
+pub struct TlsConfig {
+    roots: Roots,
+    client_identity: Option<(Vec<CertificateDer<'static>>, Arc<PrivateKeyDer<'static>>)>,
+    min_version: Option<TlsVersion>,
+}
+
+#[derive(Clone, Default)]
+enum Roots {
+    #[default]
+    Default,
+    Native,
+    Pem(Vec<CertificateDer<'static>>),
+}
+
+impl fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let roots = match &self.roots {
+            Roots::Default => "default".to_string(),
+            Roots::Native => "native".to_string(),
+            Roots::Pem(certs) => format!("{} PEM certificates", certs.len()),
+        };
+        f.debug_struct("TlsConfig")
+            .field("roots", &roots)
+            .field("client_identity", &self.client_identity.is_some())
+            .field("min_version", &self.min_version)
+            .finish()
+    }
+}
+
+impl TlsConfig {
+    /// Create TLS settings with the backend's default roots, no client certificate and no minimum version.
+    ///
+    /// 创建使用后端默认根证书、无客户端证书、不限制最低版本的 TLS 配置。
+    pub fn new() -> Self {
+        TlsConfig::default()
+    }
+
+    /// Trust only the certificates in a PEM bundle.
+    ///
+    /// 仅信任 PEM 证书包中的证书。
+    pub fn set_root_certificates_pem(mut self, pem: &[u8]) -> Result<Self, Error> {
+        let certs = CertificateDer::pem_slice_iter(pem)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Error::TlsConfigError(format!("无法解析根证书：{}", e)))?;
+        if certs.is_empty() {
+            return Err(Error::TlsConfigError("PEM 中没有证书".to_string()));
+        }
+        self.roots = Roots::Pem(certs);
+        Ok(self)
+    }
+
+    /// Trust the operating system's certificate store.
+    ///
+    /// 信任操作系统的证书库。
+    pub fn set_native_roots(mut self) -> Self {
+        self.roots = Roots::Native;
+        self
+    }
+
+    /// Present a client certificate for mutual TLS. `cert_chain` holds the PEM certificate chain,
+    /// leaf first; `private_key` holds its PEM private key.
+    ///
+    /// 为双向 TLS 提供客户端证书。`cert_chain` 为 PEM 证书链（叶子证书在前），`private_key` 为对应的 PEM 私钥。
+    pub fn set_client_identity_pem(mut self, cert_chain: &[u8], private_key: &[u8]) -> Result<Self, Error> {
+        let certs = CertificateDer::pem_slice_iter(cert_chain)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Error::TlsConfigError(format!("无法解析客户端证书：{}", e)))?;
+        if certs.is_empty() {
+            return Err(Error::TlsConfigError("PEM 中没有客户端证书".to_string()));
+        }
+        let key = PrivateKeyDer::from_pem_slice(private_key)
+            .map_err(|e| Error::TlsConfigError(format!("无法解析客户端私钥：{}", e)))?;
+        self.client_identity = Some((certs, Arc::new(key)));
+        Ok(self)
+    }
+
+    /// Refuse to negotiate protocol versions older than `version`.
+    ///
+    /// 拒绝协商低于 `version` 的协议版本。
+    pub fn set_min_version(mut self, version: TlsVersion) -> Self {
+        self.min_version = Some(version);
+        self
+    }
+
+    /// Validate the settings and build the backend configurations for the enabled features.
+    ///
+    /// 校验配置，并为已启用的特性构建后端配置。
+    pub(crate) fn build(&self) -> Result<BuiltTls, Error> {
+        Ok(BuiltTls {
+            #[cfg(feature = "_async-rustls")]
+            async_rustls: self.rustls_client_config()?,
+            #[cfg(feature = "async-native-tls")]
+            async_native: self.native_tls_connector()?,
+            #[cfg(feature = "_sync-base")]
+            sync: self.ureq_tls_config()?,
+        })
+    }
+
+    #[cfg(any(feature = "_async-rustls", feature = "_sync-rustls"))]
+    fn rustls_provider(&self) -> Arc<rustls::crypto::CryptoProvider> {
+        let mut provider = rustls::crypto::aws_lc_rs::default_provider();
+        // rustls only offers versions the provider has cipher suites for
+        if self.min_version == Some(TlsVersion::Tls13) {
+            provider.cipher_suites.retain(|suite| suite.version() == &rustls::version::TLS13);
+        }
+        Arc::new(provider)
+    }
+
+    #[cfg(any(feature = "_async-rustls", feature = "_sync-rustls"))]
+    fn native_roots() -> Result<Vec<CertificateDer<'static>>, Error> {
+        let result = rustls_native_certs::load_native_certs();
+        if result.certs.is_empty() {
+            let errors = result.errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; ");
+            return Err(Error::TlsConfigError(format!("无法加载系统根证书：{}", errors)));
+        }
+        Ok(result.certs)
+    }
+
+    #[cfg(any(feature = "_async-rustls", feature = "_sync-rustls"))]
+    fn rustls_client_config(&self) -> Result<rustls::ClientConfig, Error> {
+        let tls_error = |e: rustls::Error| Error::TlsConfigError(e.to_string());
+        let builder = rustls::ClientConfig::builder_with_provider(self.rustls_provider())
+            .with_protocol_versions(rustls::ALL_VERSIONS)
+            .map_err(tls_error)?;
+        let mut roots = rustls::RootCertStore::empty();
+        match &self.roots {
+            #[cfg(feature = "_async-rustls")]
+            Roots::Default => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+            // the sync transport keeps ureq's bundled webpki roots, so only key checks matter here
+            #[cfg(not(feature = "_async-rustls"))]
+            Roots::Default => {}
+            Roots::Native => {
+                roots.add_parsable_certificates(Self::native_roots()?);
+            }
+            Roots::Pem(certs) => {
+                for cert in certs {
+                    roots.add(cert.clone()).map_err(tls_error)?;
+                }
+            }
+        }
+        let builder = builder.with_root_certificates(roots);
+        match &self.client_identity {
+            Some((chain, key)) => builder.with_client_auth_cert(chain.clone(), key.clone_key()).map_err(tls_error),
+            None => Ok(builder.with_no_client_auth()),
+        }
+    }
+
+    #[cfg(any(feature = "async-native-tls", feature = "sync-native-tls"))]
+    fn native_identity_pem(&self) -> Result<Option<(String, String)>, Error> {
+        let Some((chain, key)) = &self.client_identity else {
+            return Ok(None);
+        };
+        let PrivateKeyDer::Pkcs8(key) = key.as_ref() else {
+            return Err(Error::TlsConfigError("native-tls 仅支持 PKCS#8 格式的客户端私钥".to_string()));
+        };
+        let chain = chain.iter().map(|cert| pem_encode("CERTIFICATE", cert)).collect();
+        Ok(Some((chain, pem_encode("PRIVATE KEY", key.secret_pkcs8_der()))))
+    }
+
+    #[cfg(feature = "async-native-tls")]
+    fn native_tls_connector(&self) -> Result<native_tls::TlsConnector, Error> {
+        let tls_error = |e: native_tls::Error| Error::TlsConfigError(e.to_string());
+        let mut builder = native_tls::TlsConnector::builder();
+        match self.min_version {
+            Some(TlsVersion::Tls13) => {
+                return Err(Error::TlsConfigError("native-tls 不支持将最低版本设为 TLS 1.3".to_string()));
+            }
+            Some(TlsVersion::Tls12) => {
+                builder.min_protocol_version(Some(native_tls::Protocol::Tlsv12));
+            }
+            None => {}
+        }
+        if let Roots::Pem(certs) = &self.roots {
+            builder.disable_built_in_roots(true);
+            for cert in certs {
+                builder.add_root_certificate(native_tls::Certificate::from_der(cert).map_err(tls_error)?);
+            }
+        }
+        if let Some((chain, key)) = self.native_identity_pem()? {
+            builder.identity(native_tls::Identity::from_pkcs8(chain.as_bytes(), key.as_bytes()).map_err(tls_error)?);
+        }
+        builder.build().map_err(tls_error)
+    }
+
+    #[cfg(feature = "_sync-base")]
+    fn ureq_tls_config(&self) -> Result<ureq::tls::TlsConfig, Error> {
+        use ureq::tls::{Certificate, ClientCert, PrivateKey, RootCerts};
+        #[cfg(feature = "_sync-rustls")]
+        let builder = {
+            // build once to surface certificate and key errors now rather than on first connect
+            self.rustls_client_config()?;
+            ureq::tls::TlsConfig::builder()
+                .provider(ureq::tls::TlsProvider::Rustls)
+                .unversioned_rustls_crypto_provider(self.rustls_provider())
+        };
+        #[cfg(feature = "sync-native-tls")]
+        let builder = {
+            if self.min_version.is_some() {
+                return Err(Error::TlsConfigError("同步 native-tls 后端不支持设置最低 TLS 版本".to_string()));
+            }
+            self.native_identity_pem()?;
+            ureq::tls::TlsConfig::builder().provider(ureq::tls::TlsProvider::NativeTls)
+        };
+        let to_ureq = |certs: &[CertificateDer<'static>]| -> RootCerts {
+            certs.iter().map(|cert| Certificate::from_der(cert).to_owned()).collect::<Vec<_>>().into()
+        };
+        let roots = match &self.roots {
+            Roots::Default => RootCerts::WebPki,
+            #[cfg(feature = "_sync-rustls")]
+            Roots::Native => to_ureq(&Self::native_roots()?),
+            #[cfg(feature = "sync-native-tls")]
+            Roots::Native => RootCerts::PlatformVerifier,
+            Roots::Pem(certs) => to_ureq(certs),
+        };
+        // ureq only takes typed keys from PEM, so re-encode with the label matching the key kind
+        let client_cert = match &self.client_identity {
+            Some((chain, key)) => {
+                let label = match key.as_ref() {
+                    PrivateKeyDer::Pkcs1(_) => "RSA PRIVATE KEY",
+                    PrivateKeyDer::Sec1(_) => "EC PRIVATE KEY",
+                    _ => "PRIVATE KEY",
+                };
+                let key = PrivateKey::from_pem(pem_encode(label, key.secret_der()).as_bytes())
+                    .map_err(|e| Error::TlsConfigError(e.to_string()))?;
+                let chain = chain.iter().map(|cert| Certificate::from_der(cert).to_owned()).collect::<Vec<_>>();
+                Some(ClientCert::new_with_certs(&chain, key))
+            }
+            None => None,
+        };
+        Ok(builder.root_certs(roots).client_cert(client_cert).build())
+    }
+
+    /// Sync TLS settings used when none were configured.
+    ///
+    /// 未配置时同步传输层使用的 TLS 设置。
+    #[cfg(feature = "_sync-base")]
+    pub(crate) fn default_ureq_tls_config() -> ureq::tls::TlsConfig {
+        TlsConfig::default().ureq_tls_config().expect("default TLS settings are valid")
+    }
+}
+
+#[cfg(any(feature = "async-native-tls", feature = "_sync-base"))]
+fn pem_encode(label: &str, der: &[u8]) -> String {
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    let encoded = STANDARD.encode(der);
+    let mut pem = format!("-----BEGIN {}-----\n", label);
+    for line in encoded.as_bytes().chunks(64) {
+        pem.push_str(std::str::from_utf8(line).expect("base64 is ASCII"));
+        pem.push('\n');
+    }
+    pem.push_str(&format!("-----END {}-----\n", label));
+    pem
+}
+
+/// Backend configurations built from a validated `TlsConfig`.
+///
+/// 由校验通过的 `TlsConfig` 构建出的后端配置。
+pub(crate) struct BuiltTls {
+    #[cfg(feature = "_async-rustls")]
+    pub async_rustls: rustls::ClientConfig,
+    #[cfg(feature = "async-native-tls")]
+    pub async_native: native_tls::TlsConnector,
+    #[cfg(feature = "_sync-base")]
+    pub sync: ureq::tls::TlsConfig,
+}
+
+impl fmt::Debug for BuiltTls {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BuiltTls").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_util::{TEST_CA_PEM as CA, TEST_CLIENT_CERT_PEM as CLIENT_CERT, TEST_CLIENT_KEY_PEM as CLIENT_KEY};
+
+    #[test]
+    fn test_parse_pem_settings() {
+        let config = TlsConfig::new()
+            .set_root_certificates_pem(CA)
+            .unwrap()
+            .set_client_identity_pem(CLIENT_CERT, CLIENT_KEY)
+            .unwrap();
+        assert!(matches!(&config.roots, Roots::Pem(certs) if certs.len() == 1));
+        assert!(config.build().is_ok());
+        assert!(!format!("{:?}", config).contains("PRIVATE"));
+
+        assert!(matches!(TlsConfig::new().set_root_certificates_pem(b"not pem"), Err(Error::TlsConfigError(_))));
+        assert!(matches!(TlsConfig::new().set_client_identity_pem(CLIENT_CERT, CA), Err(Error::TlsConfigError(_))));
+    }
+
+    #[cfg(any(feature = "_async-rustls", feature = "_sync-rustls"))]
+    #[test]
+    fn test_mismatched_client_key_is_rejected() {
+        let server_key = include_bytes!("../testdata/tls/server.key");
+        let config = TlsConfig::new().set_client_identity_pem(CLIENT_CERT, server_key).unwrap();
+        assert!(matches!(config.build(), Err(Error::TlsConfigError(_))));
+    }
+}
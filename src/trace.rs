@@ -0,0 +1,143 @@
+//! Optional `tracing` instrumentation for OSS operations.
+//!
+//! OSS 操作的可选 `tracing` 埋点。
+use crate::Error;
+#[cfg(feature = "tracing")]
+use http::HeaderMap;
+use http::{Method, Response};
+
+/// Span covering one OSS operation, including all of its retry attempts.
+///
+/// Only the operation name, bucket, key, method, status, request ID, byte counts, attempt count and
+/// latency are recorded; headers, query strings, credentials and signatures never are. Without the
+/// `tracing` feature this is a zero-sized no-op.
+///
+/// 覆盖一次 OSS 操作（包含所有重试）的 Span。
+///
+/// 仅记录操作名、Bucket、对象键、方法、状态码、请求 ID、字节数、尝试次数与耗时；不会记录请求头、
+/// 查询参数、凭证或签名。未启用 `tracing` 特性时为零大小的空实现。
+pub(crate) struct OperationTrace {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    start: std::time::Instant,
+}
+
+impl OperationTrace {
+    #[cfg(feature = "tracing")]
+    pub fn new(
+        operation: &'static str,
+        method: &Method,
+        bucket: Option<&str>,
+        key: Option<&str>,
+        bytes_sent: Option<u64>,
+    ) -> Self {
+        use tracing::field::Empty;
+        let span = tracing::info_span!(
+            "oss.request",
+            operation,
+            bucket = Empty,
+            key = Empty,
+            method = %method,
+            status = Empty,
+            request_id = Empty,
+            bytes_sent = Empty,
+            bytes_received = Empty,
+            attempts = Empty,
+            latency_ms = Empty,
+            error = Empty,
+        );
+        if let Some(bucket) = bucket {
+            span.record("bucket", bucket);
+        }
+        if let Some(key) = key {
+            span.record("key", key);
+        }
+        if let Some(bytes_sent) = bytes_sent {
+            span.record("bytes_sent", bytes_sent);
+        }
+        OperationTrace { span, start: std::time::Instant::now() }
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    pub fn new(_: &'static str, _: &Method, _: Option<&str>, _: Option<&str>, _: Option<u64>) -> Self {
+        OperationTrace {}
+    }
+
+    /// Attach the span to a future so events emitted while it runs are nested under it.
+    ///
+    /// 将 Span 附加到 Future 上，使其运行期间产生的事件归属于该 Span。
+    #[cfg(all(feature = "tracing", feature = "_async-base"))]
+    pub fn instrument<F: std::future::Future>(&self, future: F) -> impl std::future::Future<Output = F::Output> {
+        tracing::Instrument::instrument(future, self.span.clone())
+    }
+
+    #[cfg(all(not(feature = "tracing"), feature = "_async-base"))]
+    pub fn instrument<F: std::future::Future>(&self, future: F) -> F {
+        future
+    }
+
+    /// Run a blocking call inside the span.
+    ///
+    /// 在该 Span 内执行阻塞调用。
+    #[cfg(feature = "_sync-base")]
+    pub fn in_scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        #[cfg(feature = "tracing")]
+        return self.span.in_scope(f);
+        #[cfg(not(feature = "tracing"))]
+        f()
+    }
+
+    /// Record that attempt `attempt` failed with `error` and the request will be sent again.
+    ///
+    /// 记录第 `attempt` 次尝试以 `error` 失败，请求将再次发送。
+    pub fn retry(&self, attempt: u32, error: &Error) {
+        #[cfg(feature = "tracing")]
+        self.span.in_scope(|| tracing::debug!(attempt, error = %error, "retrying OSS request"));
+        #[cfg(not(feature = "tracing"))]
+        let _ = (attempt, error);
+    }
+
+    /// Record the outcome of the operation once the last attempt completed.
+    ///
+    /// 在最后一次尝试结束后记录操作结果。
+    pub fn finish<B>(&self, attempts: u32, result: &Result<Response<B>, Error>) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("attempts", attempts);
+            self.span.record("latency_ms", self.start.elapsed().as_millis() as u64);
+            match result {
+                Ok(response) => {
+                    self.span.record("status", response.status().as_u16());
+                    self.record_headers(response.headers());
+                }
+                Err(error) => {
+                    if let Error::OssError(status, oss_error) = error {
+                        self.span.record("status", status.as_u16());
+                        if let Some(request_id) = &oss_error.request_id {
+                            self.span.record("request_id", request_id.as_str());
+                        }
+                    }
+                    self.span.record("error", tracing::field::display(error));
+                }
+            }
+        }
+        #[cfg(not(feature = "tracing"))]
+        let _ = (attempts, result);
+    }
+
+    #[cfg(feature = "tracing")]
+    fn record_headers(&self, headers: &HeaderMap) {
+        if let Some(request_id) = headers.get("x-oss-request-id").and_then(|v| v.to_str().ok()) {
+            self.span.record("request_id", request_id);
+        }
+        if let Some(length) = content_length(headers) {
+            self.span.record("bytes_received", length);
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn content_length(headers: &HeaderMap) -> Option<u64> {
+    headers.get(http::header::CONTENT_LENGTH).and_then(|v| v.to_str().ok()).and_then(|v| v.parse().ok())
+}
@@ -0,0 +1,374 @@
+//! Shared HTTP transport used by every request issued from the same `OssClient`.
+//!
+//! 同一个 `OssClient` 发出的所有请求共享的 HTTP 传输层。
+#[cfg(feature = "_async-base")]
+use crate::Error;
+use crate::Proxy;
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use crate::tls::BuiltTls;
+#[cfg(feature = "_sync-base")]
+use crate::tls::TlsConfig;
+#[cfg(feature = "_async-base")]
+use bytes::Bytes;
+#[cfg(feature = "_async-base")]
+use http_body::{Body, Frame, SizeHint};
+#[cfg(feature = "_async-base")]
+use http_body_util::combinators::BoxBody;
+#[cfg(feature = "_async-base")]
+use hyper::body::Incoming;
+#[cfg(feature = "_async-rustls")]
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+#[cfg(feature = "async-native-tls")]
+use hyper_tls::HttpsConnector;
+#[cfg(feature = "_async-base")]
+use hyper_util::{
+    client::{
+        legacy::{
+            Client,
+            connect::{
+                HttpConnector,
+                proxy::{SocksV5, Tunnel},
+            },
+        },
+        proxy::matcher::Matcher,
+    },
+    rt::{TokioExecutor, TokioIo, TokioTimer},
+};
+#[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+#[cfg(feature = "_async-base")]
+use std::{
+    error::Error as StdError,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+#[cfg(feature = "_async-base")]
+use tokio::{
+    net::TcpStream,
+    time::{Instant, Sleep},
+};
+#[cfg(feature = "_sync-base")]
+use ureq::unversioned::{
+    resolver::DefaultResolver,
+    transport::{
+        Buffers, ConnectionDetails, Connector, DefaultConnector, NextTimeout, Transport as SyncTransport,
+        time::Duration as TimeoutDuration,
+    },
+};
+
+/// Request body type accepted by the pooled async client.
+///
+/// 连接池异步客户端接受的请求体类型。
+#[cfg(feature = "_async-base")]
+pub(crate) type AsyncBody = BoxBody<Bytes, Box<dyn StdError + Send + Sync>>;
+
+/// Pooled async client shared by all handles.
+///
+/// 所有句柄共享的带连接池的异步客户端。
+#[cfg(feature = "_async-base")]
+pub(crate) type AsyncClient = Client<HttpsConnector<ProxyConnector>, AsyncBody>;
+
+/// Connection pool and timeout settings applied to both the async and sync transports.
+///
+/// 同时作用于异步与同步传输层的连接池与超时配置。
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TransportConfig {
+    pub pool_idle_timeout: Option<Duration>,
+    pub pool_max_idle_per_host: Option<usize>,
+    pub connect_timeout: Option<Duration>,
+    pub read_timeout: Option<Duration>,
+    pub timeout: Option<Duration>,
+    /// `None` reads the proxy from the environment.
+    pub proxy: Option<Proxy>,
+    /// `None` keeps the backend's default TLS settings.
+    #[cfg(any(feature = "_async-base", feature = "_sync-base"))]
+    pub tls: Option<Arc<BuiltTls>>,
+}
+
+/// Lazily built HTTP clients bound to one `TransportConfig`.
+///
+/// Clients are created on first use and then reused, so keep-alive connections and TLS sessions
+/// are shared between every bucket and object handle derived from the same `OssClient`.
+///
+/// 绑定到某个 `TransportConfig` 的延迟构建 HTTP 客户端。
+///
+/// 客户端在首次使用时创建并在之后复用，因此同一 `OssClient` 派生的所有 Bucket 与对象句柄
+/// 共享长连接与 TLS 会话。
+#[derive(Debug, Default)]
+pub(crate) struct Transport {
+    config: TransportConfig,
+    #[cfg(feature = "_async-base")]
+    async_client: OnceLock<AsyncClient>,
+    #[cfg(feature = "_sync-base")]
+    sync_agent: OnceLock<ureq::Agent>,
+}
+
+impl Transport {
+    pub fn new(config: TransportConfig) -> Self {
+        Transport {
+            config,
+            #[cfg(feature = "_async-base")]
+            async_client: OnceLock::new(),
+            #[cfg(feature = "_sync-base")]
+            sync_agent: OnceLock::new(),
+        }
+    }
+
+    pub fn config(&self) -> &TransportConfig {
+        &self.config
+    }
+
+    /// Return the shared async client, building it on first use.
+    ///
+    /// 返回共享的异步客户端，首次使用时构建。
+    #[cfg(feature = "_async-base")]
+    pub fn async_client(&self) -> &AsyncClient {
+        self.async_client.get_or_init(|| {
+            let mut http = HttpConnector::new();
+            http.enforce_http(false);
+            http.set_connect_timeout(self.config.connect_timeout);
+            let http = ProxyConnector { http, matcher: Proxy::matcher(self.config.proxy.as_ref()).map(Arc::new) };
+            #[cfg(feature = "async-native-tls")]
+            let https = match &self.config.tls {
+                Some(tls) => {
+                    HttpsConnector::from((http, tokio_native_tls::TlsConnector::from(tls.async_native.clone())))
+                }
+                None => HttpsConnector::new_with_connector(http),
+            };
+            #[cfg(feature = "_async-rustls")]
+            let https = match &self.config.tls {
+                Some(tls) => HttpsConnectorBuilder::new().with_tls_config(tls.async_rustls.clone()),
+                None => HttpsConnectorBuilder::new().with_webpki_roots(),
+            }
+            .https_or_http()
+            .enable_http1()
+            .wrap_connector(http);
+            let mut builder = Client::builder(TokioExecutor::new());
+            builder.pool_timer(TokioTimer::new());
+            if let Some(timeout) = self.config.pool_idle_timeout {
+                builder.pool_idle_timeout(timeout);
+            }
+            if let Some(max_idle) = self.config.pool_max_idle_per_host {
+                builder.pool_max_idle_per_host(max_idle);
+            }
+            builder.build(https)
+        })
+    }
+
+    /// Return the shared sync agent, building it on first use.
+    ///
+    /// 返回共享的同步 Agent，首次使用时构建。
+    #[cfg(feature = "_sync-base")]
+    pub fn sync_agent(&self) -> &ureq::Agent {
+        self.sync_agent.get_or_init(|| {
+            let mut builder = ureq::Agent::config_builder()
+                .http_status_as_error(false)
+                // OSS redirects carry the target endpoint in the error body, not a `Location` header
+                .max_redirects(0)
+                .timeout_connect(self.config.connect_timeout)
+                .proxy(Proxy::ureq_proxy(self.config.proxy.as_ref()))
+                .tls_config(match &self.config.tls {
+                    Some(tls) => tls.sync.clone(),
+                    None => TlsConfig::default_ureq_tls_config(),
+                });
+            if let Some(timeout) = self.config.pool_idle_timeout {
+                builder = builder.max_idle_age(timeout);
+            }
+            if let Some(max_idle) = self.config.pool_max_idle_per_host {
+                builder = builder.max_idle_connections_per_host(max_idle).max_idle_connections(max_idle.max(10));
+            }
+            let config = builder.build();
+            match self.config.read_timeout {
+                Some(timeout) => ureq::Agent::with_parts(
+                    config,
+                    DefaultConnector::new().chain(IdleTimeoutConnector(timeout)),
+                    DefaultResolver::default(),
+                ),
+                None => config.into(),
+            }
+        })
+    }
+}
+
+/// Connector that caps every wait for response data at the read timeout, so a stalled response
+/// body fails instead of blocking forever.
+///
+/// 将每次等待响应数据的时间限制在读取超时之内的连接器，响应体停滞时报错而不是永久阻塞。
+#[cfg(feature = "_sync-base")]
+#[derive(Debug)]
+struct IdleTimeoutConnector(Duration);
+
+#[cfg(feature = "_sync-base")]
+impl Connector<Box<dyn SyncTransport>> for IdleTimeoutConnector {
+    type Out = IdleTimeout;
+
+    fn connect(
+        &self,
+        _details: &ConnectionDetails,
+        chained: Option<Box<dyn SyncTransport>>,
+    ) -> Result<Option<IdleTimeout>, ureq::Error> {
+        Ok(chained.map(|inner| IdleTimeout { inner, timeout: self.0 }))
+    }
+}
+
+#[cfg(feature = "_sync-base")]
+#[derive(Debug)]
+struct IdleTimeout {
+    inner: Box<dyn SyncTransport>,
+    timeout: Duration,
+}
+
+#[cfg(feature = "_sync-base")]
+impl SyncTransport for IdleTimeout {
+    fn buffers(&mut self) -> &mut dyn Buffers {
+        self.inner.buffers()
+    }
+
+    fn transmit_output(&mut self, amount: usize, timeout: NextTimeout) -> Result<(), ureq::Error> {
+        self.inner.transmit_output(amount, timeout)
+    }
+
+    fn await_input(&mut self, timeout: NextTimeout) -> Result<bool, ureq::Error> {
+        let idle = TimeoutDuration::Exact(self.timeout);
+        let timeout = if idle < timeout.after { NextTimeout { after: idle, ..timeout } } else { timeout };
+        self.inner.await_input(timeout)
+    }
+
+    fn is_open(&mut self) -> bool {
+        self.inner.is_open()
+    }
+
+    fn is_tls(&self) -> bool {
+        self.inner.is_tls()
+    }
+}
+
+/// TCP connector that tunnels through an HTTP `CONNECT` or SOCKS5 proxy when the matcher selects
+/// one for the destination; TLS is layered on top by the wrapping `HttpsConnector`.
+///
+/// 当匹配器为目标选中代理时，通过 HTTP `CONNECT` 或 SOCKS5 代理建立隧道的 TCP 连接器；
+/// TLS 由外层的 `HttpsConnector` 负责。
+#[cfg(feature = "_async-base")]
+#[derive(Clone)]
+pub(crate) struct ProxyConnector {
+    http: HttpConnector,
+    matcher: Option<Arc<Matcher>>,
+}
+
+#[cfg(feature = "_async-base")]
+type BoxError = Box<dyn StdError + Send + Sync>;
+
+#[cfg(feature = "_async-base")]
+impl tower_service::Service<http::Uri> for ProxyConnector {
+    type Response = TokioIo<TcpStream>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+        self.http.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, dst: http::Uri) -> Self::Future {
+        let intercept = self.matcher.as_ref().and_then(|matcher| matcher.intercept(&dst));
+        let mut http = self.http.clone();
+        Box::pin(async move {
+            let Some(proxy) = intercept else {
+                return http.call(dst).await.map_err(Into::into);
+            };
+            match proxy.uri().scheme_str() {
+                Some("http") => {
+                    let mut tunnel = Tunnel::new(proxy.uri().clone(), http);
+                    if let Some(auth) = proxy.basic_auth() {
+                        tunnel = tunnel.with_auth(auth.clone());
+                    }
+                    tunnel.call(dst).await.map_err(Into::into)
+                }
+                Some(scheme @ ("socks5" | "socks5h")) => {
+                    let mut socks = SocksV5::new(proxy.uri().clone(), http).local_dns(scheme == "socks5");
+                    if let Some((username, password)) = proxy.raw_auth() {
+                        socks = socks.with_auth(username.to_string(), password.to_string());
+                    }
+                    socks.call(dst).await.map_err(Into::into)
+                }
+                _ => Err(format!("unsupported proxy scheme in {}", proxy.uri()).into()),
+            }
+        })
+    }
+}
+
+/// Response body that enforces the read timeout between chunks and the overall request deadline.
+///
+/// It implements `http_body::Body`; use `http_body_util::BodyExt` to collect or stream it.
+///
+/// 在数据块之间强制读取超时并限制整体截止时间的响应体。
+///
+/// 实现了 `http_body::Body`，可借助 `http_body_util::BodyExt` 收集或流式读取。
+#[cfg(feature = "_async-base")]
+#[derive(Debug)]
+pub struct ResponseBody {
+    inner: Incoming,
+    read_timeout: Option<Duration>,
+    deadline: Option<Instant>,
+    timer: Option<Pin<Box<Sleep>>>,
+    timed_out: bool,
+}
+
+#[cfg(feature = "_async-base")]
+impl ResponseBody {
+    pub(crate) fn new(inner: Incoming, read_timeout: Option<Duration>, deadline: Option<Instant>) -> Self {
+        ResponseBody { inner, read_timeout, deadline, timer: None, timed_out: false }
+    }
+
+    fn next_wakeup(&self) -> Option<Instant> {
+        let idle = self.read_timeout.map(|timeout| Instant::now() + timeout);
+        match (idle, self.deadline) {
+            (Some(idle), Some(deadline)) => Some(idle.min(deadline)),
+            (idle, deadline) => idle.or(deadline),
+        }
+    }
+}
+
+#[cfg(feature = "_async-base")]
+impl Body for ResponseBody {
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_frame(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, Error>>> {
+        let this = &mut *self;
+        // a timed-out body ends after reporting the error once
+        if this.timed_out {
+            return Poll::Ready(None);
+        }
+        match Pin::new(&mut this.inner).poll_frame(cx) {
+            Poll::Ready(frame) => {
+                this.timer = None;
+                Poll::Ready(frame.map(|frame| frame.map_err(Error::from)))
+            }
+            Poll::Pending => {
+                if this.timer.is_none() {
+                    match this.next_wakeup() {
+                        Some(at) => this.timer = Some(Box::pin(tokio::time::sleep_until(at))),
+                        None => return Poll::Pending,
+                    }
+                }
+                match this.timer.as_mut().map(|timer| timer.as_mut().poll(cx)) {
+                    Some(Poll::Ready(())) => {
+                        this.timed_out = true;
+                        Poll::Ready(Some(Err(Error::Timeout)))
+                    }
+                    _ => Poll::Pending,
+                }
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.timed_out || self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
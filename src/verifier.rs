@@ -0,0 +1,428 @@
+//! Verification of V4 `Authorization` headers and pre-signed URLs, for gateways that accept
+//! OSS-signed requests.
+//!
+//! 校验 V4 `Authorization` 头与预签名 URL，适用于接收 OSS 签名请求的网关。
+use crate::common::{AddressingStyle, signature_v4};
+use crate::signer::{self, ALGORITHM, UNSIGNED_PAYLOAD};
+use crate::{Credentials, Error};
+use aws_lc_rs::constant_time::verify_slices_are_equal;
+use http::{Method, Request, header};
+use time::{Duration, OffsetDateTime, PrimitiveDateTime, format_description};
+
+// OSS rejects pre-signed URLs that are valid for longer than seven days
+const MAX_EXPIRES: i64 = 7 * 24 * 3600;
+
+/// Outcome of verifying a signed request.
+///
+/// 签名请求的校验结果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    /// The signature is valid; carries the AccessKeyId that signed the request.
+    ///
+    /// 签名有效，携带签名所用的 AccessKeyId。
+    Valid(String),
+    /// The pre-signed URL has expired, or the header-signed request is too far from the current time.
+    ///
+    /// 预签名 URL 已过期，或请求头签名的请求时间与当前时间相差过大。
+    Expired(String),
+    /// The request is not signed, is malformed, uses an unknown key, or its signature does not match.
+    ///
+    /// 请求未签名、格式错误、使用了未知的密钥，或签名不匹配。
+    Mismatch(String),
+}
+
+impl Verification {
+    /// Whether the signature is valid.
+    ///
+    /// 签名是否有效。
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Verification::Valid(_))
+    }
+}
+
+/// Why a request was rejected, detailed enough for the emulator to answer with the matching OSS
+/// error code.
+///
+/// 请求被拒绝的原因，足以让模拟器返回对应的 OSS 错误码。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Rejection {
+    /// Unsigned or malformed request.
+    Invalid(String),
+    UnknownAccessKey(String),
+    /// Header-signed request too far from the current time.
+    Skewed(String),
+    /// Expired pre-signed URL.
+    Expired(String),
+    SignatureMismatch {
+        access_key_id: String,
+        string_to_sign: String,
+    },
+}
+
+impl From<Rejection> for Verification {
+    fn from(rejection: Rejection) -> Self {
+        match rejection {
+            Rejection::Invalid(reason) => Verification::Mismatch(reason),
+            Rejection::UnknownAccessKey(access_key_id) => {
+                Verification::Mismatch(format!("AccessKeyId {} is unknown", access_key_id))
+            }
+            Rejection::Skewed(reason) | Rejection::Expired(reason) => Verification::Expired(reason),
+            Rejection::SignatureMismatch { .. } => Verification::Mismatch("signature does not match".to_string()),
+        }
+    }
+}
+
+/// Verifies requests signed with Signature V4, either in the `Authorization` header or as a
+/// pre-signed URL, using the same canonicalization as the requests this crate sends.
+///
+/// The bucket is resolved from the request URI as for [`Signer`](crate::Signer). The payload hash in
+/// `x-oss-content-sha256` is signed but not compared with the body, which `verify` does not read.
+///
+/// 校验使用 V4 签名的请求（签名位于 `Authorization` 头或预签名 URL 中），规范化方式与本库发送的请求一致。
+///
+/// Bucket 的解析方式与 [`Signer`](crate::Signer) 相同。`x-oss-content-sha256` 中的请求体哈希参与签名校验，
+/// 但不会与请求体比对，`verify` 不读取请求体。
+///
+/// ```
+/// use aliyun_oss_rs::{Credentials, Signer, Verifier};
+/// use time::{Duration, OffsetDateTime};
+///
+/// let credentials = Credentials::new("<AccessKeyId>", "<AccessKeySecret>");
+/// let request = http::Request::get("https://example-bucket.oss-cn-hangzhou.aliyuncs.com/rust.png").body(()).unwrap();
+/// let now = OffsetDateTime::now_utc();
+/// let url = Signer::new(credentials.clone(), "cn-hangzhou").presign_url(&request, now, Duration::hours(1)).unwrap();
+///
+/// let verifier = Verifier::new(credentials, "cn-hangzhou");
+/// assert!(verifier.verify_url(&http::Method::GET, &url, now).is_valid());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Verifier {
+    credentials: Vec<Credentials>,
+    region: String,
+    addressing_style: AddressingStyle,
+    bucket: Option<String>,
+    max_skew: Duration,
+}
+
+impl Verifier {
+    /// Create a verifier that accepts signatures made with `credentials` for `region`.
+    ///
+    /// 创建校验器，接受使用 `credentials` 为 `region` 计算的签名。
+    pub fn new(credentials: Credentials, region: impl Into<String>) -> Self {
+        Verifier {
+            credentials: vec![credentials],
+            region: region.into(),
+            addressing_style: AddressingStyle::VirtualHosted,
+            bucket: None,
+            max_skew: Duration::minutes(15),
+        }
+    }
+
+    /// Also accept signatures made with these credentials, for example while rotating keys.
+    ///
+    /// 同时接受使用这些凭证计算的签名，例如在轮换密钥期间。
+    pub fn add_credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials.push(credentials);
+        self
+    }
+
+    /// Read the bucket from the first path segment instead of the host name.
+    ///
+    /// 从路径的第一段而不是主机名中读取 Bucket。
+    pub fn set_addressing_style(mut self, style: AddressingStyle) -> Self {
+        self.addressing_style = style;
+        self
+    }
+
+    /// Use a fixed bucket, for custom domains; the whole path is then the object key.
+    ///
+    /// 使用固定的 Bucket，适用于自定义域名；此时整个路径即为对象名。
+    pub fn set_bucket(mut self, bucket: impl Into<String>) -> Self {
+        self.bucket = Some(bucket.into());
+        self
+    }
+
+    /// How far `x-oss-date` of a header-signed request may be from `now`; 15 minutes by default,
+    /// as on OSS.
+    ///
+    /// 请求头签名的请求中 `x-oss-date` 与 `now` 允许的最大偏差，默认与 OSS 相同为 15 分钟。
+    pub fn set_max_skew(mut self, max_skew: Duration) -> Self {
+        self.max_skew = max_skew;
+        self
+    }
+
+    /// Verify a request signed in its `Authorization` header or in its query.
+    ///
+    /// 校验在 `Authorization` 头或查询参数中签名的请求。
+    pub fn verify<B>(&self, request: &Request<B>, now: OffsetDateTime) -> Verification {
+        match self.check(request, now) {
+            Ok(access_key_id) => Verification::Valid(access_key_id),
+            Err(rejection) => rejection.into(),
+        }
+    }
+
+    /// Like [`verify`](Self::verify), with the detailed reason of a rejection.
+    ///
+    /// 与 [`verify`](Self::verify) 相同，但返回详细的拒绝原因。
+    pub(crate) fn check<B>(&self, request: &Request<B>, now: OffsetDateTime) -> Result<String, Rejection> {
+        let queries = signer::query_pairs(request.uri().query().unwrap_or_default());
+        let query = |name: &str| queries.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str());
+        match query("x-oss-signature") {
+            Some(signature) => self.verify_query(request, &query, signature, now),
+            None => self.verify_header(request, now),
+        }
+    }
+
+    /// Verify a pre-signed URL used with `method`. URLs that sign additional headers must be checked
+    /// with [`verify`](Self::verify) on the actual request.
+    ///
+    /// 校验以 `method` 访问的预签名 URL。签名了额外请求头的 URL 需对实际请求调用 [`verify`](Self::verify) 校验。
+    pub fn verify_url(&self, method: &Method, url: &str, now: OffsetDateTime) -> Verification {
+        match Request::builder().method(method).uri(url).body(()) {
+            Ok(request) => self.verify(&request, now),
+            Err(_) => invalid("invalid URL").into(),
+        }
+    }
+
+    fn verify_header<B>(&self, request: &Request<B>, now: OffsetDateTime) -> Result<String, Rejection> {
+        let header = |name| request.headers().get(name).and_then(|value| value.to_str().ok()).unwrap_or_default();
+        let authorization = header(header::AUTHORIZATION.as_str());
+        if authorization.is_empty() {
+            return Err(invalid("request is not signed"));
+        }
+        let fields = authorization
+            .strip_prefix(ALGORITHM)
+            .filter(|fields| fields.starts_with(' '))
+            .ok_or_else(|| invalid("Authorization is not an OSS4-HMAC-SHA256 signature"))?;
+        let field = |name: &str| {
+            fields.split(',').find_map(|item| item.trim().strip_prefix(name)?.strip_prefix('=')).unwrap_or_default()
+        };
+        let (credentials, date_short) = self.credentials(field("Credential"))?;
+        let date = header("x-oss-date");
+        let signed_at = self.signed_at(date, date_short)?;
+        if (now - signed_at).abs() > self.max_skew {
+            return Err(Rejection::Skewed(format!("x-oss-date {} is too far from the current time", date)));
+        }
+        let hashed_payload = header("x-oss-content-sha256");
+        if hashed_payload.is_empty() {
+            return Err(invalid("x-oss-content-sha256 is missing"));
+        }
+        check_security_token(credentials, header("x-oss-security-token"))?;
+
+        let additional_headers = signer::additional_headers(field("AdditionalHeaders").split(';'));
+        let canonical_request = self.canonical_request(request, &additional_headers, hashed_payload)?;
+        check_signature(credentials, &self.region, date, date_short, &canonical_request, field("Signature"))
+    }
+
+    fn verify_query<'q, B>(
+        &self,
+        request: &Request<B>,
+        query: &impl Fn(&str) -> Option<&'q str>,
+        signature: &str,
+        now: OffsetDateTime,
+    ) -> Result<String, Rejection> {
+        if query("x-oss-signature-version") != Some(ALGORITHM) {
+            return Err(invalid("x-oss-signature-version is not OSS4-HMAC-SHA256"));
+        }
+        let (credentials, date_short) = self.credentials(query("x-oss-credential").unwrap_or_default())?;
+        let date = query("x-oss-date").unwrap_or_default();
+        let signed_at = self.signed_at(date, date_short)?;
+        let expires = query("x-oss-expires")
+            .and_then(|expires| expires.parse::<i64>().ok())
+            .filter(|expires| (1..=MAX_EXPIRES).contains(expires))
+            .ok_or_else(|| invalid("x-oss-expires must be between 1 and 604800 seconds"))?;
+        if signed_at - now > self.max_skew {
+            return Err(invalid(format!("x-oss-date {} is in the future", date)));
+        }
+        if now > signed_at + Duration::seconds(expires) {
+            return Err(Rejection::Expired(format!("URL signed at {} expired after {} seconds", date, expires)));
+        }
+        check_security_token(credentials, query("x-oss-security-token").unwrap_or_default())?;
+
+        let additional_headers =
+            signer::additional_headers(query("x-oss-additional-headers").unwrap_or_default().split(';'));
+        let canonical_request = self.canonical_request(request, &additional_headers, UNSIGNED_PAYLOAD)?;
+        check_signature(credentials, &self.region, date, date_short, &canonical_request, signature)
+    }
+
+    // Matching credentials and the signing date of `<AccessKeyId>/<date>/<region>/oss/aliyun_v4_request`
+    fn credentials<'c>(&self, credential: &'c str) -> Result<(&Credentials, &'c str), Rejection> {
+        let parts: Vec<&str> = credential.split('/').collect();
+        let [access_key_id, date_short, region, "oss", "aliyun_v4_request"] = parts.as_slice() else {
+            return Err(invalid("credential is malformed"));
+        };
+        if *region != self.region {
+            return Err(invalid(format!("credential is scoped to region {}", region)));
+        }
+        let credentials = self
+            .credentials
+            .iter()
+            .find(|credentials| credentials.access_key_id() == *access_key_id)
+            .ok_or_else(|| Rejection::UnknownAccessKey(access_key_id.to_string()))?;
+        Ok((credentials, date_short))
+    }
+
+    fn signed_at(&self, date: &str, date_short: &str) -> Result<OffsetDateTime, Rejection> {
+        let format = format_description::parse("[year][month][day]T[hour][minute][second]Z").expect("valid format");
+        let signed_at = PrimitiveDateTime::parse(date, &format)
+            .map_err(|_| invalid("x-oss-date is missing or malformed"))?
+            .assume_utc();
+        if !date.starts_with(date_short) {
+            return Err(invalid("x-oss-date does not match the credential date"));
+        }
+        Ok(signed_at)
+    }
+
+    fn canonical_request<B>(
+        &self,
+        request: &Request<B>,
+        additional_headers: &[String],
+        hashed_payload: &str,
+    ) -> Result<String, Rejection> {
+        signer::http_canonical_request(
+            request,
+            self.addressing_style,
+            self.bucket.as_deref(),
+            &[],
+            additional_headers,
+            hashed_payload,
+        )
+        .map_err(|error: Error| invalid(error.to_string()))
+    }
+}
+
+fn check_security_token(credentials: &Credentials, token: &str) -> Result<(), Rejection> {
+    match credentials.security_token() {
+        Some(expected) if expected != token => Err(invalid("security token does not match")),
+        _ => Ok(()),
+    }
+}
+
+fn check_signature(
+    credentials: &Credentials,
+    region: &str,
+    date: &str,
+    date_short: &str,
+    canonical_request: &str,
+    signature: &str,
+) -> Result<String, Rejection> {
+    let string_to_sign = signer::string_to_sign(date, date_short, region, canonical_request);
+    let expected = signature_v4(credentials.access_key_secret(), date_short, region, &string_to_sign);
+    if verify_slices_are_equal(expected.as_bytes(), signature.as_bytes()).is_err() {
+        return Err(Rejection::SignatureMismatch {
+            access_key_id: credentials.access_key_id().to_string(),
+            string_to_sign,
+        });
+    }
+    Ok(credentials.access_key_id().to_string())
+}
+
+fn invalid(reason: impl Into<String>) -> Rejection {
+    Rejection::Invalid(reason.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Signer;
+
+    fn mismatch(reason: &str) -> Verification {
+        Verification::Mismatch(reason.to_string())
+    }
+
+    fn at(timestamp: i64) -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(timestamp).unwrap()
+    }
+
+    fn request() -> Request<()> {
+        Request::get("https://bucket.oss-cn-hangzhou.aliyuncs.com/dir/a%20b.txt?versionId=1")
+            .header("Range", "bytes=0-9")
+            .body(())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_verify_authorization_header() {
+        let credentials = Credentials::new("ak", "sk");
+        let signer = Signer::new(credentials.clone(), "cn-hangzhou").set_additional_headers(["range"]);
+        let verifier = Verifier::new(Credentials::new("old", "secret"), "cn-hangzhou").add_credentials(credentials);
+        let mut signed = request();
+        signer.sign(&mut signed, at(1702743657)).unwrap();
+        assert_eq!(verifier.verify(&signed, at(1702743657 + 60)), Verification::Valid("ak".to_string()));
+        assert!(matches!(verifier.verify(&signed, at(1702743657 + 3600)), Verification::Expired(_)));
+
+        let mut tampered = signed.clone();
+        tampered.headers_mut().insert("range", "bytes=0-99".parse().unwrap());
+        assert_eq!(verifier.verify(&tampered, at(1702743657)), mismatch("signature does not match"));
+        let mut tampered = signed.clone();
+        *tampered.uri_mut() = "https://bucket.oss-cn-hangzhou.aliyuncs.com/dir/a%20b.txt".parse().unwrap();
+        assert_eq!(verifier.verify(&tampered, at(1702743657)), mismatch("signature does not match"));
+
+        let verifier = Verifier::new(Credentials::new("ak", "other"), "cn-hangzhou");
+        assert_eq!(verifier.verify(&signed, at(1702743657)), mismatch("signature does not match"));
+        let verifier = Verifier::new(Credentials::new("ak", "sk"), "cn-beijing");
+        assert_eq!(verifier.verify(&signed, at(1702743657)), mismatch("credential is scoped to region cn-hangzhou"));
+        assert_eq!(verifier.verify(&request(), at(1702743657)), mismatch("request is not signed"));
+    }
+
+    #[test]
+    fn test_verify_presigned_url() {
+        let credentials = Credentials::new("ak", "sk").with_security_token("token");
+        let signer = Signer::new(credentials.clone(), "cn-hangzhou");
+        let verifier = Verifier::new(credentials, "cn-hangzhou");
+        let url = signer.presign_url(&request(), at(1702781677), Duration::seconds(600)).unwrap();
+        assert!(verifier.verify_url(&Method::GET, &url, at(1702781677 + 600)).is_valid());
+        assert_eq!(
+            verifier.verify_url(&Method::GET, &url, at(1702781677 + 601)),
+            Verification::Expired("URL signed at 20231217T025437Z expired after 600 seconds".to_string())
+        );
+        assert_eq!(verifier.verify_url(&Method::PUT, &url, at(1702781677)), mismatch("signature does not match"));
+        assert_eq!(
+            verifier.verify_url(&Method::GET, &url.replace("a%20b.txt", "c.txt"), at(1702781677)),
+            mismatch("signature does not match")
+        );
+        assert_eq!(
+            verifier.verify_url(
+                &Method::GET,
+                &url.replace("x-oss-security-token=token", "x-oss-security-token=other"),
+                at(1702781677)
+            ),
+            mismatch("security token does not match")
+        );
+        assert_eq!(
+            verifier.verify_url(
+                &Method::GET,
+                &url.replace("x-oss-expires=600", "x-oss-expires=604801"),
+                at(1702781677)
+            ),
+            mismatch("x-oss-expires must be between 1 and 604800 seconds")
+        );
+
+        // signed headers must be present on the verified request
+        let signer = signer.set_additional_headers(["range"]);
+        let url = signer.presign_url(&request(), at(1702781677), Duration::seconds(600)).unwrap();
+        let mut presigned = request();
+        *presigned.uri_mut() = url.parse().unwrap();
+        assert!(verifier.verify(&presigned, at(1702781677)).is_valid());
+        assert!(!verifier.verify_url(&Method::GET, &url, at(1702781677)).is_valid());
+    }
+
+    #[cfg(feature = "_async-base")]
+    #[test]
+    fn test_verify_client_presigned_url() {
+        let client = crate::OssClient::new("ak", "sk", "cn-hangzhou");
+        let object = client.bucket("bucket").object("dir/a b+c.txt");
+        let expires = OffsetDateTime::now_utc() + Duration::hours(1);
+        let url = object
+            .presign(Method::PUT)
+            .set_content_type("text/plain")
+            .insert_query("tagging", "")
+            .url(expires)
+            .unwrap();
+        let verifier = Verifier::new(Credentials::new("ak", "sk"), "cn-hangzhou");
+        let mut request = Request::put(url.as_str()).header("Content-Type", "text/plain").body(()).unwrap();
+        assert!(verifier.verify(&request, OffsetDateTime::now_utc()).is_valid());
+        request.headers_mut().insert("content-type", "image/png".parse().unwrap());
+        assert_eq!(verifier.verify(&request, OffsetDateTime::now_utc()), mismatch("signature does not match"));
+    }
+}